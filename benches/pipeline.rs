@@ -0,0 +1,200 @@
+// benchmarks the pipeline stages on the workloads, run with `cargo bench`
+// set `ABSURD_BENCH_RECORD=1` to record the current timings as the baselines
+use std::{collections::HashMap, fs, path::Path, process::Command, time::Duration};
+
+/// times each workload is run, the median is reported
+const RUNS: usize = 5;
+/// how much slower than its baseline a workload may get
+const TOLERANCE: f64 = 1.5;
+/// absolute slack on top of the tolerance, so short runs don't fail on noise
+const SLACK: Duration = Duration::from_millis(5);
+const BASELINES: &str = "benches/baselines.txt";
+const STAGES: [&str; 4] = ["scanning", "parsing", "resolving", "interpreting"];
+
+/// workload file, the sizes it is run with and its expected output for a size
+type Workload = (&'static str, [usize; 3], fn(usize) -> String);
+
+const WORKLOADS: [Workload; 3] = [
+    ("arithmetic.abs", [5000, 20000, 80000], arithmetic),
+    ("control_flow.abs", [10, 15, 20], control_flow),
+    ("allocation.abs", [100, 250, 500], allocation),
+];
+
+fn arithmetic(size: usize) -> String {
+    // numbers are `f32` in the interpreter
+    let sum: f32 = (0..size)
+        .map(|i| i as f32)
+        .map(|i| ((i * 3.0) - (i / 2.0)) % 7.0)
+        .sum();
+    format!("{sum}\n")
+}
+
+fn control_flow(size: usize) -> String {
+    let (mut a, mut b) = (0u64, 1u64);
+    for _ in 0..size {
+        (a, b) = (b, a + b);
+    }
+    // ranges are inclusive
+    format!("{}\n{a}\n", (size * 7 + 1).min(50))
+}
+
+fn allocation(size: usize) -> String {
+    let last = (size * 2 - 2) as f32;
+    format!("{}\n", last * last)
+}
+
+fn main() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let record = std::env::var("ABSURD_BENCH_RECORD").is_ok_and(|v| v == "1");
+    let baselines = if record {
+        HashMap::new()
+    } else {
+        read_baselines(&root.join(BASELINES))
+    };
+    let mut recorded = String::from("# <workload>/<size> <median total in microseconds>\n");
+    let mut slower = vec![];
+
+    println!(
+        "{:<28}{:>14}{:>14}{:>14}{:>14}{:>14}{:>14}",
+        "workload", STAGES[0], STAGES[1], STAGES[2], STAGES[3], "total", "baseline"
+    );
+    for (name, sizes, expected) in WORKLOADS {
+        let source = fs::read_to_string(root.join("benches/workloads").join(name))
+            .expect("failed to read workload");
+        for size in sizes {
+            let label = format!("{name}/{size}");
+            let file = std::env::temp_dir().join(format!("absurd_bench_{size}_{name}"));
+            fs::write(&file, format!("let size = {size};\n{source}"))
+                .expect("failed to write workload");
+            let expected = expected(size);
+
+            let mut runs: HashMap<&str, Vec<Duration>> = HashMap::new();
+            for _ in 0..RUNS {
+                let output = Command::new(env!("CARGO_BIN_EXE_absurd"))
+                    .current_dir(root)
+                    .arg("run")
+                    .arg(&file)
+                    .arg("--log")
+                    .output()
+                    .expect("failed to run absurd");
+                let stdout = strip_colors(&String::from_utf8_lossy(&output.stdout));
+                let (timings, program) = split_log(&stdout);
+                assert_eq!(program, expected, "unexpected output of '{label}'");
+                for (stage, duration) in timings {
+                    runs.entry(stage).or_default().push(duration);
+                }
+            }
+            let _ = fs::remove_file(&file);
+
+            let mut row = format!("{label:<28}");
+            for stage in STAGES.iter().chain(["total"].iter()) {
+                let duration = median(runs.get_mut(stage).expect("missing stage timing"));
+                row.push_str(&format!("{:>14}", format!("{duration:.2?}")));
+            }
+            let total = median(runs.get_mut("total").unwrap());
+            if record {
+                recorded.push_str(&format!("{label} {}\n", total.as_micros()));
+                println!("{row}");
+                continue;
+            }
+
+            let baseline = *baselines.get(&label).unwrap_or_else(|| {
+                panic!("no baseline for '{label}', record them with ABSURD_BENCH_RECORD=1")
+            });
+            row.push_str(&format!("{:>14}", format!("{baseline:.2?}")));
+            println!("{row}");
+            if total > baseline.mul_f64(TOLERANCE) + SLACK {
+                slower.push(format!(
+                    "'{label}' took {total:.2?}, baseline is {baseline:.2?}"
+                ));
+            }
+        }
+    }
+
+    if record {
+        fs::write(root.join(BASELINES), recorded).expect("failed to write the baselines");
+        return;
+    }
+    assert!(
+        slower.is_empty(),
+        "slower than {TOLERANCE}x the baseline:\n{}",
+        slower.join("\n")
+    );
+}
+
+/// reads the `<workload>/<size> <microseconds>` lines of the baselines
+fn read_baselines(path: &Path) -> HashMap<String, Duration> {
+    let text = fs::read_to_string(path)
+        .unwrap_or_else(|_| panic!("missing {BASELINES}, record it with ABSURD_BENCH_RECORD=1"));
+    text.lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (label, micros) = line.split_once(' ').expect("invalid baseline");
+            let micros = micros.trim().parse().expect("invalid baseline duration");
+            (label.to_string(), Duration::from_micros(micros))
+        })
+        .collect()
+}
+
+/// separates the `--log` timings from the program's output
+fn split_log(stdout: &str) -> (Vec<(&'static str, Duration)>, String) {
+    let mut timings = vec![];
+    let mut program = String::new();
+    for line in stdout.lines() {
+        if let Some(duration) = line.strip_prefix("total time elapsed: ") {
+            timings.push(("total", parse_duration(duration)));
+            continue;
+        }
+        if let Some(stage) = STAGES
+            .iter()
+            .find(|stage| line.starts_with(&format!("completed {stage} in ")))
+        {
+            let duration = line.rsplit(' ').next().unwrap();
+            timings.push((*stage, parse_duration(duration)));
+            continue;
+        }
+        // logs are either indented or stage headers
+        if line.starts_with("  ") || STAGES.iter().any(|s| line == format!("{s}...")) {
+            continue;
+        }
+        program.push_str(line);
+        program.push('\n');
+    }
+    (timings, program)
+}
+
+/// parses the `Debug` format of `Duration`, `1.5ms`
+fn parse_duration(text: &str) -> Duration {
+    let split = text
+        .find(|c: char| c.is_alphabetic())
+        .expect("missing duration unit");
+    let (value, unit) = text.split_at(split);
+    let value: f64 = value.parse().expect("invalid duration");
+    let nanos = match unit {
+        "ns" => 1.0,
+        "µs" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => panic!("unknown duration unit '{unit}'"),
+    };
+    Duration::from_nanos((value * nanos) as u64)
+}
+
+fn median(durations: &mut [Duration]) -> Duration {
+    durations.sort();
+    durations[durations.len() / 2]
+}
+
+fn strip_colors(text: &str) -> String {
+    let mut plain = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            // skips `ESC[...m`
+            chars.by_ref().find(|c| *c == 'm');
+        } else {
+            plain.push(c);
+        }
+    }
+    plain
+}
@@ -1,8 +1,18 @@
-use super::{Base, LiteralKind, Token, TokenType::*};
+use super::{Base, FuncBody, LiteralKind, Token, TokenType::*};
 use crate::ast::LiteralType;
+use crate::interpreter::expr::Expression;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 
 impl LiteralType {
+    /// numbers, strings, chars and booleans, named by the primitive types
+    pub fn is_primitive(&self) -> bool {
+        matches!(
+            self,
+            Self::Number(_) | Self::String(_) | Self::Char(_) | Self::Boolean(_)
+        )
+    }
+
     pub fn type_name(&self) -> String {
         match self {
             Self::Enum { .. } => "enum".to_string(),
@@ -11,11 +21,14 @@ impl LiteralType {
             Self::Number(_) => "number".to_string(),
             Self::String(_) => "string".to_string(),
             Self::Char(_) => "char".to_string(),
+            Self::Bytes(_) => "bytes".to_string(),
             Self::Boolean(_) => "bool".to_string(),
             Self::Vec(_) => "vector".to_string(),
             Self::Func(_) => "function".to_string(),
             Self::Void => "void".to_string(),
             Self::DeclrFunc(_) => "declared function".to_string(),
+            Self::Quote(_) => "quote".to_string(),
+            Self::Mod(_) => "module".to_string(),
             Self::Null => "null".to_string(),
         }
     }
@@ -38,6 +51,11 @@ impl LiteralType {
                 c.to_string().as_str(),
                 Some(LiteralKind::Char { value: c.clone() }),
             ),
+            Self::Bytes(b) => Token::empty(
+                BytesLit,
+                &format!("b\"{}\"", b.escape_ascii()),
+                Some(LiteralKind::Bytes { value: b.clone() }),
+            ),
             Self::Boolean(b) => Token::empty(
                 BoolIdent,
                 b.to_string().as_str(),
@@ -54,6 +72,7 @@ impl LiteralType {
             Self::Number(val) => *val != 0.0,
             Self::String(val) => !val.is_empty(),
             Self::Char(val) => *val != '\0',
+            Self::Bytes(val) => !val.is_empty(),
             Self::Boolean(val) => *val,
             Self::Null => false,
             Self::Vec(val) => !val.is_empty(),
@@ -67,6 +86,109 @@ impl LiteralType {
     }
 }
 
+/// structural equality, vectors, tuples, records and enum values are compared by their items
+/// numbers follow IEEE-754, `NaN` is unequal to every value, itself included
+impl PartialEq for LiteralType {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Number(a), Self::Number(b)) => number_bits(*a) == number_bits(*b),
+            (Self::String(a), Self::String(b)) => a == b,
+            (Self::Char(a), Self::Char(b)) => a == b,
+            (Self::Bytes(a), Self::Bytes(b)) => a == b,
+            (Self::Boolean(a), Self::Boolean(b)) => a == b,
+            (Self::Null, Self::Null) | (Self::Void, Self::Void) => true,
+            (Self::Vec(a), Self::Vec(b)) | (Self::Tuple(a), Self::Tuple(b)) => a == b,
+            // fields are found by name, `{x: 1, y: 2} == {y: 2, x: 1}`
+            (Self::Record(a), Self::Record(b)) => {
+                a.len() == b.len()
+                    && a.iter().all(|(name, a)| {
+                        b.iter().any(|(b_name, b)| {
+                            b_name == name
+                                && match (a, b) {
+                                    // fields hold expressions, values are compared without their ids
+                                    (
+                                        Expression::Value { value: a, .. },
+                                        Expression::Value { value: b, .. },
+                                    ) => a == b,
+                                    (a, b) => a == b,
+                                }
+                        })
+                    })
+            }
+            (Self::Func(a), Self::Func(b)) => a == b,
+            (Self::DeclrFunc(a), Self::DeclrFunc(b)) => a == b,
+            (Self::Quote(a), Self::Quote(b)) => a == b,
+            (Self::Mod(a), Self::Mod(b)) => a == b,
+            (
+                Self::Enum {
+                    parent: a_parent,
+                    name: a_name,
+                    value: a,
+                },
+                Self::Enum {
+                    parent: b_parent,
+                    name: b_name,
+                    value: b,
+                },
+            ) => a_parent.lexeme == b_parent.lexeme && a_name.lexeme == b_name.lexeme && a == b,
+            _ => false,
+        }
+    }
+}
+
+impl std::cmp::Eq for LiteralType {}
+
+/// the bits numbers are compared and hashed by, so equality stays reflexive,
+/// every `NaN` equals the others and `-0.0` equals `0.0`
+fn number_bits(n: f32) -> u32 {
+    if n.is_nan() {
+        f32::NAN.to_bits()
+    } else if n == 0.0 {
+        0f32.to_bits()
+    } else {
+        n.to_bits()
+    }
+}
+
+/// hashes values consistently with their equality, for using them as keys
+impl Hash for LiteralType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Self::Number(n) => number_bits(*n).hash(state),
+            Self::String(s) => s.hash(state),
+            Self::Char(c) => c.hash(state),
+            Self::Bytes(b) => b.hash(state),
+            Self::Boolean(b) => b.hash(state),
+            Self::Vec(items) | Self::Tuple(items) => items.hash(state),
+            // in name order, the order fields are written in doesn't change the hash
+            Self::Record(fields) => {
+                let mut fields: Vec<_> = fields.iter().collect();
+                fields.sort_by(|(a, _), (b, _)| a.cmp(b));
+                fields.into_iter().for_each(|(name, value)| {
+                    name.hash(state);
+                    if let Expression::Value { value, .. } = value {
+                        value.hash(state);
+                    }
+                })
+            }
+            Self::Func(func) => func.name.hash(state),
+            Self::DeclrFunc(func) => func.name.hash(state),
+            Self::Mod(module) => module.name.hash(state),
+            Self::Enum {
+                parent,
+                name,
+                value,
+            } => {
+                parent.lexeme.hash(state);
+                name.lexeme.hash(state);
+                value.hash(state);
+            }
+            Self::Null | Self::Void | Self::Quote(_) => {}
+        }
+    }
+}
+
 impl fmt::Display for LiteralType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -103,6 +225,7 @@ impl fmt::Display for LiteralType {
             Self::Number(val) => write!(f, "{}", val),
             Self::String(val) => write!(f, "{}", val),
             Self::Char(val) => write!(f, "{}", val),
+            Self::Bytes(val) => write!(f, "b\"{}\"", val.escape_ascii()),
             Self::Boolean(val) => write!(f, "{}", val),
             Self::Null => write!(f, "null"),
             Self::Vec(val) => {
@@ -116,8 +239,76 @@ impl fmt::Display for LiteralType {
                 write!(f, "[{}]", s)
             }
             Self::Void => write!(f, "void"),
-            Self::Func(func) => write!(f, "{:?}", func.name),
-            Self::DeclrFunc(declr_func) => write!(f, "{}()", declr_func.name),
+            // closures are named after the `func` keyword, which can't name a function
+            Self::Func(func) if func.name == "func" => write!(f, "<func>"),
+            Self::Func(func) => write!(f, "<func {}>", func.name),
+            Self::DeclrFunc(declr_func) => write!(f, "<func {}>", declr_func.name),
+            Self::Quote(quote) => match &quote.body {
+                FuncBody::Expression(expr) => write!(f, "quote {{ {} }}", expr),
+                FuncBody::Statements(stmts) => write!(f, "quote {{ {} statements }}", stmts.len()),
+            },
+            Self::Mod(module) => write!(f, "mod {}", module.name),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{hash_map::DefaultHasher, HashMap, HashSet};
+
+    fn record(fields: &[(&str, f32)]) -> LiteralType {
+        LiteralType::Record(
+            fields
+                .iter()
+                .map(|(name, n)| {
+                    let value = LiteralType::Number(*n);
+                    (name.to_string(), Expression::Value { id: 0, value })
+                })
+                .collect(),
+        )
+    }
+
+    fn hash(value: &LiteralType) -> u64 {
+        let mut state = DefaultHasher::new();
+        value.hash(&mut state);
+        state.finish()
+    }
+
+    #[test]
+    fn nan_and_zero_as_keys() {
+        let mut set = HashSet::new();
+        set.insert(LiteralType::Number(f32::NAN));
+        set.insert(LiteralType::Number(-f32::NAN));
+        set.insert(LiteralType::Number(0.0));
+        set.insert(LiteralType::Number(-0.0));
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&LiteralType::Number(f32::NAN)));
+        assert!(!set.contains(&LiteralType::Number(1.0)));
+
+        let mut map = HashMap::new();
+        map.insert(LiteralType::Vec(vec![LiteralType::Number(f32::NAN)]), 1);
+        assert_eq!(
+            map.get(&LiteralType::Vec(vec![LiteralType::Number(f32::NAN)])),
+            Some(&1)
+        );
+    }
+
+    #[test]
+    fn record_hash_ignores_field_order() {
+        let a = record(&[("x", 1.0), ("y", 2.0)]);
+        let b = record(&[("y", 2.0), ("x", 1.0)]);
+        assert_eq!(hash(&a), hash(&b));
+        assert_ne!(hash(&a), hash(&record(&[("x", 2.0), ("y", 1.0)])));
+    }
+
+    #[test]
+    fn records_as_keys() {
+        let mut map = HashMap::new();
+        map.insert(record(&[("x", 1.0), ("y", 2.0)]), 1);
+        map.insert(record(&[("y", 2.0), ("x", 1.0)]), 2);
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.get(&record(&[("x", 1.0), ("y", 2.0)])), Some(&2));
+        assert_eq!(map.get(&record(&[("x", 1.0), ("z", 2.0)])), None);
+    }
+}
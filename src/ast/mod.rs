@@ -1,6 +1,7 @@
 pub mod literals;
 use std::{
     cell::RefCell,
+    collections::HashMap,
     fmt::{self, Debug},
     rc::Rc,
 };
@@ -48,6 +49,14 @@ pub enum TokenType {
     Async,
     /// await
     Await,
+    /// move
+    Move,
+    /// quote
+    Quote,
+    /// impl
+    Impl,
+    /// where
+    Where,
     /// pub
     Pub,
     /// mut
@@ -163,6 +172,8 @@ pub enum TokenType {
     Dot,
     /// ..
     DblDot,
+    /// ..<
+    DblDotLs,
     /// /
     Div,
     /// \
@@ -173,6 +184,8 @@ pub enum TokenType {
     RParse,
     /// ?
     Qstn,
+    /// @
+    At,
     /// |
     Pipe,
     /// ||
@@ -183,6 +196,8 @@ pub enum TokenType {
     CharLit,
     /// string, "hi"
     StrLit,
+    /// byte string, b"hi"
+    BytesLit,
     /// number, 5
     NumLit,
     /// true
@@ -213,11 +228,12 @@ pub enum Base {
     Hexadecimal = 16,
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, Clone)]
 pub enum LiteralType {
     Number(f32),
     String(String),
     Char(char),
+    Bytes(Vec<u8>),
     Boolean(bool),
     Null,
     Void,
@@ -226,6 +242,8 @@ pub enum LiteralType {
     Record(Vec<(String, Expression)>),
     Func(FuncImpl),
     DeclrFunc(DeclrFuncType),
+    Quote(QuoteImpl),
+    Mod(ModImpl),
     Enum {
         parent: Token,
         name: Token,
@@ -279,11 +297,14 @@ pub enum LiteralKind {
     Number { base: Base, value: f32 },
     String { value: String },
     Char { value: char },
+    Bytes { value: Vec<u8> },
     Bool { value: bool },
     Type(Box<TypeKind>),
     Null,
 }
 
+type MemoValueType = Rc<RefCell<HashMap<Vec<LiteralType>, LiteralType>>>;
+
 #[derive(Clone, PartialEq, Debug)]
 pub struct FuncImpl {
     pub name: String,
@@ -293,6 +314,23 @@ pub struct FuncImpl {
     pub is_async: bool,
     pub is_pub: bool,
     pub env: Rc<RefCell<Env>>,
+    /// arguments given by a partial application, `None` for the parameters left
+    pub bound: Vec<Option<LiteralType>>,
+    /// results of `@memo` functions by their arguments, shared by the copies of the function
+    pub memo: Option<MemoValueType>,
+}
+
+/// quoted code, evaluated in the environment `eval` is called from
+#[derive(Clone, PartialEq, Debug)]
+pub struct QuoteImpl {
+    pub body: FuncBody,
+}
+
+/// module namespace, bound by `use "src" as name;`
+#[derive(Clone, PartialEq, Debug)]
+pub struct ModImpl {
+    pub name: String,
+    pub env: Rc<RefCell<Env>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -339,6 +377,13 @@ pub enum CallType {
     Enum,
 }
 
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+pub enum TestKind {
+    Test,
+    Before,
+    After,
+}
+
 #[derive(Debug, PartialEq, Clone, Eq)]
 pub enum Destruct {
     Vector,
@@ -362,6 +407,10 @@ pub enum Statement {
         extends: Vec<Token>,
         is_strict: bool,
         fields: Vec<RecordField>,
+        /// computed fields, `get area -> number { ... }`, functions of `self`
+        getters: Vec<Statement>,
+        /// message of `@deprecated("...")`
+        deprecated: Option<String>,
     },
     Enum {
         name: Token,
@@ -371,6 +420,10 @@ pub enum Statement {
     Sh {
         cmd: String,
     },
+    Impl {
+        name: Token,
+        methods: Vec<Statement>,
+    },
     Type {
         name: Token,
         is_pub: bool,
@@ -391,14 +444,34 @@ pub enum Statement {
         is_pub: bool,
         pub_names: Vec<Token>,
         is_func: bool,
+        constraint: Option<Expression>,
     },
     Func {
         name: Token,
         value_type: Token,
         body: FuncBody,
         params: Vec<(Token, Token)>,
+        /// parameters before `/` can't be labeled and ones from `*` on must be,
+        /// `(a: T, /, b: T, *, c: T)` has 1 positional-only and starts keyword-only at 2
+        positional_only: usize,
+        keyword_only: Option<usize>,
+        /// type parameters with their bounds, `<T: comparable, U>`
+        generics: Vec<(Token, Option<Token>)>,
         is_async: bool,
         is_pub: bool,
+        is_inline: bool,
+        /// results are cached by argument values, `@memo`
+        is_memo: bool,
+        /// called with the command line arguments after the top level runs, `@entry`
+        is_entry: bool,
+        /// only depends on its arguments, and the functions it calls as well, `@pure`
+        is_pure: bool,
+        /// has effects, like printing or reading input, `@io`
+        is_io: bool,
+        /// run by `--test`, `@test`, or around each test, `@before` and `@after`
+        test: Option<TestKind>,
+        /// message of `@deprecated("...")`
+        deprecated: Option<String>,
     },
     If {
         cond: Expression,
@@ -426,8 +499,9 @@ pub enum Statement {
     Break {},
     Match {
         cond: Expression,
-        cases: Vec<(Expression, FuncBody)>,
-        def_case: FuncBody,
+        /// patterns with their guards, `Enum::A if x > 0 => ...`
+        cases: Vec<(Expression, Option<Expression>, FuncBody)>,
+        def_case: Option<FuncBody>,
     },
     Mod {
         src: String,
@@ -437,6 +511,7 @@ pub enum Statement {
         src: String,
         names: Vec<(Token, Option<Token>)>,
         all: bool,
+        alias: Option<Token>,
     },
 }
 
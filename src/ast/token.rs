@@ -49,6 +49,7 @@ impl TokenType {
             Self::DblColon => "::",
             Self::CharLit => "char literal",
             Self::StrLit => "string literal",
+            Self::BytesLit => "byte string literal",
             Self::NumLit => "number literal",
             Self::TrueLit => "true literal",
             Self::FalseLit => "false literal",
@@ -60,11 +61,13 @@ impl TokenType {
             Self::Comma => ",",
             Self::Dot => ".",
             Self::DblDot => "..",
+            Self::DblDotLs => "..<",
             Self::Div => "/",
             Self::Esc => "\\",
             Self::LParse => "\\{",
             Self::RParse => "\\}",
             Self::Qstn => "?",
+            Self::At => "@",
             Self::Pipe => "|",
             Self::Or => "||",
             Self::Ident => "identifier",
@@ -84,6 +87,10 @@ impl TokenType {
             Self::From => "from keyword",
             Self::Async => "async keyword",
             Self::Await => "await keyword",
+            Self::Move => "move keyword",
+            Self::Quote => "quote keyword",
+            Self::Impl => "impl keyword",
+            Self::Where => "where keyword",
             Self::Pub => "pub keyword",
             Self::Mut => "mut keyword",
             Self::Func => "function keyword",
@@ -1,14 +1,17 @@
 // bundles multiple parts together
 use coloredpp::Colorize;
-use std::{cell::RefCell, rc::Rc, time::Instant};
+use std::{cell::RefCell, fs, path::PathBuf, process::exit, rc::Rc, time::Instant};
 
 use crate::{
     ast::Statement,
-    errors::Error,
+    errors::{raw, set_source, Error},
+    hoister::{walk_stmts, Hoister, Node},
+    inliner::Inliner,
     interpreter::{env::Env, expr::Expression, Interpreter},
     manifest::Project,
     parser::{scanner::Scanner, Parser},
     resolver::Resolver,
+    transpiler::Transpiler,
 };
 
 pub fn parser(src: &str, err: Error, log: bool) -> Vec<Statement> {
@@ -29,21 +32,104 @@ pub fn parser(src: &str, err: Error, log: bool) -> Vec<Statement> {
         println!("{}", "parsing...".yellow());
         start = Some(Instant::now());
     }
-    let mut parser = Parser::new(tokens.clone(), err, log);
-    let stmts = parser.parse();
+    let mut parser = Parser::new(tokens.clone(), err.clone(), log);
+    let sync = &err.project().sync;
+    if !sync.is_empty() {
+        // `sync` holds source text, `";"`, `"let"`
+        let sync = sync
+            .iter()
+            .filter_map(|src| Scanner::new(src, err.clone(), false).next_token())
+            .map(|token| token.token)
+            .collect();
+        parser.sync_on(sync);
+    }
+    parser.max_depth(err.project().max_depth);
+    let stmts = match parser.parse() {
+        Ok(stmts) => stmts,
+        Err((_, errors)) => {
+            for error in errors {
+                err.clone()
+                    .recoverable()
+                    .throw(error.code, error.line, error.pos, error.args);
+            }
+            exit(1)
+        }
+    };
+    // scanning errors are reported along with the syntax errors
+    if lexer.has_errors() {
+        exit(1);
+    }
     if log {
         let parse_duration = start.unwrap().elapsed();
         let text = format!("{:?}", parse_duration);
         println!("{} {}", "completed parsing in".green(), text.blue());
     }
-    stmts
+    Hoister::new().hoist(Inliner::new(err).inline(stmts))
 }
 
 pub fn parse_expr(src: &str, err: Error) -> Expression {
     let mut lexer = Scanner::new(src, err.clone(), false);
-    let tokens = lexer.scan();
-    let mut parser = Parser::new(tokens.clone(), err, false);
-    parser.expr()
+    let tokens = lexer.scan().clone();
+    if lexer.has_errors() {
+        exit(1);
+    }
+    let mut parser = Parser::new(tokens, err.clone(), false);
+    match parser.parse_expression() {
+        Ok(expr) => expr,
+        Err(error) => {
+            err.throw(error.code, error.line, error.pos, error.args);
+            exit(1)
+        }
+    }
+}
+
+/// a part of an interpolated string
+pub enum Part {
+    Text(String),
+    /// source of an interpolated expression
+    Expr(String),
+}
+
+/// splits a string into its text and the `{expr}`s it interpolates, braces
+/// nest inside an expression, `{{` and `}}` outside of one are literal braces
+pub fn interpolation(s: &str) -> Vec<Part> {
+    let mut parts = vec![];
+    let mut text = String::new();
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '}' if chars.peek() == Some(&c) => {
+                chars.next();
+                text.push(c);
+            }
+            '{' => {
+                let mut expr = String::new();
+                let mut depth = 1;
+                for c in chars.by_ref() {
+                    match c {
+                        '{' => depth += 1,
+                        '}' => depth -= 1,
+                        _ => {}
+                    }
+                    if depth == 0 {
+                        break;
+                    }
+                    expr.push(c);
+                }
+                // an unclosed brace is kept as text
+                if depth > 0 {
+                    text.push('{');
+                    text.push_str(&expr);
+                } else {
+                    parts.push(Part::Text(std::mem::take(&mut text)));
+                    parts.push(Part::Expr(expr));
+                }
+            }
+            c => text.push(c),
+        }
+    }
+    parts.push(Part::Text(text));
+    parts
 }
 
 pub fn interpreter_raw(src: &str, project: Project, log: bool) {
@@ -73,7 +159,10 @@ pub fn interpreter_raw(src: &str, project: Project, log: bool) {
     }
 
     int.env.borrow_mut().resolve(locals);
+    set_source(src);
     int.interpret(stmts.iter().collect(), 0);
+    int.entry(&stmts);
+    int.tests(&stmts);
     if log {
         let interpreter_duration = start.unwrap().elapsed();
         let text = format!("{:?}", interpreter_duration);
@@ -84,6 +173,62 @@ pub fn interpreter_raw(src: &str, project: Project, log: bool) {
     }
 }
 
+pub fn transpiler_raw(src: &str, project: Project, target: &str) -> String {
+    let err = Error::new(src, project.clone());
+    let stmts = parser(src, err.clone(), project.log);
+    match target {
+        "js" => Transpiler::new(err).transpile(&stmts),
+        _ => {
+            raw(format!("unsupported target '{target}'").as_str());
+            exit(1);
+        }
+    }
+}
+
+/// the `mod` and `use` tree of a file as indented lines, without running it,
+/// a module already on the way from the entry file is a cycle and isn't followed
+pub fn graph_raw(src: &str, file: &str, project: Project) -> String {
+    let mut out = String::new();
+    graph(src, file, &project, &mut vec![], &mut out);
+    out
+}
+
+fn graph(src: &str, file: &str, project: &Project, path: &mut Vec<PathBuf>, out: &mut String) {
+    out.push_str(&format!("{}{}\n", "  ".repeat(path.len()), file));
+    path.push(fs::canonicalize(file).unwrap_or_else(|_| PathBuf::from(file)));
+    let stmts = parser(src, Error::new(src, project.clone()), false);
+    let mut deps = vec![];
+    walk_stmts(&stmts, &mut |node| match node {
+        Node::Stmt(Statement::Mod { src, .. }) => deps.push((src.trim_matches('"'), true)),
+        // other sources are imported from a `mod`, which is already an edge
+        Node::Stmt(Statement::Use { src, .. }) if src.contains("::") => {
+            deps.push((src.trim_matches('"'), false))
+        }
+        _ => {}
+    });
+    let indent = "  ".repeat(path.len());
+    for (dep, is_file) in deps {
+        if !is_file {
+            out.push_str(&format!("{indent}{dep}\n"));
+            continue;
+        }
+        // `mod` paths are relative to the working directory, as when running
+        let dep_path = fs::canonicalize(dep).unwrap_or_else(|_| PathBuf::from(dep));
+        if path.contains(&dep_path) {
+            out.push_str(&format!("{indent}{} {}\n", dep, "(cycle)".red()));
+            continue;
+        }
+        match fs::read_to_string(dep) {
+            Ok(src) => graph(&src, dep, project, path, out),
+            Err(_) => {
+                raw(format!("failed to open file '{dep}'").as_str());
+                exit(1);
+            }
+        }
+    }
+    path.pop();
+}
+
 pub fn interpreter_mod(
     src: &str,
     mod_src: Option<String>,
@@ -96,5 +241,8 @@ pub fn interpreter_mod(
     let mut resolver = Resolver::new(err);
     let locals = resolver.resolve(&stmts, &mut int.env);
     int.env.borrow_mut().resolve(locals);
-    int.interpret(stmts.iter().collect(), 0)
+    let main_src = set_source(src);
+    let env = int.interpret(stmts.iter().collect(), 0);
+    set_source(&main_src);
+    env
 }
@@ -6,17 +6,33 @@ use std::{
     process::{exit, Command, Stdio},
 };
 
-use crate::{bundler::interpreter_raw, errors::raw, manifest::Project, VERSION};
+use crate::{
+    bundler::{graph_raw, interpreter_raw, transpiler_raw},
+    errors::{explain, raw},
+    manifest::Project,
+    VERSION,
+};
 pub fn cli_new(project: &mut Project) {
     let mut program = CLI::new();
     program
         .name("Absurd")
         .version(VERSION)
         .description("The Absurd Programming Language")
+        // matched first, other options would take `--explain` and `--target` for their short names
+        .option("-x, --explain", "explain an error code (E0x301)")
+        .option("-j, --target", "transpile to the target instead of running (js)")
+        // before `--side-effects`, which would take it for the `s`
+        .option("-w, --deny-warnings", "report warnings as errors")
         .option("-s, --side-effects", "disable side-effects")
         .option("-l, --log", "enable logging mode")
         .option("-t, --test", "enable testing mode")
+        .option("-c, --cfg", "enable @cfg flags (linux,debug)")
         .arg("run", "run [file]", "interpret the file")
+        .arg(
+            "graph",
+            "graph [file]",
+            "print the modules the file uses, without running it",
+        )
         .arg("update", "update", "update to the latest version")
         .arg("ci", "ci", "enter source from the CLI")
         .arg(
@@ -40,20 +56,48 @@ pub fn cli_new(project: &mut Project) {
     if program.get("--log").is_some() {
         project.log = true
     }
+    if program.get("--deny-warnings").is_some() {
+        project.deny_warnings = true
+    }
+    if let Some(cfg) = program.get("--cfg") {
+        project.cfg.extend(
+            cfg[0]
+                .split(',')
+                .filter(|flag| !flag.is_empty())
+                .map(|flag| flag.to_string()),
+        );
+    }
+    if let Some(code) = program.get("--explain") {
+        if code[0].is_empty() {
+            raw("expected an error code");
+        }
+        explain(&code[0]);
+        exit(0);
+    }
     if program.get("update").is_some() {
         update();
         exit(0);
     }
 
+    let target = program.get("--target").map(|t| t[0].clone());
     let run = program.get("run");
-    if run.is_some() {
-        run_file(
-            run.unwrap().get(0).expect("expected a file").clone(),
-            project.clone(),
-        );
+    if let Some(values) = run {
+        let file = values.first().expect("expected a file").clone();
+        // the values of an argument end with an empty one
+        project.args = values[1..values.len().saturating_sub(1).max(1)].to_vec();
+        match target {
+            Some(target) => transpile_file(file, target, project.clone()),
+            None => run_file(file, project.clone()),
+        }
         exit(1);
     }
 
+    if let Some(graph) = program.get("graph") {
+        let file = graph.first().expect("expected a file");
+        print!("{}", graph_raw(&read_file(file), file, project.clone()));
+        exit(0);
+    }
+
     let add = program.get("add");
     if add.is_some() {
         add_mod(add.unwrap().get(0), add.unwrap().get(1));
@@ -77,8 +121,8 @@ pub fn cli_new(project: &mut Project) {
     }
 }
 
-fn run_file(f: String, project: Project) {
-    let mut file = match File::open(f.clone()) {
+fn read_file(f: &str) -> String {
+    let mut file = match File::open(f) {
         Ok(s) => s,
         Err(_) => {
             raw(format!("failed to open file '{f}'").as_str());
@@ -93,10 +137,19 @@ fn run_file(f: String, project: Project) {
             exit(1);
         }
     }
+    contents
+}
 
+fn run_file(f: String, project: Project) {
+    let contents = read_file(&f);
     interpreter_raw(&contents, project.clone(), project.log);
 }
 
+fn transpile_file(f: String, target: String, project: Project) {
+    let contents = read_file(&f);
+    print!("{}", transpiler_raw(&contents, project, &target));
+}
+
 fn run_code(code: String, project: Project) {
     interpreter_raw(&code, project.clone(), project.log);
 }
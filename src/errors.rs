@@ -1,9 +1,20 @@
 // handles Absurd errors
 use super::manifest::Project;
 use coloredpp::Colorize;
+use std::cell::RefCell;
 use std::process::exit;
 use ErrorCode::*;
 
+thread_local! {
+    /// the source of the running file, for errors created without it, like runtime ones
+    static SOURCE: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// sets the source errors without one print their lines from, returns the previous one
+pub fn set_source(src: &str) -> String {
+    SOURCE.with(|source| source.replace(src.to_string()))
+}
+
 #[derive(Debug, Clone)]
 pub enum ErrorCode {
     /// `syntax error (E0x101): malformed or unterminated char`
@@ -23,8 +34,32 @@ pub enum ErrorCode {
     /// `syntax error (E0x106): expected a token '{0}'`
     /// - {0}: token
     E0x106,
-    /// `sybtax error (E0x107): invalid assignment target`
+    /// `syntax error (E0x107): can't assign to '{0}', only variables, indices and fields can be assigned to`
+    /// - {0}: assignment target
     E0x107,
+    /// `syntax error (E0x108): expected {0} values, got {1}`
+    /// - {0}: expected count
+    /// - {1}: actual count
+    E0x108,
+    /// `syntax error (E0x109): positional argument after a labeled argument`
+    E0x109,
+    /// `syntax error (E0x110): unknown character '{0}'`
+    /// - {0}: character
+    E0x110,
+    /// `syntax error (E0x111): unknown escape sequence '{0}'`
+    /// - {0}: escape sequence
+    E0x111,
+    /// `syntax error (E0x112): misplaced '_' in the number '{0}'`
+    /// - {0}: number
+    E0x112,
+    /// `syntax error (E0x113): unterminated block comment`
+    E0x113,
+    /// `syntax error (E0x114): loop count must be a whole number, got '{0}'`
+    /// - {0}: count
+    E0x114,
+    /// `syntax error (E0x115): expression too deeply nested, more than {0} levels`
+    /// - {0}: maximum depth
+    E0x115,
     /// `runtime error (E0x301): type mismatch: expected '{0}', got '{1}'`
     /// - {0}: expected type
     /// - {1}: actual type
@@ -67,16 +102,130 @@ pub enum ErrorCode {
     /// `runtime error (E0x416): failed to get values from {0}`
     /// - {0}: source
     E0x416,
+    /// `runtime error (E0x417): '{0}' doesn't satisfy the constraint '{1}'`
+    /// - {0}: variable
+    /// - {1}: constraint
+    E0x417,
+    /// `runtime error (E0x418): unreachable default case, every item of '{0}' is matched`
+    /// - {0}: enum name
+    E0x418,
+    /// `runtime error (E0x419): match on '{0}' doesn't cover {1}`
+    /// - {0}: enum name
+    /// - {1}: missing items
+    E0x419,
+    /// `runtime error (E0x420): '{0}' is read in its own initializer`
+    /// - {0}: variable
+    E0x420,
+    /// `runtime error (E0x421): '{0}' is used before its declaration`
+    /// - {0}: variable
+    E0x421,
+    /// `runtime error (E0x422): '{0}' has no parameter '{1}'`
+    /// - {0}: function name
+    /// - {1}: label
+    E0x422,
+    /// `runtime error (E0x423): argument '{0}' of '{1}' is given more than once`
+    /// - {0}: parameter name
+    /// - {1}: function name
+    E0x423,
+    /// `runtime warning (E0x424): '{0}' is deprecated: {1}`
+    /// - {0}: function or record name
+    /// - {1}: message, left out if empty
+    E0x424,
+    /// `runtime error (E0x425): the local '{0}' shadows the function '{0}', and can't be called`
+    /// - {0}: name
+    E0x425,
+    /// `runtime error (E0x426): '{0}' is used after being moved`
+    /// - {0}: name
+    E0x426,
+    /// `runtime error (E0x427): '{0}' can't be moved inside a loop or function it's declared outside of`
+    /// - {0}: name
+    E0x427,
+    /// `runtime error (E0x428): unknown bound '{0}', expected one of: {1}`
+    /// - {0}: bound
+    /// - {1}: known bounds
+    E0x428,
+    /// `runtime error (E0x429): the type '{0}' doesn't satisfy the bound '{1}: {2}'`
+    /// - {0}: type of the argument
+    /// - {1}: type parameter
+    /// - {2}: bound
+    E0x429,
+    /// `runtime error (E0x430): '{0}' of '{1}' is positional-only and can't be labeled`
+    /// - {0}: parameter name
+    /// - {1}: function name
+    E0x430,
+    /// `runtime error (E0x431): '{0}' of '{1}' is keyword-only and must be labeled`
+    /// - {0}: parameter name
+    /// - {1}: function name
+    E0x431,
+    /// `runtime error (E0x432): '{0}' is declared as '{1}' but its value is '{2}', did you mean 'let {0}: {2}'`
+    /// - {0}: variable name
+    /// - {1}: declared type
+    /// - {2}: type of the value
+    E0x432,
+    /// `runtime error (E0x433): item {0} of '{1}' expected '{2}', found '{3}'`
+    /// - {0}: index path, `[1][0]`
+    /// - {1}: variable name
+    /// - {2}: expected type
+    /// - {3}: found type
+    E0x433,
+    /// `runtime error (E0x434): '{0}' can't be memoized: {1}`
+    /// - {0}: function name
+    /// - {1}: reason
+    E0x434,
+    /// `runtime error (E0x435): '{0}' can't be applied to '{1}' and '{2}'`
+    /// - {0}: operator
+    /// - {1}: type of the left operand
+    /// - {2}: type of the right operand
+    E0x435,
+    /// `runtime error (E0x436): can't access '{0}', {1}`
+    /// - {0}: indexed or assigned path
+    /// - {1}: reason
+    E0x436,
+    /// `runtime error (E0x437): the char range '{0}'..'{1}' is reversed, its start is after its end`
+    /// - {0}: start
+    /// - {1}: end
+    E0x437,
+    /// `runtime error (E0x438): '{0}' can't be the entry: {1}`
+    /// - {0}: function name
+    /// - {1}: reason
+    E0x438,
+    /// `runtime error (E0x439): '{0}' can't be pure: {1}`
+    /// - {0}: function name
+    /// - {1}: reason
+    E0x439,
+    /// `runtime error (E0x440): '{0}' can't be '@{1}': {2}`
+    /// - {0}: function name
+    /// - {1}: annotation
+    /// - {2}: reason
+    E0x440,
+    /// `runtime warning (E0x441): '{0}' is never read`
+    /// - {0}: variable name
+    E0x441,
+    /// `runtime error (E0x442): the type parameter '{0}' is '{1}' from an earlier argument, got '{2}'`
+    /// - {0}: type parameter
+    /// - {1}: type of the earlier argument
+    /// - {2}: type of the argument
+    E0x442,
     /// `environment error (E0x501): failed to get a distance`
     E0x501,
     /// `environment error (E0x502): failed to resolve a value`
     E0x502,
+    /// `compile error (E0x601): unsupported construct for the {0} target: {1}`
+    /// - {0}: target
+    /// - {1}: construct
+    E0x601,
+    /// `compile error (E0x602): '{0}' can not be inlined: {1}`
+    /// - {0}: function name
+    /// - {1}: reason
+    E0x602,
 }
 
 #[derive(Debug, Clone)]
 pub struct Error {
     source: String,
     project: Project,
+    is_recoverable: bool,
+    is_warning: bool,
 }
 
 impl Error {
@@ -84,7 +233,29 @@ impl Error {
         Error {
             source: src.to_string(),
             project,
+            is_recoverable: false,
+            is_warning: false,
+        }
+    }
+
+    /// reports errors without exiting, so the caller can continue
+    pub fn recoverable(mut self) -> Self {
+        self.is_recoverable = true;
+        self
+    }
+
+    /// reports warnings without exiting, unless warnings are denied
+    pub fn warn(&self, code: ErrorCode, line: usize, pos: (usize, usize), args: Vec<String>) {
+        if self.project.deny_warnings {
+            return self.throw(code, line, pos, args);
         }
+        let mut warning = self.clone();
+        warning.is_warning = true;
+        warning.throw(code, line, pos, args);
+    }
+
+    pub fn project(&self) -> &Project {
+        &self.project
     }
 
     pub fn throw(&self, code: ErrorCode, line: usize, pos: (usize, usize), args: Vec<String>) {
@@ -128,7 +299,66 @@ impl Error {
             E0x107 => self.error(
                 107,
                 "syntax",
-                format!("invalid assignment target"),
+                format!(
+                    "can't assign to '{}', only variables, indices and fields can be assigned to",
+                    args[0]
+                ),
+                line,
+                pos,
+            ),
+            E0x108 => self.error(
+                108,
+                "syntax",
+                format!("expected {} values, got {}", args[0], args[1]),
+                line,
+                pos,
+            ),
+            E0x109 => self.error(
+                109,
+                "syntax",
+                "positional argument after a labeled argument".to_string(),
+                line,
+                pos,
+            ),
+            E0x110 => self.error(
+                110,
+                "syntax",
+                format!("unknown character '{}'", args[0]),
+                line,
+                pos,
+            ),
+            E0x111 => self.error(
+                111,
+                "syntax",
+                format!("unknown escape sequence '{}'", args[0]),
+                line,
+                pos,
+            ),
+            E0x112 => self.error(
+                112,
+                "syntax",
+                format!("misplaced '_' in the number '{}'", args[0]),
+                line,
+                pos,
+            ),
+            E0x113 => self.error(
+                113,
+                "syntax",
+                "unterminated block comment".to_string(),
+                line,
+                pos,
+            ),
+            E0x114 => self.error(
+                114,
+                "syntax",
+                format!("loop count must be a whole number, got '{}'", args[0]),
+                line,
+                pos,
+            ),
+            E0x115 => self.error(
+                115,
+                "syntax",
+                format!("expression too deeply nested, more than {} levels", args[0]),
                 line,
                 pos,
             ),
@@ -154,20 +384,20 @@ impl Error {
                 pos,
             ),
             E0x304 => self.error(
-                306,
+                304,
                 "runtime",
                 format!("failed to resolve '{}'", args[0]),
                 line,
                 pos,
             ),
             E0x305 => self.error(
-                307,
+                305,
                 "runtime",
                 format!("'{}' is already declared", args[0]),
                 line,
                 pos,
             ),
-            E0x306 => self.error(308, "runtime", "stack underflow".to_string(), line, pos),
+            E0x306 => self.error(306, "runtime", "stack underflow".to_string(), line, pos),
             E0x401 => self.error(
                 401,
                 "runtime",
@@ -253,6 +483,227 @@ impl Error {
                 line,
                 pos,
             ),
+            E0x417 => self.error(
+                417,
+                "runtime",
+                format!("'{}' doesn't satisfy the constraint '{}'", args[0], args[1]),
+                line,
+                pos,
+            ),
+            E0x418 => self.error(
+                418,
+                "runtime",
+                format!(
+                    "unreachable default case, every item of '{}' is matched",
+                    args[0]
+                ),
+                line,
+                pos,
+            ),
+            E0x419 => self.error(
+                419,
+                "runtime",
+                format!("match on '{}' doesn't cover {}", args[0], args[1]),
+                line,
+                pos,
+            ),
+            E0x420 => self.error(
+                420,
+                "runtime",
+                format!("'{}' is read in its own initializer", args[0]),
+                line,
+                pos,
+            ),
+            E0x421 => self.error(
+                421,
+                "runtime",
+                format!("'{}' is used before its declaration", args[0]),
+                line,
+                pos,
+            ),
+            E0x422 => self.error(
+                422,
+                "runtime",
+                format!("'{}' has no parameter '{}'", args[0], args[1]),
+                line,
+                pos,
+            ),
+            E0x423 => self.error(
+                423,
+                "runtime",
+                format!(
+                    "argument '{}' of '{}' is given more than once",
+                    args[0], args[1]
+                ),
+                line,
+                pos,
+            ),
+            E0x424 => self.error(
+                424,
+                "runtime",
+                match args[1].is_empty() {
+                    true => format!("'{}' is deprecated", args[0]),
+                    false => format!("'{}' is deprecated: {}", args[0], args[1]),
+                },
+                line,
+                pos,
+            ),
+            E0x425 => self.error(
+                425,
+                "runtime",
+                format!(
+                    "the local '{}' shadows the function '{}', and can't be called",
+                    args[0], args[0]
+                ),
+                line,
+                pos,
+            ),
+            E0x426 => self.error(
+                426,
+                "runtime",
+                format!("'{}' is used after being moved", args[0]),
+                line,
+                pos,
+            ),
+            E0x427 => self.error(
+                427,
+                "runtime",
+                format!(
+                    "'{}' can't be moved inside a loop or function it's declared outside of",
+                    args[0]
+                ),
+                line,
+                pos,
+            ),
+            E0x428 => self.error(
+                428,
+                "runtime",
+                format!("unknown bound '{}', expected one of: {}", args[0], args[1]),
+                line,
+                pos,
+            ),
+            E0x429 => self.error(
+                429,
+                "runtime",
+                format!(
+                    "the type '{}' doesn't satisfy the bound '{}: {}'",
+                    args[0], args[1], args[2]
+                ),
+                line,
+                pos,
+            ),
+            E0x430 => self.error(
+                430,
+                "runtime",
+                format!(
+                    "'{}' of '{}' is positional-only and can't be labeled",
+                    args[0], args[1]
+                ),
+                line,
+                pos,
+            ),
+            E0x431 => self.error(
+                431,
+                "runtime",
+                format!(
+                    "'{}' of '{}' is keyword-only and must be labeled",
+                    args[0], args[1]
+                ),
+                line,
+                pos,
+            ),
+            E0x432 => self.error(
+                432,
+                "runtime",
+                format!(
+                    "'{0}' is declared as '{1}' but its value is '{2}', did you mean 'let {0}: {2}'",
+                    args[0], args[1], args[2]
+                ),
+                line,
+                pos,
+            ),
+            E0x433 => self.error(
+                433,
+                "runtime",
+                format!(
+                    "item {} of '{}' expected '{}', found '{}'",
+                    args[0], args[1], args[2], args[3]
+                ),
+                line,
+                pos,
+            ),
+            E0x434 => self.error(
+                434,
+                "runtime",
+                format!("'{}' can't be memoized: {}", args[0], args[1]),
+                line,
+                pos,
+            ),
+            E0x435 => self.error(
+                435,
+                "runtime",
+                format!(
+                    "'{}' can't be applied to '{}' and '{}'",
+                    args[0], args[1], args[2]
+                ),
+                line,
+                pos,
+            ),
+            E0x436 => self.error(
+                436,
+                "runtime",
+                format!("can't access '{}', {}", args[0], args[1]),
+                line,
+                pos,
+            ),
+            E0x437 => self.error(
+                437,
+                "runtime",
+                format!(
+                    "the char range '{}'..'{}' is reversed, its start is after its end",
+                    args[0], args[1]
+                ),
+                line,
+                pos,
+            ),
+            E0x438 => self.error(
+                438,
+                "runtime",
+                format!("'{}' can't be the entry: {}", args[0], args[1]),
+                line,
+                pos,
+            ),
+            E0x439 => self.error(
+                439,
+                "runtime",
+                format!("'{}' can't be pure: {}", args[0], args[1]),
+                line,
+                pos,
+            ),
+            E0x440 => self.error(
+                440,
+                "runtime",
+                format!("'{}' can't be '@{}': {}", args[0], args[1], args[2]),
+                line,
+                pos,
+            ),
+            E0x441 => self.error(
+                441,
+                "runtime",
+                format!("'{}' is never read", args[0]),
+                line,
+                pos,
+            ),
+            E0x442 => self.error(
+                442,
+                "runtime",
+                format!(
+                    "the type parameter '{}' is '{}' from an earlier argument, got '{}'",
+                    args[0], args[1], args[2]
+                ),
+                line,
+                pos,
+            ),
             E0x501 => self.error(
                 501,
                 "environment",
@@ -267,6 +718,23 @@ impl Error {
                 line,
                 pos,
             ),
+            E0x601 => self.error(
+                601,
+                "compile",
+                format!(
+                    "unsupported construct for the {} target: {}",
+                    args[0], args[1]
+                ),
+                line,
+                pos,
+            ),
+            E0x602 => self.error(
+                602,
+                "compile",
+                format!("'{}' can not be inlined: {}", args[0], args[1]),
+                line,
+                pos,
+            ),
         };
     }
 }
@@ -286,28 +754,17 @@ impl Error {
     }
 
     pub fn print_lines(&self, line: usize, pos: (usize, usize)) {
-        let lines: Vec<&str> = self.source.lines().collect();
-
-        let snippet = self.project.snippet as isize;
-
-        if snippet < 0 {
+        // negative snippet size disables the snippet
+        if self.project.snippet < 0 {
             return;
         }
 
-        let start = if line as isize > snippet {
-            line - snippet as usize
-        } else {
-            1
-        };
-        let end = if line + snippet as usize <= lines.len() {
-            line + snippet as usize
-        } else {
-            lines.len()
+        let source = match self.source.is_empty() {
+            true => SOURCE.with(|source| source.borrow().clone()),
+            false => self.source.clone(),
         };
-
-        for i in start..=end {
+        for (i, line_content) in context(&source, line, self.project.snippet as usize) {
             if i == line {
-                let line_content = lines[i - 1];
                 let (before, to_underscore, after) =
                     self.split_line_at_char_indices(line_content, pos);
 
@@ -318,21 +775,37 @@ impl Error {
                     to_underscore.red().bold().underline(),
                     after.red().bold()
                 );
+                eprintln!(
+                    "{} | {}",
+                    " ".repeat(i.to_string().len()),
+                    carets(line_content, pos).red().bold()
+                );
             } else {
-                eprintln!("{} | {}", i.to_string().yellow(), &lines[i - 1].red());
+                eprintln!("{} | {}", i.to_string().yellow(), line_content.red());
             }
         }
     }
 
     pub fn panic(&self, kind: &str, code: usize, msg: String) {
         let err_code = format!("E0x{}", code).yellow();
+        if self.is_warning {
+            let head = format!("{} warning {}:", kind, err_code);
+            eprintln!(
+                "{} {}",
+                head.fg_hex("#fcc449").bold(),
+                msg.fg_hex("#fcc449")
+            );
+            return;
+        }
         let head = format!("{} error {}:", kind, err_code);
         eprintln!(
             "{} {}",
             head.fg_hex("#fc4949").bold(),
             msg.fg_hex("#fc4949")
         );
-        exit(0);
+        if !self.is_recoverable {
+            exit(0);
+        }
     }
 
     fn split_line_at_char_indices(
@@ -340,19 +813,924 @@ impl Error {
         line: &str,
         pos: (usize, usize),
     ) -> (String, String, String) {
+        let start = char_index(line, pos.0);
+        let end = char_index(line, pos.1).max(start);
         let mut chars = line.chars();
-        let before: String = if pos.0 > 0 {
-            chars.by_ref().take(pos.0 - 1).collect()
-        } else {
-            String::new()
-        };
-        let to_underscore: String = chars.by_ref().take(pos.1 - pos.0).collect();
+        let before: String = chars.by_ref().take(start).collect();
+        let to_underscore: String = chars.by_ref().take(end - start).collect();
         let after: String = chars.collect();
         (before, to_underscore, after)
     }
 }
 
+/// marks the columns of `pos` in the line with `^`, keeping tabs so the marks line up,
+/// a span going past the end of the line, like one ending on a later line, is cut there
+pub fn carets(line: &str, pos: (usize, usize)) -> String {
+    let start = char_index(line, pos.0);
+    let end = char_index(line, pos.1).max(start + 1);
+    let indent: String = line
+        .chars()
+        .take(start)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    format!("{}{}", indent, "^".repeat(end - start))
+}
+
+/// the index of the char at a column, columns start at 1 and tabs take 4 like in the scanner
+fn char_index(line: &str, column: usize) -> usize {
+    let mut col = 1;
+    let mut index = 0;
+    for c in line.chars() {
+        if col >= column {
+            break;
+        }
+        col += if c == '\t' { 4 } else { 1 };
+        index += 1;
+    }
+    index
+}
+
+/// returns up to `size` lines before and after the line,
+/// numbered from 1 and clamped to the start and end of the source
+pub fn context(source: &str, line: usize, size: usize) -> Vec<(usize, &str)> {
+    let lines: Vec<&str> = source.lines().collect();
+    if line == 0 || line > lines.len() {
+        return vec![];
+    }
+    let start = line.saturating_sub(size).max(1);
+    let end = (line + size).min(lines.len());
+    (start..=end).map(|i| (i, lines[i - 1])).collect()
+}
+
+/// explanations printed by `--explain`, as (code, title, description)
+pub const EXPLANATIONS: &[(&str, &str, &str)] = &[
+    (
+        "E0x101",
+        "syntax error: malformed or unterminated char",
+        "a char literal must hold exactly one character between single quotes.
+
+erroneous code:
+    let c: char = 'ab';
+
+fix it by keeping a single character, or use a string:
+    let c: char = 'a';
+    let s: string = \"ab\";",
+    ),
+    (
+        "E0x102",
+        "syntax error: unterminated string",
+        "a string literal was opened but the file ended before its closing quote.
+
+erroneous code:
+    print(\"hello);
+
+fix it by closing the string:
+    print(\"hello\");",
+    ),
+    (
+        "E0x103",
+        "syntax error: unexpected token",
+        "the parser found a token that can not start or continue an expression here.
+
+erroneous code:
+    let x = 1 + ;
+
+fix it by completing the expression:
+    let x = 1 + 2;",
+    ),
+    (
+        "E0x104",
+        "syntax error: failed to unwrap a number",
+        "a number was expected, such as a tuple index or a number literal,
+but the token could not be read as one.",
+    ),
+    (
+        "E0x105",
+        "syntax error: failed to parse",
+        "the parser expected a specific construct, such as a block statement,
+and found something else. check the code around the reported position
+for a missing or misplaced part of the construct.",
+    ),
+    (
+        "E0x106",
+        "syntax error: expected a token",
+        "a required token, such as a closing parenthesis or a semicolon, is missing.
+
+erroneous code:
+    let x = 1
+
+fix it by adding the missing token:
+    let x = 1;",
+    ),
+    (
+        "E0x107",
+        "syntax error: invalid assignment target",
+        "only variables, their indices and their fields can be assigned to, the left
+side of '=' is something else, like a literal or a call.
+
+erroneous code:
+    1 + 2 = x;
+    5 = x;
+
+fix it by assigning to a variable, an index or a field:
+    let mut y = 0;
+    y = x;
+    let mut v = [1, 2];
+    v[0] = x;",
+    ),
+    (
+        "E0x108",
+        "syntax error: expected a number of values",
+        "a declaration with several typed names got a different number of values.
+
+erroneous code:
+    let a: number, b: number = 1;
+
+fix it by giving one value per name:
+    let a: number, b: number = 1, 2;",
+    ),
+    (
+        "E0x109",
+        "syntax error: positional argument after a labeled argument",
+        "labeled arguments are matched to parameters by name, so every positional
+argument has to come before them.
+
+erroneous code:
+    area(w: 2, 3);
+
+fix it by moving the positional argument first, or by labeling it:
+    area(3, w: 2);
+    area(w: 2, h: 3);",
+    ),
+    (
+        "E0x110",
+        "syntax error: unknown character",
+        "the character isn't a part of any token. it is skipped, and scanning goes on
+to report the other errors.
+
+erroneous code:
+    let price = $5;
+
+fix it by removing the character:
+    let price = 5;",
+    ),
+    (
+        "E0x111",
+        "syntax error: unknown escape sequence",
+        "a backslash in a string or a char starts an escape sequence, the known ones
+are \\n, \\t, \\r, \\0, \\\\, \\\", \\', \\{, \\} and \\u{...} with a hexadecimal code point,
+\\{ and \\} are braces which aren't interpolated.
+
+erroneous code:
+    print(\"C:\\users\");
+
+fix it by escaping the backslash:
+    print(\"C:\\\\users\");",
+    ),
+    (
+        "E0x112",
+        "syntax error: misplaced '_' in a number",
+        "underscores in numbers only separate digits for readability, there must be a
+digit on both sides of each of them.
+
+erroneous code:
+    let million = 1__000_000_;
+
+fix it by keeping single underscores between digits:
+    let million = 1_000_000;",
+    ),
+    (
+        "E0x113",
+        "syntax error: unterminated block comment",
+        "a block comment was opened but the file ended before its closing '*/'.
+block comments nest, every '/*' inside of one needs its own '*/'.
+
+erroneous code:
+    /* outer /* inner */
+    print(1);
+
+fix it by closing every comment:
+    /* outer /* inner */ */
+    print(1);",
+    ),
+    (
+        "E0x114",
+        "syntax error: loop count isn't a whole number",
+        "`loop n { ... }` runs its body n times, so n has to be zero or a positive
+whole number.
+
+erroneous code:
+    loop 2.5 {
+        print(1);
+    }
+
+fix it by using a whole number:
+    loop 3 {
+        print(1);
+    }",
+    ),
+    (
+        "E0x115",
+        "syntax error: expression too deeply nested",
+        "expressions and blocks can only be nested so deep, 256 levels unless the
+`max_depth` setting in project.toml says otherwise, deeper nesting would
+overflow the stack.
+
+erroneous code:
+    let x = ((((((((1))))))));  # with max_depth = 4
+
+fix it by splitting the expression into variables:
+    let a = (((1)));
+    let x = (((a)));",
+    ),
+    (
+        "E0x301",
+        "runtime error: type mismatch",
+        "an operation received a value of a type it can not handle.
+
+erroneous code:
+    let x: number = \"a\";
+
+fix it by using a value of the expected type:
+    let x: number = 1;",
+    ),
+    (
+        "E0x302",
+        "runtime error: break statement not within a loop",
+        "'break' can only be used inside a loop body.
+
+erroneous code:
+    break;
+
+fix it by moving the break into a loop:
+    loop { break; }",
+    ),
+    (
+        "E0x303",
+        "runtime error: return statement not within a function",
+        "'return' can only be used inside a function body.
+
+erroneous code:
+    return 1;
+
+fix it by moving the return into a function:
+    func one() -> number { return 1; }",
+    ),
+    (
+        "E0x304",
+        "runtime error: failed to resolve",
+        "the name could not be found, for example a method that is not defined
+for the type of its receiver, or a member that a module doesn't have.
+
+erroneous code:
+    let x = 1;
+    x.nope();
+
+a variable or function which isn't declared anywhere is reported before the
+program runs.
+
+erroneous code:
+    let x = 1;
+    x.nope();
+    print(y);
+
+fix it by calling a method or member that exists, and declaring the names it reads.",
+    ),
+    (
+        "E0x305",
+        "runtime error: already declared",
+        "a name was declared twice in the same scope.
+
+erroneous code:
+    func f() -> void { let a = 1; let a = 2; }
+
+fix it by renaming one of them, or by assigning instead:
+    func f() -> void { let mut a = 1; a = 2; }",
+    ),
+    (
+        "E0x306",
+        "runtime error: stack underflow",
+        "the resolver tried to leave a scope it never entered. this is an internal
+error, please report it with the code that caused it.",
+    ),
+    (
+        "E0x401",
+        "runtime error: function must have one name",
+        "a callback bound to a variable can only be declared under a single name.
+fix it by declaring each callback in its own 'let' statement.",
+    ),
+    (
+        "E0x402",
+        "runtime error: public variable must have a value",
+        "a public variable is exported from its module and has to be initialized.
+fix it by giving it a value:
+    let pub x: number = 0;",
+    ),
+    (
+        "E0x404",
+        "runtime error: failed to create a function",
+        "the interpreter could not build a function from its declaration, usually
+because the declaration is missing its parameters or body.",
+    ),
+    (
+        "E0x405",
+        "runtime error: invalid number of arguments",
+        "a function was called with more or fewer arguments than it declares.
+
+erroneous code:
+    func add(a: number, b: number) -> number { return a + b; }
+    add(1);
+
+fix it by passing every argument:
+    add(1, 2);",
+    ),
+    (
+        "E0x406",
+        "runtime error: missing return statement",
+        "a function with a non-void return type has a path that ends without
+returning a value.
+
+erroneous code:
+    func sign(n: number) -> number { if n > 0 { return 1; } }
+
+fix it by returning on every path:
+    func sign(n: number) -> number { if n > 0 { return 1; } return 0; }",
+    ),
+    (
+        "E0x410",
+        "runtime error: can not assign to an immutable variable",
+        "variables are immutable unless declared with 'mut'.
+
+erroneous code:
+    let x = 1;
+    x = 2;
+
+fix it by declaring the variable as mutable:
+    let mut x = 1;
+    x = 2;",
+    ),
+    (
+        "E0x411",
+        "runtime error: can not assign to a public variable",
+        "public variables are shared with other modules and can not be reassigned.
+
+fix it by keeping a private copy that can change:
+    let pub x = 1;
+    let mut y = x;
+    y = 2;",
+    ),
+    (
+        "E0x412",
+        "runtime error: invalid type, while assigning to a variable",
+        "the assigned value doesn't match the type the variable was declared with.
+
+erroneous code:
+    let mut x: number = 1;
+    x = \"one\";
+
+fix it by assigning a value of the declared type:
+    x = 2;",
+    ),
+    (
+        "E0x413",
+        "runtime error: can not assign to a non-variable",
+        "the name refers to a function, an enum or another non-variable value.
+
+erroneous code:
+    func f() -> void {}
+    f = 1;
+
+fix it by assigning to a variable instead:
+    let mut g = 1;",
+    ),
+    (
+        "E0x414",
+        "runtime error: failed to assign a value",
+        "the interpreter could not store the value into the variable, usually
+because the variable is not declared in any reachable scope.
+
+erroneous code:
+    y = 1;
+
+fix it by declaring the variable first:
+    let mut y = 0;
+    y = 1;",
+    ),
+    (
+        "E0x415",
+        "runtime error: side effects are disabled",
+        "the program was run with side effects disabled ('--side-effects' or
+'side_effects = false' in project.toml) and tried to import a module.
+
+fix it by enabling side effects, or by removing the import.",
+    ),
+    (
+        "E0x416",
+        "runtime error: failed to get values from a module",
+        "the imported module could not be found, or doesn't export the name.
+
+erroneous code:
+    use missing from \"./nowhere\";
+
+fix it by importing a public name from an existing module:
+    use add from \"./math\";",
+    ),
+    (
+        "E0x417",
+        "runtime error: constraint not satisfied",
+        "the value of a variable doesn't satisfy the constraint on its type.
+
+erroneous code:
+    let n: number where n > 0 = -1;
+
+fix it by assigning a value that satisfies the constraint:
+    let n: number where n > 0 = 1;",
+    ),
+    (
+        "E0x418",
+        "runtime error: unreachable default case",
+        "every item of the enum is already matched, so the '_' case can never run.
+
+erroneous code:
+    enum Color { Red, Green }
+    match c { Color::Red => print(1), Color::Green => print(2), _ => print(3) }
+
+fix it by removing the default case:
+    match c { Color::Red => print(1), Color::Green => print(2) }",
+    ),
+    (
+        "E0x419",
+        "runtime error: match doesn't cover every item",
+        "a match on an enum has to handle every item, or have a '_' case.
+
+erroneous code:
+    enum Color { Red, Green }
+    match c { Color::Red => print(1), }
+
+fix it by matching the missing items, or by adding a default case:
+    match c { Color::Red => print(1), _ => print(2) }",
+    ),
+    (
+        "E0x420",
+        "runtime error: read in its own initializer",
+        "a variable is used in the expression that initializes it.
+
+erroneous code:
+    let x = x + 1;
+
+fix it by initializing it from another value:
+    let y = 1;
+    let x = y + 1;",
+    ),
+    (
+        "E0x421",
+        "runtime error: used before its declaration",
+        "a global variable is read before the line that declares it.
+
+erroneous code:
+    print(x);
+    let x = 1;
+
+fix it by moving the declaration up:
+    let x = 1;
+    print(x);",
+    ),
+    (
+        "E0x422",
+        "runtime error: no such parameter",
+        "a labeled argument names a parameter the called function doesn't have.
+builtin functions don't take labeled arguments.
+
+erroneous code:
+    func area(w: number, h: number) -> number = w * h;
+    area(w: 2, height: 3);
+
+fix it by using the parameter name from the declaration:
+    area(w: 2, h: 3);",
+    ),
+    (
+        "E0x423",
+        "runtime error: argument given more than once",
+        "a parameter got a value twice, either from two labels or from a label
+and a positional argument.
+
+erroneous code:
+    func area(w: number, h: number) -> number = w * h;
+    area(2, w: 3);
+
+fix it by giving every parameter one value:
+    area(2, h: 3);",
+    ),
+    (
+        "E0x424",
+        "runtime warning: deprecated function or record",
+        "a function or record marked '@deprecated' is used. the warning shows
+the message given to the annotation, and is an error with '--deny-warnings'.
+
+erroneous code:
+    func area(w: number, h: number) -> number = w * h;
+    @deprecated(\"use area\") func square(w: number) -> number = w * w;
+    square(2);
+
+fix it by using what the message suggests:
+    area(2, 2);",
+    ),
+    (
+        "E0x425",
+        "runtime error: called local shadows a function",
+        "a local variable or parameter has the name of a function, so calls of
+that name go to the local. its value can't be called.
+
+erroneous code:
+    func twice(n: number) -> number = n * 2;
+    func f(twice: number) -> number = twice(twice);
+
+fix it by renaming the local:
+    func f(n: number) -> number = twice(n);",
+    ),
+    (
+        "E0x426",
+        "runtime error: use after move",
+        "'move name' takes the value out of the variable, which is left empty.
+the variable can't be read again until it's assigned a new value.
+
+erroneous code:
+    let items: Vec<number> = [1, 2, 3];
+    let copy: Vec<number> = move items;
+    print(items);
+
+fix it by reading the new owner, or by copying instead of moving:
+    print(copy);",
+    ),
+    (
+        "E0x427",
+        "runtime error: move out of an outer variable",
+        "a loop body or a function can run more than once, so it can't move a
+variable declared outside of it, the second run would find it empty.
+
+erroneous code:
+    let items: Vec<number> = [1, 2, 3];
+    loop {
+        let copy: Vec<number> = move items;
+    }
+
+fix it by copying the value, or by moving it before the loop:
+    let copy: Vec<number> = move items;",
+    ),
+    (
+        "E0x428",
+        "runtime error: unknown bound",
+        "a type parameter of a function was given a bound which doesn't exist.
+the bounds are 'comparable' for numbers, strings and chars, 'numeric' for
+numbers and 'display' for every value but functions.
+
+erroneous code:
+    func max<T: ordered>(a: T, b: T) -> T = if a > b: a ? b;
+
+fix it by using one of the bounds:
+    func max<T: comparable>(a: T, b: T) -> T = if a > b: a ? b;",
+    ),
+    (
+        "E0x429",
+        "runtime error: unsatisfied bound",
+        "a function was called with an argument whose type doesn't support what the
+bound of its type parameter requires.
+
+erroneous code:
+    func max<T: comparable>(a: T, b: T) -> T = if a > b: a ? b;
+    max(true, false);
+
+fix it by passing values of a type satisfying the bound:
+    max(1, 2);",
+    ),
+    (
+        "E0x430",
+        "runtime error: labeled positional-only argument",
+        "parameters before a '/' in a function's parameters are positional-only, their
+names aren't part of the function's interface and can't be used as labels.
+
+erroneous code:
+    func area(w: number, h: number, /) -> number = w * h;
+    area(w: 2, h: 3);
+
+fix it by passing the arguments by position:
+    area(2, 3);",
+    ),
+    (
+        "E0x431",
+        "runtime error: unlabeled keyword-only argument",
+        "parameters after a '*' in a function's parameters are keyword-only, they can
+only be given by their label.
+
+erroneous code:
+    func pad(text: string, *, width: number) -> string = text;
+    pad(\"x\", 4);
+
+fix it by labeling the argument:
+    pad(\"x\", width: 4);",
+    ),
+    (
+        "E0x432",
+        "runtime error: declared type doesn't match the value",
+        "a variable was declared with a type, but its initial value is of another one.
+literal values are checked before running, other ones when they're assigned.
+
+erroneous code:
+    let x: number = \"a\";
+
+fix it by declaring the type of the value, or by leaving the type out:
+    let x: string = \"a\";
+    let x = \"a\";",
+    ),
+    (
+        "E0x433",
+        "runtime error: vector item of the wrong type",
+        "an item of a vector literal doesn't have the item type of the vector, the
+error names the item by its index, and by one index per level in nested vectors.
+
+erroneous code:
+    let m: Vec<Vec<number>> = [[1, 2], [\"3\", 4]];
+
+fix it by giving the item the declared type:
+    let m: Vec<Vec<number>> = [[1, 2], [3, 4]];",
+    ),
+    (
+        "E0x434",
+        "runtime error: function can't be memoized",
+        "a '@memo' function keeps the result of each call and returns it again when
+it's called with equal arguments, so its result may only depend on them. it
+can't print, read input, run shell commands, read mutable variables declared
+outside of it or change them other than by a statement of their own, call
+functions doing any of that, be async or take functions as arguments. a
+statement like 'calls += 1;' only runs when the result isn't kept already.
+
+erroneous code:
+    let mut calls = 0;
+    @memo
+    func square(n: number) -> number {
+        calls += 1;
+        return n * n + calls;
+    }
+
+fix it by keeping the outer variables out of the result:
+    let mut calls = 0;
+    @memo
+    func square(n: number) -> number {
+        calls += 1;
+        return n * n;
+    }",
+    ),
+    (
+        "E0x435",
+        "runtime error: operator applied to the wrong types",
+        "the operands of a binary operator have types the operator doesn't work on.
+arithmetic works on numbers, and on a number and a vector of numbers, '+' also
+joins two strings, ordering works on two numbers, strings or chars, '==', '!=',
+'||' and '&&' work on any values. only operands whose type is known before the
+code runs are checked, literals and the operations on them, variables, calls
+and 'any' values are not.
+
+erroneous code:
+    let x = \"a\" - 1;
+
+fix it by converting the operand, or by using an operator for its type:
+    let x = \"a{1}\";",
+    ),
+    (
+        "E0x436",
+        "runtime error: index or field doesn't exist",
+        "an index or an assignment into a vector or a record names an item or a
+field it doesn't have, or assigns into a value which has no items or fields.
+assignments don't add items, an index must be a whole number below the length
+of the vector, and a field must be one of the record's.
+
+erroneous code:
+    let mut v = [1, 2];
+    v[2] = 3;
+    print(v[-1]);
+
+fix it by using an existing item, or by adding a new one:
+    v[1] = 3;
+    push(v, 3);
+    print(v[1]);",
+    ),
+    (
+        "E0x437",
+        "runtime error: reversed char range",
+        "a char range counts up from its start to its end by code point, a range
+starting after its end has no chars. a number range starting above its end is
+empty instead, since its bounds are often computed.
+
+erroneous code:
+    let letters = 'z'..'a';
+
+fix it by putting the lower char first:
+    let letters = 'a'..'z';",
+    ),
+    (
+        "E0x438",
+        "runtime error: function can't be the entry",
+        "an '@entry' function is called after the top level statements run, with the
+command line arguments when it takes a parameter. a program has at most one,
+declared at the top level, taking nothing or the arguments as 'Vec<string>'.
+
+erroneous code:
+    @entry
+    func start(n: number) -> void {}
+
+fix it by taking the arguments and converting them:
+    @entry
+    func start(args: Vec<string>) -> void {}",
+    ),
+    (
+        "E0x439",
+        "runtime error: function can't be pure",
+        "a '@pure' function only depends on its arguments and has no effects, so it
+can't print, read input, run shell commands, change or read mutable variables
+declared outside of it, or call functions doing any of that, including the
+ones declared '@io'.
+
+erroneous code:
+    @pure
+    func area(w: number, h: number) -> number {
+        print(w);
+        return w * h;
+    }
+
+fix it by moving the effect to the caller:
+    @pure
+    func area(w: number, h: number) -> number = w * h;",
+    ),
+    (
+        "E0x440",
+        "runtime error: function can't be a test",
+        "with '--test', the '@test' functions run after the top level statements, in the
+order they're declared, with the '@before' functions running before each of them
+and the '@after' functions after. they're called without arguments, so they're
+declared at the top level and take no parameters.
+
+erroneous code:
+    use assert from std::core::test;
+    @test
+    func adds(n: number) -> void {
+        assert(n + 1 == 2, \"adds\");
+    }
+
+fix it by setting up the values in the test or in a '@before' function:
+    use assert from std::core::test;
+    @test
+    func adds() -> void {
+        let n = 1;
+        assert(n + 1 == 2, \"adds\");
+    }",
+    ),
+    (
+        "E0x441",
+        "runtime warning: variable is never read",
+        "a 'let' binding is declared, maybe assigned, but its value is never read.
+public top level variables are read by other modules, so they're never reported.
+
+erroneous code:
+    func area(w: number, h: number) -> number {
+        let half = w / 2;
+        return w * h;
+    }
+
+fix it by removing the binding:
+    func area(w: number, h: number) -> number {
+        return w * h;
+    }",
+    ),
+    (
+        "E0x442",
+        "runtime error: mismatched type parameter",
+        "a type parameter stands for one type in a call, it takes the type of the first
+argument passed for it, and every other argument passed for it must have that type.
+
+erroneous code:
+    func max<T: comparable>(a: T, b: T) -> T = a > b ? a : b;
+    max(1, \"a\");
+
+fix it by passing arguments of the same type:
+    max(1, 2);
+    max(\"a\", \"b\");",
+    ),
+    (
+        "E0x501",
+        "environment error: failed to get a distance",
+        "the environment could not find the scope a resolved variable lives in.
+this is an internal error, please report it with the code that caused it.",
+    ),
+    (
+        "E0x502",
+        "environment error: failed to resolve a value",
+        "the environment found the scope of a variable but not its value.
+this is an internal error, please report it with the code that caused it.",
+    ),
+    (
+        "E0x601",
+        "compile error: unsupported construct for the target",
+        "the construct has no equivalent in the transpile target yet.
+
+fix it by rewriting the code without it, or by running it with 'absurd run'.",
+    ),
+    (
+        "E0x602",
+        "compile error: can not be inlined",
+        "a function marked '@inline' can not be expanded at its call sites,
+for example because it calls itself.
+
+erroneous code:
+    @inline func f(n: number) -> number = f(n - 1);
+
+fix it by removing the annotation:
+    func f(n: number) -> number = f(n - 1);",
+    ),
+];
+
+/// prints the explanation of an error code, for `--explain`
+pub fn explain(code: &str) {
+    let code = code.trim().to_uppercase().replace("E0X", "E0x");
+    match EXPLANATIONS.iter().find(|(c, _, _)| *c == code) {
+        Some((code, title, desc)) => {
+            println!("{} {}", code.yellow().bold(), title.bold());
+            println!("\n{}", desc);
+        }
+        None => raw(format!("no such error '{}', codes are {}", code, code_ranges()).as_str()),
+    }
+}
+
+/// the explained codes as ranges, grouped by their hundreds, `E0x101-E0x115, E0x301-E0x306`
+fn code_ranges() -> String {
+    let mut ranges: Vec<(&str, &str)> = vec![];
+    for (code, _, _) in EXPLANATIONS {
+        match ranges.last_mut() {
+            Some((start, end)) if start[..4] == code[..4] => *end = code,
+            _ => ranges.push((code, code)),
+        }
+    }
+    ranges
+        .iter()
+        .map(|(start, end)| format!("{}-{}", start, end))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 pub fn raw(msg: &str) {
     eprintln!("{}", msg.red());
     exit(0);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "one\ntwo\nthree\nfour\nfive\nsix\nseven";
+
+    #[test]
+    fn context_around_a_line() {
+        assert_eq!(
+            context(SOURCE, 4, 2),
+            [
+                (2, "two"),
+                (3, "three"),
+                (4, "four"),
+                (5, "five"),
+                (6, "six")
+            ]
+        );
+        assert_eq!(context(SOURCE, 4, 0), [(4, "four")]);
+    }
+
+    #[test]
+    fn context_at_the_edges() {
+        assert_eq!(
+            context(SOURCE, 1, 2),
+            [(1, "one"), (2, "two"), (3, "three")]
+        );
+        assert_eq!(context(SOURCE, 7, 1), [(6, "six"), (7, "seven")]);
+        // a window larger than the file is the whole file
+        assert_eq!(context(SOURCE, 3, 100).len(), 7);
+    }
+
+    #[test]
+    fn context_outside_the_source() {
+        assert!(context(SOURCE, 0, 2).is_empty());
+        assert!(context(SOURCE, 8, 2).is_empty());
+        assert!(context("", 1, 2).is_empty());
+    }
+
+    #[test]
+    fn carets_under_a_span() {
+        assert_eq!(carets("let x = nope;", (9, 13)), "        ^^^^");
+        // an empty span still gets one mark
+        assert_eq!(carets("let x = nope;", (9, 9)), "        ^");
+        assert_eq!(carets("\tx = nope;", (9, 13)), "\t    ^^^^");
+    }
+
+    #[test]
+    fn carets_cut_at_the_end_of_the_line() {
+        // a span ending on a later line stops at the last char
+        assert_eq!(carets("let s = \"a", (9, 20)), "        ^^");
+    }
+}
@@ -0,0 +1,770 @@
+// Absurd hoister, it moves loop-invariant pure expressions out of loops
+use crate::ast::{CallType, FuncBody, LiteralType, Statement, Token, TokenType::*};
+use crate::inliner::{map, visit};
+use crate::interpreter::expr::{AssignKind, Expression};
+use std::collections::{HashMap, HashSet};
+
+/// a statement or an expression, for walking the whole program
+pub enum Node<'a> {
+    Stmt(&'a Statement),
+    Expr(&'a Expression),
+}
+
+#[derive(Debug, Clone)]
+pub struct Hoister {
+    /// short functions, with their arity, whose result only depends on their arguments
+    pure_funcs: HashMap<String, usize>,
+    /// names assigned in function and quote bodies, any call may change them
+    call_assigned: HashSet<String>,
+    /// counter for generated names
+    tmp: usize,
+}
+
+impl Hoister {
+    pub fn new() -> Self {
+        Hoister {
+            pure_funcs: HashMap::new(),
+            call_assigned: HashSet::new(),
+            tmp: 0,
+        }
+    }
+
+    /// entry method
+    pub fn hoist(&mut self, stmts: Vec<Statement>) -> Vec<Statement> {
+        self.collect(&stmts);
+        stmts.into_iter().map(|stmt| self.stmt(stmt)).collect()
+    }
+
+    /// collects the pure functions and the names calls may assign to
+    fn collect(&mut self, stmts: &[Statement]) {
+        let mut bound = HashSet::new();
+        let mut declared = HashMap::new();
+        let mut bodies = vec![];
+        walk_stmts(stmts, &mut |node| match node {
+            Node::Stmt(Statement::Func {
+                name, params, body, ..
+            }) => {
+                *declared.entry(name.lexeme.clone()).or_insert(0) += 1;
+                bound.extend(params.iter().map(|(p, _)| p.lexeme.clone()));
+                bodies.push(body);
+            }
+            Node::Stmt(Statement::Var { names, .. }) => {
+                bound.extend(names.iter().map(|name| name.lexeme.clone()))
+            }
+            Node::Stmt(Statement::For {
+                iterator, index, ..
+            }) => {
+                bound.insert(iterator.lexeme.clone());
+                bound.extend(index.iter().map(|index| index.lexeme.clone()));
+            }
+            Node::Expr(Expression::Func { params, body, .. }) => {
+                bound.extend(params.iter().map(|(p, _)| p.lexeme.clone()));
+                bodies.push(body);
+            }
+            Node::Expr(Expression::Quote { body, .. }) => bodies.push(body),
+            Node::Expr(Expression::Comprehension { var, .. }) => {
+                bound.insert(var.lexeme.clone());
+            }
+            Node::Stmt(Statement::Match { cases, .. }) => bound.extend(bindings(cases)),
+            _ => {}
+        });
+        for body in bodies {
+            walk_body(body, &mut |node| {
+                if let Node::Expr(Expression::Assign { name, .. }) = node {
+                    self.call_assigned.insert(name.lexeme.clone());
+                }
+            });
+        }
+
+        // top level short functions, which no other name shadows
+        let short: Vec<(&String, Vec<String>, &Expression)> = stmts
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Statement::Func {
+                    name,
+                    params,
+                    body: FuncBody::Expression(body),
+                    is_async: false,
+                    is_io: false,
+                    test: None,
+                    ..
+                } if declared.get(&name.lexeme) == Some(&1) && !bound.contains(&name.lexeme) => {
+                    let params = params.iter().map(|(p, _)| p.lexeme.clone()).collect();
+                    Some((&name.lexeme, params, body.as_ref()))
+                }
+                _ => None,
+            })
+            .collect();
+        // a function is pure once every function it calls is,
+        // so recursive functions never are
+        loop {
+            let count = self.pure_funcs.len();
+            for (name, params, body) in &short {
+                if !self.pure_funcs.contains_key(*name) && self.is_pure(body, params) {
+                    self.pure_funcs.insert(name.to_string(), params.len());
+                }
+            }
+            if self.pure_funcs.len() == count {
+                break;
+            }
+        }
+    }
+
+    /// checks if the expression only reads the names and calls pure functions
+    fn is_pure(&self, expr: &Expression, names: &[String]) -> bool {
+        let mut is_pure = true;
+        visit(expr, &mut |expr| match expr {
+            Expression::Var { name, .. } => {
+                is_pure &=
+                    names.contains(&name.lexeme) || self.pure_funcs.contains_key(&name.lexeme)
+            }
+            Expression::Binary { operator, .. } => is_pure &= operator.token != In,
+            Expression::Call { call_type, .. } => is_pure &= *call_type == CallType::Func,
+            Expression::Value { .. }
+            | Expression::Unary { .. }
+            | Expression::Grouping { .. }
+            | Expression::Labeled { .. } => {}
+            _ => is_pure = false,
+        });
+        is_pure
+    }
+
+    fn tmp_name(&mut self) -> Token {
+        self.tmp += 1;
+        Token::empty(Ident, &format!("$inv{}", self.tmp), None)
+    }
+
+    /// ids for the generated expressions, counting down so they don't meet the parsed ones
+    fn tmp_id(&mut self) -> usize {
+        self.tmp += 1;
+        usize::MAX - self.tmp
+    }
+
+    /// declares the hoisted variables in a block, right before the loop,
+    /// they're computed when the loop first runs its body,
+    /// so a loop which never does doesn't compute them
+    fn wrap(
+        &mut self,
+        hoisted: Vec<(Token, Expression)>,
+        mut body: Vec<Statement>,
+        make: impl FnOnce(Vec<Statement>) -> Statement,
+    ) -> Statement {
+        if hoisted.is_empty() {
+            return make(body);
+        }
+        let first = self.tmp_name();
+        let mut stmts = vec![];
+        let mut init = vec![self.assign(&first, value(LiteralType::Boolean(false)))];
+        for (name, expr) in hoisted {
+            stmts.push(declare(&name, LiteralType::Null));
+            init.push(self.assign(&name, expr));
+        }
+        stmts.push(declare(&first, LiteralType::Boolean(true)));
+        body.insert(
+            0,
+            Statement::If {
+                cond: Expression::Var {
+                    id: self.tmp_id(),
+                    name: first,
+                },
+                body: init,
+                else_if_branches: vec![],
+                else_branch: None,
+            },
+        );
+        stmts.push(make(body));
+        Statement::Block { stmts }
+    }
+
+    /// `name = value;`
+    fn assign(&mut self, name: &Token, value: Expression) -> Statement {
+        Statement::Expression {
+            expr: Expression::Assign {
+                id: self.tmp_id(),
+                name: name.clone(),
+                path: vec![],
+                value: Box::new(value),
+                kind: AssignKind::Normal,
+            },
+        }
+    }
+
+    fn stmts(&mut self, stmts: Vec<Statement>) -> Vec<Statement> {
+        stmts.into_iter().map(|stmt| self.stmt(stmt)).collect()
+    }
+
+    fn body(&mut self, body: FuncBody) -> FuncBody {
+        match body {
+            FuncBody::Statements(stmts) => FuncBody::Statements(self.stmts(stmts)),
+            FuncBody::Expression(expr) => FuncBody::Expression(Box::new(self.expr(*expr))),
+        }
+    }
+
+    /// statement hoister, inner loops are hoisted before the loops around them
+    fn stmt(&mut self, stmt: Statement) -> Statement {
+        match stmt {
+            Statement::Expression { expr } => Statement::Expression {
+                expr: self.expr(expr),
+            },
+            Statement::Block { stmts } => Statement::Block {
+                stmts: self.stmts(stmts),
+            },
+            Statement::Var {
+                names,
+                destruct,
+                value_type,
+                value,
+                is_mut,
+                is_pub,
+                pub_names,
+                is_func,
+                constraint,
+            } => Statement::Var {
+                value: value.map(|value| match value {
+                    // mutable vectors are built each time, only their items are folded
+                    Expression::Vec { id, items } if is_mut => Expression::Vec {
+                        id,
+                        items: items.into_iter().map(|item| self.expr(item)).collect(),
+                    },
+                    value => self.expr(value),
+                }),
+                names,
+                destruct,
+                value_type,
+                is_mut,
+                is_pub,
+                pub_names,
+                is_func,
+                constraint,
+            },
+            Statement::Func {
+                name,
+                value_type,
+                body,
+                params,
+                positional_only,
+                keyword_only,
+                generics,
+                is_async,
+                is_pub,
+                is_inline,
+                is_memo,
+                is_entry,
+                is_pure,
+                is_io,
+                test,
+                deprecated,
+            } => Statement::Func {
+                body: self.body(body),
+                name,
+                value_type,
+                params,
+                positional_only,
+                keyword_only,
+                generics,
+                is_async,
+                is_pub,
+                is_inline,
+                is_memo,
+                is_entry,
+                is_pure,
+                is_io,
+                test,
+                deprecated,
+            },
+            Statement::If {
+                cond,
+                body,
+                else_if_branches,
+                else_branch,
+            } => Statement::If {
+                cond: self.expr(cond),
+                body: self.stmts(body),
+                else_if_branches: else_if_branches
+                    .into_iter()
+                    .map(|(cond, body)| (self.expr(cond), self.stmts(body)))
+                    .collect(),
+                else_branch: else_branch.map(|body| self.stmts(body)),
+            },
+            Statement::Return { expr } => Statement::Return {
+                expr: self.expr(expr),
+            },
+            Statement::Match {
+                cond,
+                cases,
+                def_case,
+            } => Statement::Match {
+                cond: self.expr(cond),
+                cases: cases
+                    .into_iter()
+                    .map(|(case, guard, body)| {
+                        (case, guard.map(|guard| self.expr(guard)), self.body(body))
+                    })
+                    .collect(),
+                def_case: def_case.map(|body| self.body(body)),
+            },
+            Statement::Impl { name, methods } => Statement::Impl {
+                methods: self.stmts(methods),
+                name,
+            },
+            Statement::Record {
+                name,
+                extends,
+                is_strict,
+                fields,
+                getters,
+                deprecated,
+            } => Statement::Record {
+                getters: self.stmts(getters),
+                name,
+                extends,
+                is_strict,
+                fields,
+                deprecated,
+            },
+            Statement::For {
+                iterator,
+                index,
+                expr,
+                body,
+            } => {
+                let expr = self.expr(expr);
+                let body = self.stmts(body);
+                let mut names = vec![iterator.lexeme.clone()];
+                names.extend(index.iter().map(|index| index.lexeme.clone()));
+                let Some(variant) = self.variant(&body, names) else {
+                    return Statement::For {
+                        iterator,
+                        index,
+                        expr,
+                        body,
+                    };
+                };
+                let mut hoisted = vec![];
+                let body = self.lift_stmts(body, &variant, &mut hoisted);
+                self.wrap(hoisted, body, |body| Statement::For {
+                    iterator,
+                    index,
+                    expr,
+                    body,
+                })
+            }
+            Statement::While { cond, body } => {
+                let cond = self.expr(cond);
+                let body = self.stmts(body);
+                let Some(variant) = self.variant(&body, vec![]) else {
+                    return Statement::While { cond, body };
+                };
+                // the condition runs before the body computes the hoisted values
+                let mut hoisted = vec![];
+                let body = self.lift_stmts(body, &variant, &mut hoisted);
+                self.wrap(hoisted, body, |body| Statement::While { cond, body })
+            }
+            Statement::Loop { iter, body } => {
+                let body = self.stmts(body);
+                let Some(variant) = self.variant(&body, vec![]) else {
+                    return Statement::Loop { iter, body };
+                };
+                let mut hoisted = vec![];
+                let body = self.lift_stmts(body, &variant, &mut hoisted);
+                self.wrap(hoisted, body, |body| Statement::Loop { iter, body })
+            }
+            stmt => stmt,
+        }
+    }
+
+    /// hoists loops in callbacks, and builds constant vectors once
+    fn expr(&mut self, expr: Expression) -> Expression {
+        match expr {
+            Expression::Func {
+                id,
+                name,
+                value_type,
+                body,
+                params,
+                is_async,
+                is_pub,
+            } => Expression::Func {
+                body: self.body(body),
+                id,
+                name,
+                value_type,
+                params,
+                is_async,
+                is_pub,
+            },
+            Expression::Quote { .. } => expr,
+            expr => match map(expr, &mut |expr| self.expr(expr)) {
+                // evaluating the value clones it, the items aren't evaluated again
+                Expression::Vec { id, items } => match items.iter().map(constant).collect() {
+                    Some(values) => Expression::Value {
+                        id,
+                        value: LiteralType::Vec(values),
+                    },
+                    None => Expression::Vec { id, items },
+                },
+                expr => expr,
+            },
+        }
+    }
+
+    /// returns the names which may change while the loop body runs,
+    /// or none if the body can change names it doesn't mention
+    fn variant(&self, body: &[Statement], mut names: Vec<String>) -> Option<HashSet<String>> {
+        let mut is_opaque = false;
+        let mut has_calls = false;
+        walk_stmts(body, &mut |node| match node {
+            Node::Stmt(Statement::Var { names: vars, .. }) => {
+                names.extend(vars.iter().map(|var| var.lexeme.clone()))
+            }
+            Node::Stmt(Statement::Func { name, params, .. }) => {
+                names.push(name.lexeme.clone());
+                names.extend(params.iter().map(|(p, _)| p.lexeme.clone()));
+            }
+            Node::Stmt(Statement::For {
+                iterator, index, ..
+            }) => {
+                names.push(iterator.lexeme.clone());
+                names.extend(index.iter().map(|index| index.lexeme.clone()));
+            }
+            Node::Stmt(Statement::Use { .. } | Statement::Mod { .. } | Statement::Sh { .. }) => {
+                is_opaque = true
+            }
+            Node::Expr(Expression::Assign { name, .. }) => names.push(name.lexeme.clone()),
+            Node::Expr(Expression::Func { params, .. }) => {
+                names.extend(params.iter().map(|(p, _)| p.lexeme.clone()))
+            }
+            Node::Expr(Expression::Comprehension { var, .. }) => names.push(var.lexeme.clone()),
+            Node::Stmt(Statement::Match { cases, .. }) => names.extend(bindings(cases)),
+            Node::Expr(Expression::Quote { .. }) => is_opaque = true,
+            Node::Expr(Expression::Call {
+                name,
+                call_type: CallType::Func,
+                ..
+            }) => match name.as_ref() {
+                Expression::Var { name, .. } if self.pure_funcs.contains_key(&name.lexeme) => {}
+                _ => has_calls = true,
+            },
+            Node::Expr(Expression::Method { .. } | Expression::Await { .. }) => has_calls = true,
+            _ => {}
+        });
+        if is_opaque {
+            return None;
+        }
+        let mut variant: HashSet<String> = names.into_iter().collect();
+        if has_calls {
+            variant.extend(self.call_assigned.iter().cloned());
+        }
+        Some(variant)
+    }
+
+    /// checks if the expression is pure and reads none of the variant names
+    fn is_invariant(&self, expr: &Expression, variant: &HashSet<String>) -> bool {
+        match expr {
+            Expression::Value { .. } => true,
+            Expression::Var { name, .. } => !variant.contains(&name.lexeme),
+            Expression::Grouping { expression, .. } => self.is_invariant(expression, variant),
+            Expression::Unary { left, .. } => self.is_invariant(left, variant),
+            Expression::Binary {
+                left,
+                operator,
+                right,
+                ..
+            } => {
+                // `&&` and `||` don't always need their right side
+                !matches!(operator.token, In | DblAnd | Or)
+                    && self.is_invariant(left, variant)
+                    && self.is_invariant(right, variant)
+            }
+            Expression::Call {
+                name,
+                args,
+                call_type: CallType::Func,
+                ..
+            } => match name.as_ref() {
+                Expression::Var { name, .. } => {
+                    self.pure_funcs.get(&name.lexeme) == Some(&args.len())
+                        && !variant.contains(&name.lexeme)
+                        && args.iter().all(|arg| {
+                            !matches!(arg, Expression::Labeled { .. })
+                                && self.is_invariant(arg, variant)
+                        })
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// replaces the largest invariant binary expressions and calls with generated variables,
+    /// only in the parts which run whenever the expression does
+    fn lift(
+        &mut self,
+        expr: Expression,
+        variant: &HashSet<String>,
+        hoisted: &mut Vec<(Token, Expression)>,
+    ) -> Expression {
+        match expr {
+            Expression::Binary { .. } | Expression::Call { .. }
+                if self.is_invariant(&expr, variant) =>
+            {
+                let name = self.tmp_name();
+                let id = expr.id();
+                hoisted.push((name.clone(), expr));
+                Expression::Var { id, name }
+            }
+            Expression::Binary {
+                id,
+                left,
+                operator,
+                right,
+            } if matches!(operator.token, DblAnd | Or) => Expression::Binary {
+                left: Box::new(self.lift(*left, variant, hoisted)),
+                id,
+                operator,
+                right,
+            },
+            Expression::If {
+                id,
+                cond,
+                body,
+                else_branch,
+            } => Expression::If {
+                cond: Box::new(self.lift(*cond, variant, hoisted)),
+                id,
+                body,
+                else_branch,
+            },
+            // evaluated later, or once per item
+            Expression::Func { .. }
+            | Expression::Quote { .. }
+            | Expression::Comprehension { .. } => expr,
+            expr => map(expr, &mut |expr| self.lift(expr, variant, hoisted)),
+        }
+    }
+
+    /// lifts the statements until one which may leave the loop early,
+    /// the ones after it don't run every iteration
+    fn lift_stmts(
+        &mut self,
+        stmts: Vec<Statement>,
+        variant: &HashSet<String>,
+        hoisted: &mut Vec<(Token, Expression)>,
+    ) -> Vec<Statement> {
+        let mut may_leave = false;
+        stmts
+            .into_iter()
+            .map(|stmt| {
+                if may_leave {
+                    return stmt;
+                }
+                walk_stmt(&stmt, &mut |node| {
+                    may_leave |= matches!(
+                        node,
+                        Node::Stmt(Statement::Break {} | Statement::Return { .. })
+                    )
+                });
+                self.lift_stmt(stmt, variant, hoisted)
+            })
+            .collect()
+    }
+
+    /// lifts the parts of the statement which run whenever it does,
+    /// branches, loop bodies and function bodies may not run
+    fn lift_stmt(
+        &mut self,
+        stmt: Statement,
+        variant: &HashSet<String>,
+        hoisted: &mut Vec<(Token, Expression)>,
+    ) -> Statement {
+        match stmt {
+            Statement::Expression { expr } => Statement::Expression {
+                expr: self.lift(expr, variant, hoisted),
+            },
+            Statement::Block { stmts } => Statement::Block {
+                stmts: self.lift_stmts(stmts, variant, hoisted),
+            },
+            Statement::Var {
+                names,
+                destruct,
+                value_type,
+                value,
+                is_mut,
+                is_pub,
+                pub_names,
+                is_func,
+                constraint,
+            } => Statement::Var {
+                names,
+                destruct,
+                value_type,
+                value: value.map(|value| self.lift(value, variant, hoisted)),
+                is_mut,
+                is_pub,
+                pub_names,
+                is_func,
+                constraint,
+            },
+            // the branches and later conditions may not run
+            Statement::If {
+                cond,
+                body,
+                else_if_branches,
+                else_branch,
+            } => Statement::If {
+                cond: self.lift(cond, variant, hoisted),
+                body,
+                else_if_branches,
+                else_branch,
+            },
+            Statement::Return { expr } => Statement::Return {
+                expr: self.lift(expr, variant, hoisted),
+            },
+            // inner loop bodies may run no times
+            Statement::For {
+                iterator,
+                index,
+                expr,
+                body,
+            } => Statement::For {
+                expr: self.lift(expr, variant, hoisted),
+                body,
+                iterator,
+                index,
+            },
+            Statement::While { cond, body } => Statement::While {
+                cond: self.lift(cond, variant, hoisted),
+                body,
+            },
+            // only the matching case runs, and guards only when their pattern matches
+            Statement::Match {
+                cond,
+                cases,
+                def_case,
+            } => Statement::Match {
+                cond: self.lift(cond, variant, hoisted),
+                cases,
+                def_case,
+            },
+            // function bodies run when called, not when the loop runs,
+            // `loop` bodies may run no times
+            stmt => stmt,
+        }
+    }
+}
+
+/// names bound by the string patterns of match cases
+pub fn bindings(
+    cases: &[(Expression, Option<Expression>, FuncBody)],
+) -> impl Iterator<Item = String> + '_ {
+    cases
+        .iter()
+        .flat_map(|(case, _, _)| case.pattern_names())
+        .map(|name| name.lexeme.clone())
+}
+
+/// the value of a literal, strings which interpolate `{expr}` or hold
+/// escaped braces aren't constant
+fn constant(expr: &Expression) -> Option<LiteralType> {
+    match expr {
+        Expression::Value {
+            value: LiteralType::String(s),
+            ..
+        } if s.contains(['{', '}']) => None,
+        Expression::Value { value, .. } => Some(value.clone()),
+        _ => None,
+    }
+}
+
+fn value(value: LiteralType) -> Expression {
+    Expression::Value { id: 0, value }
+}
+
+/// `let mut name = value;`
+fn declare(name: &Token, init: LiteralType) -> Statement {
+    Statement::Var {
+        names: vec![name.clone()],
+        destruct: None,
+        value_type: Token::empty(AnyIdent, "any", None),
+        value: Some(value(init)),
+        is_mut: true,
+        is_pub: false,
+        pub_names: vec![name.clone()],
+        is_func: false,
+        constraint: None,
+    }
+}
+
+/// calls `f` on every statement and expression, in function and quote bodies too
+pub fn walk_stmts<'a>(stmts: &'a [Statement], f: &mut impl FnMut(Node<'a>)) {
+    stmts.iter().for_each(|stmt| walk_stmt(stmt, f));
+}
+
+pub fn walk_body<'a>(body: &'a FuncBody, f: &mut impl FnMut(Node<'a>)) {
+    match body {
+        FuncBody::Statements(stmts) => walk_stmts(stmts, f),
+        FuncBody::Expression(expr) => walk_expr(expr, f),
+    }
+}
+
+fn walk_stmt<'a>(stmt: &'a Statement, f: &mut impl FnMut(Node<'a>)) {
+    f(Node::Stmt(stmt));
+    match stmt {
+        Statement::Expression { expr } | Statement::Return { expr } => walk_expr(expr, f),
+        Statement::Block { stmts }
+        | Statement::Loop { body: stmts, .. }
+        | Statement::Impl { methods: stmts, .. }
+        | Statement::Record { getters: stmts, .. } => walk_stmts(stmts, f),
+        Statement::Var {
+            value, constraint, ..
+        } => {
+            value.iter().for_each(|value| walk_expr(value, f));
+            constraint
+                .iter()
+                .for_each(|constraint| walk_expr(constraint, f));
+        }
+        Statement::Func { body, .. } => walk_body(body, f),
+        Statement::If {
+            cond,
+            body,
+            else_if_branches,
+            else_branch,
+        } => {
+            walk_expr(cond, f);
+            walk_stmts(body, f);
+            else_if_branches.iter().for_each(|(cond, body)| {
+                walk_expr(cond, f);
+                walk_stmts(body, f);
+            });
+            else_branch.iter().for_each(|body| walk_stmts(body, f));
+        }
+        Statement::For { expr, body, .. } | Statement::While { cond: expr, body } => {
+            walk_expr(expr, f);
+            walk_stmts(body, f);
+        }
+        Statement::Match {
+            cond,
+            cases,
+            def_case,
+        } => {
+            walk_expr(cond, f);
+            cases.iter().for_each(|(_, guard, body)| {
+                guard.iter().for_each(|guard| walk_expr(guard, f));
+                walk_body(body, f);
+            });
+            def_case.iter().for_each(|body| walk_body(body, f));
+        }
+        _ => {}
+    }
+}
+
+fn walk_expr<'a>(expr: &'a Expression, f: &mut impl FnMut(Node<'a>)) {
+    let mut bodies = vec![];
+    visit(expr, &mut |expr: &'a Expression| {
+        f(Node::Expr(expr));
+        if let Expression::Func { body, .. } | Expression::Quote { body, .. } = expr {
+            bodies.push(body);
+        }
+    });
+    bodies.into_iter().for_each(|body| walk_body(body, f));
+}
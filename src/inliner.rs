@@ -0,0 +1,645 @@
+// Absurd inliner, it replaces calls to `@inline` functions with their bodies
+use crate::ast::{CallType, FuncBody, Statement, Token};
+use crate::errors::{Error, ErrorCode::*};
+use crate::interpreter::expr::Expression;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone)]
+struct InlineFunc {
+    params: Vec<String>,
+    body: Expression,
+    /// names the body reads, besides its parameters
+    free: HashSet<String>,
+    /// bodies that bind names or have side effects of their own are kept as calls
+    is_pure: bool,
+    /// message of `@deprecated`, warned at each expanded call
+    deprecated: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Inliner {
+    funcs: HashMap<String, InlineFunc>,
+    /// names bound in the enclosing function and block scopes
+    scopes: Vec<HashSet<String>>,
+    err: Error,
+}
+
+impl Inliner {
+    pub fn new(err: Error) -> Self {
+        Inliner {
+            funcs: HashMap::new(),
+            scopes: Vec::new(),
+            err,
+        }
+    }
+
+    /// entry method
+    pub fn inline(&mut self, stmts: Vec<Statement>) -> Vec<Statement> {
+        stmts.iter().for_each(|stmt| self.collect(stmt));
+        if self.funcs.is_empty() {
+            return stmts;
+        }
+        self.check_recursion(&stmts);
+        stmts.into_iter().map(|stmt| self.stmt(stmt)).collect()
+    }
+
+    /// collects the top level `@inline` functions
+    fn collect(&mut self, stmt: &Statement) {
+        if let Statement::Func {
+            name,
+            body,
+            params,
+            is_inline: true,
+            deprecated,
+            ..
+        } = stmt
+        {
+            let body = match body {
+                FuncBody::Expression(body) => *body.clone(),
+                FuncBody::Statements(_) => {
+                    self.err.throw(
+                        E0x602,
+                        name.line,
+                        name.pos,
+                        vec![
+                            name.lexeme.clone(),
+                            "only short functions (`= expr`) can be inlined".to_string(),
+                        ],
+                    );
+                    return;
+                }
+            };
+            let params: Vec<String> = params.iter().map(|(p, _)| p.lexeme.clone()).collect();
+            let mut free = HashSet::new();
+            let mut is_pure = true;
+            visit(&body, &mut |expr| match expr {
+                Expression::Var { name, .. } if !params.contains(&name.lexeme) => {
+                    free.insert(name.lexeme.clone());
+                }
+                Expression::Func { .. }
+                | Expression::Comprehension { .. }
+                | Expression::Quote { .. }
+                | Expression::Assign { .. }
+                | Expression::Move { .. }
+                | Expression::Await { .. } => is_pure = false,
+                _ => {}
+            });
+            self.funcs.insert(
+                name.lexeme.clone(),
+                InlineFunc {
+                    params,
+                    body,
+                    free,
+                    is_pure,
+                    deprecated: deprecated.clone(),
+                },
+            );
+        }
+    }
+
+    /// rejects `@inline` functions which reach themselves through other `@inline` functions
+    fn check_recursion(&self, stmts: &[Statement]) {
+        for stmt in stmts {
+            if let Statement::Func {
+                name,
+                is_inline: true,
+                ..
+            } = stmt
+            {
+                let mut seen = HashSet::new();
+                let mut stack = vec![name.lexeme.clone()];
+                while let Some(crnt) = stack.pop() {
+                    let Some(func) = self.funcs.get(&crnt) else {
+                        continue;
+                    };
+                    for callee in &func.free {
+                        if *callee == name.lexeme {
+                            self.err.throw(
+                                E0x602,
+                                name.line,
+                                name.pos,
+                                vec![name.lexeme.clone(), "it's recursive".to_string()],
+                            );
+                        }
+                        if seen.insert(callee.clone()) {
+                            stack.push(callee.clone());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn scoped<T>(&mut self, names: Vec<&Token>, f: impl FnOnce(&mut Self) -> T) -> T {
+        self.scopes
+            .push(names.iter().map(|name| name.lexeme.clone()).collect());
+        let value = f(self);
+        self.scopes.pop();
+        value
+    }
+
+    fn bind(&mut self, names: &[Token]) {
+        if let Some(scope) = self.scopes.last_mut() {
+            names.iter().for_each(|name| {
+                scope.insert(name.lexeme.clone());
+            });
+        }
+    }
+
+    fn is_shadowed(&self, name: &str) -> bool {
+        self.scopes.iter().any(|scope| scope.contains(name))
+    }
+
+    fn stmts(&mut self, stmts: Vec<Statement>, names: Vec<&Token>) -> Vec<Statement> {
+        self.scoped(names, |inliner| {
+            stmts.into_iter().map(|stmt| inliner.stmt(stmt)).collect()
+        })
+    }
+
+    fn body(&mut self, body: FuncBody, names: Vec<&Token>) -> FuncBody {
+        match body {
+            FuncBody::Statements(stmts) => FuncBody::Statements(self.stmts(stmts, names)),
+            FuncBody::Expression(expr) => {
+                FuncBody::Expression(Box::new(self.scoped(names, |inliner| inliner.expr(*expr))))
+            }
+        }
+    }
+
+    /// statement inliner
+    fn stmt(&mut self, stmt: Statement) -> Statement {
+        match stmt {
+            Statement::Expression { expr } => Statement::Expression {
+                expr: self.expr(expr),
+            },
+            Statement::Block { stmts } => Statement::Block {
+                stmts: self.stmts(stmts, vec![]),
+            },
+            Statement::Var {
+                names,
+                destruct,
+                value_type,
+                value,
+                is_mut,
+                is_pub,
+                pub_names,
+                is_func,
+                constraint,
+            } => {
+                let value = value.map(|value| self.expr(value));
+                self.bind(&names);
+                Statement::Var {
+                    names,
+                    destruct,
+                    value_type,
+                    value,
+                    is_mut,
+                    is_pub,
+                    pub_names,
+                    is_func,
+                    constraint: constraint.map(|constraint| self.expr(constraint)),
+                }
+            }
+            Statement::Func {
+                name,
+                value_type,
+                body,
+                params,
+                positional_only,
+                keyword_only,
+                generics,
+                is_async,
+                is_pub,
+                is_inline,
+                is_memo,
+                is_entry,
+                is_pure,
+                is_io,
+                test,
+                deprecated,
+            } => {
+                self.bind(std::slice::from_ref(&name));
+                let names = params.iter().map(|(p, _)| p).collect();
+                Statement::Func {
+                    body: self.body(body, names),
+                    name,
+                    value_type,
+                    params,
+                    positional_only,
+                    keyword_only,
+                    generics,
+                    is_async,
+                    is_pub,
+                    is_inline,
+                    is_memo,
+                    is_entry,
+                    is_pure,
+                    is_io,
+                    test,
+                    deprecated,
+                }
+            }
+            Statement::If {
+                cond,
+                body,
+                else_if_branches,
+                else_branch,
+            } => Statement::If {
+                cond: self.expr(cond),
+                body: self.stmts(body, vec![]),
+                else_if_branches: else_if_branches
+                    .into_iter()
+                    .map(|(cond, body)| (self.expr(cond), self.stmts(body, vec![])))
+                    .collect(),
+                else_branch: else_branch.map(|body| self.stmts(body, vec![])),
+            },
+            Statement::Return { expr } => Statement::Return {
+                expr: self.expr(expr),
+            },
+            Statement::For {
+                iterator,
+                index,
+                expr,
+                body,
+            } => {
+                let expr = self.expr(expr);
+                let mut names = vec![&iterator];
+                names.extend(index.as_ref());
+                let body = self.stmts(body, names);
+                Statement::For {
+                    iterator,
+                    index,
+                    expr,
+                    body,
+                }
+            }
+            Statement::While { cond, body } => Statement::While {
+                cond: self.expr(cond),
+                body: self.stmts(body, vec![]),
+            },
+            Statement::Loop { iter, body } => Statement::Loop {
+                iter,
+                body: self.stmts(body, vec![]),
+            },
+            Statement::Match {
+                cond,
+                cases,
+                def_case,
+            } => Statement::Match {
+                cond: self.expr(cond),
+                cases: cases
+                    .into_iter()
+                    .map(|(case, guard, body)| {
+                        let names = case.pattern_names();
+                        let guard = guard
+                            .map(|guard| self.scoped(names.clone(), |inliner| inliner.expr(guard)));
+                        let body = self.body(body, names);
+                        (case, guard, body)
+                    })
+                    .collect(),
+                def_case: def_case.map(|body| self.body(body, vec![])),
+            },
+            Statement::Impl { name, methods } => Statement::Impl {
+                methods: self.stmts(methods, vec![]),
+                name,
+            },
+            Statement::Record {
+                name,
+                extends,
+                is_strict,
+                fields,
+                getters,
+                deprecated,
+            } => Statement::Record {
+                getters: self.stmts(getters, vec![]),
+                name,
+                extends,
+                is_strict,
+                fields,
+                deprecated,
+            },
+            stmt => stmt,
+        }
+    }
+
+    /// expression inliner
+    fn expr(&mut self, expr: Expression) -> Expression {
+        let expr = match expr {
+            Expression::Func {
+                id,
+                name,
+                value_type,
+                body,
+                params,
+                is_async,
+                is_pub,
+            } => {
+                let names = params.iter().map(|(p, _)| p).collect();
+                return Expression::Func {
+                    body: self.body(body, names),
+                    id,
+                    name,
+                    value_type,
+                    params,
+                    is_async,
+                    is_pub,
+                };
+            }
+            Expression::Comprehension {
+                id,
+                expr,
+                var,
+                iter,
+                filter,
+            } => {
+                let iter = Box::new(self.expr(*iter));
+                let (expr, filter) = self.scoped(vec![&var], |inliner| {
+                    (
+                        Box::new(inliner.expr(*expr)),
+                        filter.map(|filter| Box::new(inliner.expr(*filter))),
+                    )
+                });
+                return Expression::Comprehension {
+                    id,
+                    expr,
+                    var,
+                    iter,
+                    filter,
+                };
+            }
+            // quoted code is evaluated later, in the scope of `eval`
+            Expression::Quote { .. } => return expr,
+            expr => map(expr, &mut |expr| self.expr(expr)),
+        };
+
+        match self.expand(&expr) {
+            // the body can call other `@inline` functions
+            Some(inlined) => self.expr(inlined),
+            None => expr,
+        }
+    }
+
+    /// returns the body of the called `@inline` function, with the arguments in place of
+    /// its parameters, if the call can be replaced without changing what it does
+    fn expand(&self, expr: &Expression) -> Option<Expression> {
+        let Expression::Call {
+            id,
+            name,
+            args,
+            call_type: CallType::Func,
+        } = expr
+        else {
+            return None;
+        };
+        let Expression::Var { name, .. } = &**name else {
+            return None;
+        };
+        let func = self.funcs.get(&name.lexeme)?;
+        if self.is_shadowed(&name.lexeme)
+            || !func.is_pure
+            || args.len() != func.params.len()
+            || args
+                .iter()
+                .any(|arg| matches!(arg, Expression::Labeled { .. }))
+            || func.free.iter().any(|name| self.is_shadowed(name))
+        {
+            return None;
+        }
+
+        // an argument with effects may only be evaluated once, and in the call's order
+        let impure: Vec<usize> = (0..args.len()).filter(|i| !is_pure(&args[*i])).collect();
+        if impure.len() > 1 {
+            return None;
+        }
+        if let Some(i) = impure.first() {
+            let mut uses = 0;
+            let mut has_calls = false;
+            visit(&func.body, &mut |expr| match expr {
+                Expression::Var { name, .. } if name.lexeme == func.params[*i] => uses += 1,
+                Expression::Call { .. } | Expression::Method { .. } => has_calls = true,
+                _ => {}
+            });
+            if uses != 1 || has_calls {
+                return None;
+            }
+        }
+
+        // the resolver doesn't see the call once it's replaced
+        if let Some(message) = &func.deprecated {
+            self.err.warn(
+                E0x424,
+                name.line,
+                name.pos,
+                vec![name.lexeme.clone(), message.clone()],
+            );
+        }
+        let values: HashMap<&String, &Expression> = func.params.iter().zip(args).collect();
+        Some(Expression::Grouping {
+            id: *id,
+            expression: Box::new(substitute(func.body.clone(), &values)),
+        })
+    }
+}
+
+fn is_pure(expr: &Expression) -> bool {
+    match expr {
+        Expression::Var { .. } | Expression::Value { .. } => true,
+        Expression::Range { start, end, .. } => is_pure(start) && is_pure(end),
+        Expression::Grouping { expression, .. } => is_pure(expression),
+        Expression::Unary { left, .. } => is_pure(left),
+        Expression::Binary { left, right, .. } => is_pure(left) && is_pure(right),
+        _ => false,
+    }
+}
+
+fn substitute(expr: Expression, values: &HashMap<&String, &Expression>) -> Expression {
+    match expr {
+        Expression::Var { ref name, .. } => match values.get(&name.lexeme) {
+            Some(value) => (*value).clone(),
+            None => expr,
+        },
+        expr => map(expr, &mut |expr| substitute(expr, values)),
+    }
+}
+
+/// calls `f` on the expression and every expression inside it
+pub fn visit<'a>(expr: &'a Expression, f: &mut impl FnMut(&'a Expression)) {
+    f(expr);
+    match expr {
+        Expression::If {
+            cond,
+            body,
+            else_branch,
+            ..
+        } => {
+            visit(cond, f);
+            visit(body, f);
+            if let Some(else_branch) = else_branch {
+                visit(else_branch, f);
+            }
+        }
+        Expression::Record { fields, .. } => fields.iter().for_each(|(_, e)| visit(e, f)),
+        Expression::Assign { path, value, .. } => {
+            path.iter().for_each(|e| visit(e, f));
+            visit(value, f);
+        }
+        Expression::Vec { items, .. } | Expression::Tuple { items, .. } => {
+            items.iter().for_each(|e| visit(e, f))
+        }
+        Expression::Call { name, args, .. } => {
+            visit(name, f);
+            args.iter().for_each(|e| visit(e, f));
+        }
+        Expression::Unary { left, .. } => visit(left, f),
+        Expression::Binary { left, right, .. } => {
+            visit(left, f);
+            visit(right, f);
+        }
+        Expression::Range { start, end, .. } => {
+            visit(start, f);
+            visit(end, f);
+        }
+        Expression::Grouping { expression, .. } => visit(expression, f),
+        Expression::Await { expr, .. } => visit(expr, f),
+        Expression::Labeled { value, .. } => visit(value, f),
+        Expression::Method { object, args, .. } => {
+            visit(object, f);
+            args.iter().for_each(|e| visit(e, f));
+        }
+        Expression::Comprehension {
+            expr, iter, filter, ..
+        } => {
+            visit(expr, f);
+            visit(iter, f);
+            if let Some(filter) = filter {
+                visit(filter, f);
+            }
+        }
+        Expression::Func { .. }
+        | Expression::Quote { .. }
+        | Expression::Var { .. }
+        | Expression::Move { .. }
+        | Expression::Value { .. } => {}
+    }
+}
+
+/// rebuilds the expression with `f` applied to the expressions directly inside it
+pub fn map(expr: Expression, f: &mut impl FnMut(Expression) -> Expression) -> Expression {
+    let mut boxed = |e: Box<Expression>| Box::new(f(*e));
+    match expr {
+        Expression::If {
+            id,
+            cond,
+            body,
+            else_branch,
+        } => Expression::If {
+            id,
+            cond: boxed(cond),
+            body: boxed(body),
+            else_branch: else_branch.map(boxed),
+        },
+        Expression::Record { id, fields } => Expression::Record {
+            id,
+            fields: fields.into_iter().map(|(k, e)| (k, f(e))).collect(),
+        },
+        Expression::Assign {
+            id,
+            name,
+            path,
+            value,
+            kind,
+        } => {
+            let value = boxed(value);
+            Expression::Assign {
+                id,
+                name,
+                path: path.into_iter().map(f).collect(),
+                value,
+                kind,
+            }
+        }
+        Expression::Vec { id, items } => Expression::Vec {
+            id,
+            items: items.into_iter().map(f).collect(),
+        },
+        Expression::Tuple { id, items } => Expression::Tuple {
+            id,
+            items: items.into_iter().map(f).collect(),
+        },
+        Expression::Call {
+            id,
+            name,
+            args,
+            call_type,
+        } => Expression::Call {
+            id,
+            name: Box::new(f(*name)),
+            args: args.into_iter().map(f).collect(),
+            call_type,
+        },
+        Expression::Unary { id, left, operator } => Expression::Unary {
+            id,
+            left: boxed(left),
+            operator,
+        },
+        Expression::Binary {
+            id,
+            left,
+            operator,
+            right,
+        } => Expression::Binary {
+            id,
+            left: Box::new(f(*left)),
+            operator,
+            right: Box::new(f(*right)),
+        },
+        Expression::Range {
+            id,
+            start,
+            end,
+            inclusive,
+        } => Expression::Range {
+            id,
+            start: Box::new(f(*start)),
+            end: Box::new(f(*end)),
+            inclusive,
+        },
+        Expression::Grouping { id, expression } => Expression::Grouping {
+            id,
+            expression: boxed(expression),
+        },
+        Expression::Await { id, expr } => Expression::Await {
+            id,
+            expr: boxed(expr),
+        },
+        Expression::Labeled { id, label, value } => Expression::Labeled {
+            id,
+            label,
+            value: boxed(value),
+        },
+        Expression::Method {
+            id,
+            object,
+            name,
+            args,
+        } => Expression::Method {
+            id,
+            object: Box::new(f(*object)),
+            name,
+            args: args.into_iter().map(f).collect(),
+        },
+        Expression::Comprehension {
+            id,
+            expr,
+            var,
+            iter,
+            filter,
+        } => Expression::Comprehension {
+            id,
+            expr: Box::new(f(*expr)),
+            var,
+            iter: Box::new(f(*iter)),
+            filter: filter.map(|filter| Box::new(f(*filter))),
+        },
+        expr => expr,
+    }
+}
@@ -3,12 +3,20 @@ use crate::{
     errors::{Error, ErrorCode::*},
     manifest::Project,
 };
-use std::{borrow::Borrow, cell::RefCell, collections::HashMap, process::exit, rc::Rc};
+use std::{
+    borrow::Borrow,
+    cell::{RefCell, RefMut},
+    collections::HashMap,
+    process::exit,
+    rc::Rc,
+};
 
-type EnvValueType = Rc<RefCell<HashMap<String, ValueType>>>;
-type EnvTypeValueType = Rc<RefCell<HashMap<String, Token>>>;
+// the maps of a scope are shared with its snapshots until either is written to
+type EnvValueType = Rc<RefCell<Rc<HashMap<String, ValueType>>>>;
+type EnvTypeValueType = Rc<RefCell<Rc<HashMap<String, Token>>>>;
 type EnvEnumValueType = Rc<RefCell<HashMap<String, Vec<(Token, Option<Token>)>>>>;
 type ModEnvValueType = Rc<RefCell<HashMap<String, Vec<(String, ValueType)>>>>;
+type EnvMethodValueType = Rc<RefCell<HashMap<String, HashMap<String, LiteralType>>>>;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum ValueKind {
@@ -40,6 +48,14 @@ pub struct ValueType {
     pub kind: ValueKind,
 }
 
+/// the values of a scope at some point, see `Env::snapshot`
+#[derive(Clone, Debug)]
+pub struct Snapshot {
+    values: Rc<HashMap<String, ValueType>>,
+    pub_vals: Rc<HashMap<String, ValueType>>,
+    type_values: Rc<HashMap<String, Token>>,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct Env {
     pub values: EnvValueType,
@@ -48,6 +64,7 @@ pub struct Env {
     pub type_values: EnvTypeValueType,
     pub enums: EnvEnumValueType,
     pub mods: Vec<Env>,
+    pub methods: EnvMethodValueType,
     pub locals: Rc<RefCell<HashMap<usize, usize>>>,
     pub enclosing: Option<Rc<RefCell<Env>>>,
 }
@@ -65,6 +82,7 @@ impl Env {
             type_values: get_empty_tc(),
             enums: get_empty_ec(),
             mods: Vec::new(),
+            methods: get_empty_mt(),
             locals: Rc::new(RefCell::new(locals)),
             enclosing: None,
         }
@@ -78,11 +96,29 @@ impl Env {
             type_values: get_empty_tc(),
             enums: get_empty_ec(),
             mods: self.mods.clone(),
+            methods: Rc::clone(&self.methods),
             locals: Rc::clone(&self.locals),
             enclosing: Some(Rc::new(RefCell::new(self.clone()))),
         }
     }
 
+    /// shares the values of this scope, `restore` puts them back, a map is
+    /// only copied when it's written to while a snapshot holds it
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            values: Rc::clone(&self.values.borrow_mut()),
+            pub_vals: Rc::clone(&self.pub_vals.borrow_mut()),
+            type_values: Rc::clone(&self.type_values.borrow_mut()),
+        }
+    }
+
+    /// undoes the changes made to this scope since the snapshot
+    pub fn restore(&self, snapshot: Snapshot) {
+        *self.values.borrow_mut() = snapshot.values;
+        *self.pub_vals.borrow_mut() = snapshot.pub_vals;
+        *self.type_values.borrow_mut() = snapshot.type_values;
+    }
+
     pub fn resolve(&self, locals: HashMap<usize, usize>) {
         for (k, v) in locals.iter() {
             self.locals.borrow_mut().insert(*k, *v);
@@ -92,7 +128,7 @@ impl Env {
     // variable value definitions
 
     pub fn define_var(&self, k: String, v: LiteralType, f: VarKind) {
-        self.values.borrow_mut().insert(
+        write(&self.values).insert(
             k,
             ValueType {
                 value: v,
@@ -102,7 +138,7 @@ impl Env {
     }
 
     pub fn define_pub_var(&self, k: String, v: LiteralType, f: VarKind) {
-        self.pub_vals.borrow_mut().insert(
+        write(&self.pub_vals).insert(
             k,
             ValueType {
                 value: v,
@@ -126,7 +162,7 @@ impl Env {
     // function value definitions
 
     pub fn define_func(&self, k: String, v: LiteralType, f: FuncKind) {
-        self.values.borrow_mut().insert(
+        write(&self.values).insert(
             k,
             ValueType {
                 value: v,
@@ -136,7 +172,7 @@ impl Env {
     }
 
     pub fn define_pub_func(&self, k: String, v: LiteralType, f: FuncKind) {
-        self.pub_vals.borrow_mut().insert(
+        write(&self.pub_vals).insert(
             k,
             ValueType {
                 value: v,
@@ -160,11 +196,11 @@ impl Env {
     // type value definitions
 
     pub fn define_type(&self, k: String, v: Token) {
-        self.type_values.borrow_mut().insert(k, v);
+        write(&self.type_values).insert(k, v);
     }
 
     pub fn define_pub_type(&self, k: String, v: Token) {
-        self.pub_vals.borrow_mut().insert(
+        write(&self.pub_vals).insert(
             k,
             ValueType {
                 value: LiteralType::Void,
@@ -200,7 +236,7 @@ impl Env {
     }
 
     pub fn define_pub_enum(&self, k: String, v: Vec<(Token, Option<Token>)>) {
-        self.pub_vals.borrow_mut().insert(
+        write(&self.pub_vals).insert(
             k,
             ValueType {
                 value: LiteralType::Void,
@@ -231,9 +267,43 @@ impl Env {
         self.enums.borrow_mut().get(k).unwrap_or(&vec![]).clone()
     }
 
+    /// checks this and the enclosing envs for the enum
+    pub fn has_enum(&self, k: &str) -> bool {
+        self.enums.borrow_mut().contains_key(k)
+            || self
+                .enclosing
+                .as_ref()
+                .is_some_and(|env| env.borrow_mut().has_enum(k))
+    }
+
+    // method value definitions
+
+    /// defines a method for the type, returns false if it's already defined
+    pub fn define_method(&self, typ: String, k: String, v: LiteralType) -> bool {
+        let mut methods = self.methods.borrow_mut();
+        let entry = methods.entry(typ).or_default();
+        if entry.contains_key(&k) {
+            return false;
+        }
+        entry.insert(k, v);
+        true
+    }
+
+    pub fn get_method(&self, typ: &str, k: &str) -> Option<LiteralType> {
+        self.methods
+            .borrow_mut()
+            .get(typ)
+            .and_then(|m| m.get(k))
+            .cloned()
+    }
+
     // global
+    pub fn define(&self, k: String, v: ValueType) {
+        write(&self.values).insert(k, v);
+    }
+
     pub fn remove(&self, name: String) {
-        self.values.borrow_mut().remove(&name);
+        write(&self.values).remove(&name);
     }
 
     pub fn get(&self, name: String, id: usize) -> Option<ValueType> {
@@ -243,14 +313,13 @@ impl Env {
 
     pub fn get_int(&self, name: &str, d: Option<usize>) -> Option<ValueType> {
         match d {
-            None => match &self.enclosing {
-                Some(env) => env.borrow_mut().get_int(name, None),
-                None => self
-                    .values
-                    .borrow_mut()
-                    .get(name)
-                    .cloned()
-                    .or_else(|| self.pub_vals.borrow_mut().get(name).cloned()),
+            // unresolved names take the innermost binding
+            None => match self.values.borrow_mut().get(name).cloned() {
+                Some(v) => Some(v),
+                None => match &self.enclosing {
+                    Some(env) => env.borrow_mut().get_int(name, None),
+                    None => self.pub_vals.borrow_mut().get(name).cloned(),
+                },
             },
             Some(depth) => {
                 if depth == 0 {
@@ -268,6 +337,33 @@ impl Env {
         }
     }
 
+    /// takes the value out of a variable, leaving null in its place
+    pub fn take(&self, name: &str, id: usize) -> Option<LiteralType> {
+        let d = self.locals.borrow_mut().get(&id).cloned();
+        self.take_int(name, d)
+    }
+
+    fn take_int(&self, name: &str, d: Option<usize>) -> Option<LiteralType> {
+        match d {
+            None | Some(0) => {
+                if let Some(var) = write(&self.values).get_mut(name) {
+                    return Some(std::mem::replace(&mut var.value, LiteralType::Null));
+                }
+                match (&self.enclosing, d) {
+                    (Some(env), None) => env.borrow_mut().take_int(name, None),
+                    _ => None,
+                }
+            }
+            Some(depth) => match &self.enclosing {
+                Some(env) => env.borrow_mut().take_int(name, Some(depth - 1)),
+                None => {
+                    self.err().throw(E0x502, 0, (0, 0), vec![]);
+                    exit(1);
+                }
+            },
+        }
+    }
+
     pub fn assing(&self, name: String, value: ValueType, id: usize) -> bool {
         let d = self.locals.borrow_mut().get(&id).cloned();
         self.set_int(name.as_str(), value, d)
@@ -275,12 +371,13 @@ impl Env {
 
     pub fn set_int(&self, name: &str, value: ValueType, d: Option<usize>) -> bool {
         if d.is_none() {
+            if self.values.borrow_mut().contains_key(name) {
+                write(&self.values).insert(name.to_string(), value);
+                return true;
+            }
             match &self.enclosing {
                 Some(env) => env.borrow_mut().set_int(name, value, d),
-                None => self
-                    .borrow()
-                    .values
-                    .borrow_mut()
+                None => write(&self.borrow().values)
                     .insert(name.to_string(), value)
                     .is_some(),
             }
@@ -293,7 +390,7 @@ impl Env {
                 }
             };
             if d <= 0 {
-                self.values.borrow_mut().remove(name);
+                write(&self.values).remove(name);
                 true
             } else {
                 match &self.enclosing {
@@ -309,17 +406,26 @@ impl Env {
 }
 
 fn get_empty_rc() -> EnvValueType {
-    Rc::new(RefCell::new(HashMap::new()))
+    Rc::new(RefCell::new(Rc::new(HashMap::new())))
 }
 
 fn get_empty_tc() -> EnvTypeValueType {
-    Rc::new(RefCell::new(HashMap::new()))
+    Rc::new(RefCell::new(Rc::new(HashMap::new())))
+}
+
+/// borrows the map to write to it, copying it first if a snapshot shares it
+fn write<T: Clone>(map: &RefCell<Rc<HashMap<String, T>>>) -> RefMut<'_, HashMap<String, T>> {
+    RefMut::map(map.borrow_mut(), Rc::make_mut)
 }
 
 fn get_empty_ec() -> EnvEnumValueType {
     Rc::new(RefCell::new(HashMap::new()))
 }
 
+fn get_empty_mt() -> EnvMethodValueType {
+    Rc::new(RefCell::new(HashMap::new()))
+}
+
 fn get_empty_md() -> ModEnvValueType {
     Rc::new(RefCell::new(HashMap::new()))
 }
@@ -1,13 +1,16 @@
-use super::env::{Env, ValueKind, ValueType, VarKind};
+use super::env::{Env, FuncKind, ValueKind, ValueType, VarKind};
 use super::types::TypeKind;
-use crate::ast::{LiteralKind, Statement};
-use crate::bundler::parse_expr;
+use crate::ast::LiteralKind;
+use crate::bundler::{interpolation, parse_expr, Part};
 use crate::errors::{Error, ErrorCode::*};
-use crate::interpreter::types::{type_check, typekind_to_literaltype};
+use crate::interpreter::types::{item_type, literal_mismatch, type_check, typekind_to_literaltype};
 use crate::manifest::Project;
 use crate::{
-    ast::{CallType, FuncBody, FuncImpl, LiteralType, Token, TokenType::*},
-    interpreter::run_func,
+    ast::{
+        CallType, FuncBody, FuncImpl, LiteralType, QuoteImpl, Token,
+        TokenType::{self, *},
+    },
+    interpreter::{run_func, with_caller},
 };
 use core::cmp::Eq;
 use std::process::exit;
@@ -22,6 +25,15 @@ pub enum AssignKind {
     Div,
 }
 
+/// an assignment into an item or field of the variable `name`, `target` is the whole path
+#[derive(Clone, Copy)]
+struct PathAssign<'a> {
+    name: &'a Token,
+    target: &'a [Expression],
+    kind: &'a AssignKind,
+    env: &'a Rc<RefCell<Env>>,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum Expression {
     If {
@@ -37,6 +49,8 @@ pub enum Expression {
     Assign {
         id: usize,
         name: Token,
+        /// indices and field names assigned into, `[0, "x"]` for `name[0].x = value`
+        path: Vec<Expression>,
         value: Box<Expression>,
         kind: AssignKind,
     },
@@ -90,10 +104,40 @@ pub enum Expression {
         id: usize,
         expr: Box<Expression>,
     },
+    /// `move name`, takes the value out of the variable instead of copying it
+    Move {
+        id: usize,
+        name: Token,
+    },
+    Quote {
+        id: usize,
+        body: FuncBody,
+    },
+    Method {
+        id: usize,
+        object: Box<Expression>,
+        name: Token,
+        args: Vec<Expression>,
+    },
+    /// `start..end` includes the end, `start..<end` doesn't
     Range {
         id: usize,
-        lhs: usize,
-        rhs: usize,
+        start: Box<Expression>,
+        end: Box<Expression>,
+        inclusive: bool,
+    },
+    Comprehension {
+        id: usize,
+        expr: Box<Expression>,
+        var: Token,
+        iter: Box<Expression>,
+        filter: Option<Box<Expression>>,
+    },
+    /// a call argument passed by its parameter name, `f(x: 1)`
+    Labeled {
+        id: usize,
+        label: Token,
+        value: Box<Expression>,
     },
 }
 
@@ -106,6 +150,8 @@ impl Expression {
     pub fn id(&self) -> usize {
         match self {
             Expression::Range { id, .. } => *id,
+            Expression::Comprehension { id, .. } => *id,
+            Expression::Labeled { id, .. } => *id,
             Expression::Record { id, .. } => *id,
             Expression::Var { id, .. } => *id,
             Expression::Tuple { id, .. } => *id,
@@ -113,6 +159,9 @@ impl Expression {
             Expression::Func { id, .. } => *id,
             Expression::Vec { id, .. } => *id,
             Expression::Await { id, .. } => *id,
+            Expression::Move { id, .. } => *id,
+            Expression::Quote { id, .. } => *id,
+            Expression::Method { id, .. } => *id,
             Expression::Binary { id, .. } => *id,
             Expression::Unary { id, .. } => *id,
             Expression::Value { id, .. } => *id,
@@ -122,6 +171,64 @@ impl Expression {
         }
     }
 
+    /// the parts of a string pattern case, its text, the name bound to the rest
+    /// of the string and if the text is a prefix, `"GET " ++ path` or `name ++ ".abs"`
+    pub fn string_pattern(&self) -> Option<(&Expression, &Token, bool)> {
+        match self {
+            Expression::Binary {
+                left,
+                operator,
+                right,
+                ..
+            } if operator.token == Incr => match (left.as_ref(), right.as_ref()) {
+                (Expression::Var { name, .. }, text) => Some((text, name, false)),
+                (text, Expression::Var { name, .. }) => Some((text, name, true)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// the names a match case binds, the rest of the string for a string pattern
+    /// and the items at their positions for a tuple pattern, `_` binds nothing
+    pub fn pattern_names(&self) -> Vec<&Token> {
+        let names = match self {
+            Expression::Tuple { items, .. } => items
+                .iter()
+                .flat_map(|item| match item {
+                    Expression::Var { name, .. } => vec![name],
+                    Expression::Tuple { .. } => item.pattern_names(),
+                    _ => vec![],
+                })
+                .collect(),
+            _ => self
+                .string_pattern()
+                .map(|(_, name, _)| name)
+                .into_iter()
+                .collect::<Vec<_>>(),
+        };
+        names
+            .into_iter()
+            .filter(|name| name.token != Underscore)
+            .collect()
+    }
+
+    /// builtins have no parameter names to match labeled arguments against
+    fn unlabeled(&self, func: &str, args: &[Expression]) {
+        if let Some(Expression::Labeled { label, .. }) = args
+            .iter()
+            .find(|arg| matches!(arg, Expression::Labeled { .. }))
+        {
+            self.err().throw(
+                E0x422,
+                label.line,
+                label.pos,
+                vec![func.to_string(), label.lexeme.clone()],
+            );
+            exit(1);
+        }
+    }
+
     pub fn to_literal(&self) -> LiteralType {
         match self {
             Expression::Value { value, .. } => value.clone(),
@@ -131,11 +238,97 @@ impl Expression {
 
     pub fn eval(&self, env: Rc<RefCell<Env>>) -> LiteralType {
         match self {
-            Expression::Range { lhs, rhs, .. } => {
-                let range = lhs.clone()..rhs.clone();
-                let vec: Vec<LiteralType> = range.map(|x| LiteralType::Number(x as f32)).collect();
-                LiteralType::Vec(vec)
+            Expression::Range {
+                start,
+                end,
+                inclusive,
+                ..
+            } => {
+                let (start, end) = (start.eval(Rc::clone(&env)), end.eval(Rc::clone(&env)));
+                // chars count up by code point
+                if let (LiteralType::Char(start), LiteralType::Char(end)) = (&start, &end) {
+                    let (start, end) = (*start, *end);
+                    if start > end {
+                        self.err().throw(
+                            E0x437,
+                            0,
+                            (0, 0),
+                            vec![start.to_string(), end.to_string()],
+                        );
+                        exit(1);
+                    }
+                    return LiteralType::Vec(
+                        (start..=end)
+                            .filter(|c| *inclusive || *c != end)
+                            .map(LiteralType::Char)
+                            .collect(),
+                    );
+                }
+                let bound = |value: LiteralType| match value {
+                    LiteralType::Number(n) => n,
+                    v => {
+                        self.err().throw(
+                            E0x301,
+                            0,
+                            (0, 0),
+                            vec!["number".to_string(), v.type_name()],
+                        );
+                        exit(1);
+                    }
+                };
+                let (start, end) = (bound(start), bound(end));
+                // counts up by one from the start, empty when the end is below it
+                let mut items = vec![];
+                let mut n = start;
+                while n < end || (*inclusive && n == end) {
+                    items.push(LiteralType::Number(n));
+                    n += 1.0;
+                }
+                LiteralType::Vec(items)
             }
+            Expression::Comprehension {
+                expr,
+                var,
+                iter,
+                filter,
+                ..
+            } => {
+                let items = match iter.eval(Rc::clone(&env)) {
+                    LiteralType::Vec(items) | LiteralType::Tuple(items) => items,
+                    v => {
+                        self.err().throw(
+                            E0x301,
+                            var.line,
+                            var.pos,
+                            vec!["vector".to_string(), v.type_name()],
+                        );
+                        exit(1);
+                    }
+                };
+                let mut values = vec![];
+                for item in items {
+                    // every element gets its own scope
+                    let item_env = Rc::new(RefCell::new(env.borrow().enclose()));
+                    item_env.borrow().define_var(
+                        var.lexeme.clone(),
+                        item,
+                        VarKind {
+                            is_mut: false,
+                            is_pub: false,
+                            is_func: false,
+                            value_type: var.clone(),
+                        },
+                    );
+                    if let Some(filter) = filter {
+                        if !filter.eval(Rc::clone(&item_env)).is_truthy() {
+                            continue;
+                        }
+                    }
+                    values.push(expr.eval(item_env));
+                }
+                LiteralType::Vec(values)
+            }
+            Expression::Labeled { value, .. } => value.eval(env),
             Expression::If {
                 cond,
                 body,
@@ -153,53 +346,67 @@ impl Expression {
             }
             Expression::Record { fields, .. } => LiteralType::Record(fields.clone()),
             Expression::Assign {
-                name, value, kind, ..
+                name,
+                path,
+                value,
+                kind,
+                ..
             } => {
                 let mut val = (*value).eval(Rc::clone(&env));
+                // `name[i] = value` assigns the variable a copy with the item replaced
+                let mut item = None;
+                let kind = if path.is_empty() {
+                    kind.clone()
+                } else {
+                    let root = env
+                        .borrow()
+                        .get(name.lexeme.clone(), self.id())
+                        .map_or(LiteralType::Null, |v| v.value);
+                    let to = PathAssign {
+                        name,
+                        target: path,
+                        kind,
+                        env: &env,
+                    };
+                    let (root, assigned) = self.assign_path(&to, root, path, val);
+                    val = root;
+                    item = Some(assigned);
+                    AssignKind::Normal
+                };
                 let mut is_mut = false;
+                let mut value_type = name.clone();
                 match env.borrow().get(name.lexeme.clone(), self.id()) {
+                    // `let x: T;` is declared without a value, its first value is checked against `T`
+                    Some(ValueType {
+                        value: LiteralType::Null,
+                        kind: ValueKind::Var(s),
+                    }) if s.value_type.token != Null => {
+                        if !type_check(&s.value_type, &val, &env) {
+                            self.err().throw(
+                                E0x412,
+                                name.line,
+                                name.pos,
+                                vec![name.lexeme.clone()],
+                            );
+                        }
+                        is_mut = s.is_mut;
+                        value_type = s.value_type;
+                    }
                     Some(v) => match v.clone().kind {
                         ValueKind::Var(s) => {
                             if !s.is_mut {
                                 self.err().throw(E0x410, name.line, name.pos, vec![]);
                             }
                             is_mut = true;
+                            value_type = s.value_type.clone();
                             if s.is_pub {
                                 self.err().throw(E0x411, name.line, name.pos, vec![]);
                             }
-                            match v.value {
-                                LiteralType::Number(x) => match kind {
-                                    AssignKind::Plus => match val {
-                                        LiteralType::Number(n) => {
-                                            val = LiteralType::Number(n + x);
-                                        }
-                                        _ => {}
-                                    },
-                                    AssignKind::Minus => match val {
-                                        LiteralType::Number(n) => {
-                                            val = LiteralType::Number(n - x);
-                                        }
-                                        _ => {}
-                                    },
-                                    AssignKind::Mult => match val {
-                                        LiteralType::Number(n) => {
-                                            val = LiteralType::Number(n * x);
-                                        }
-                                        _ => {}
-                                    },
-                                    AssignKind::Div => match val {
-                                        LiteralType::Number(n) => {
-                                            val = LiteralType::Number(n / x);
-                                        }
-                                        _ => {}
-                                    },
-                                    _ => {}
-                                },
-                                _ => {
-                                    if kind.clone() != AssignKind::Normal {
-                                        self.err().throw(E0x414, name.line, name.pos, vec![]);
-                                        exit(1);
-                                    }
+                            match compound(&kind, &v.value, val.clone()) {
+                                Some(result) => val = result,
+                                None => {
+                                    self.err().throw(E0x414, name.line, name.pos, vec![]);
+                                    exit(1);
                                 }
                             }
 
@@ -255,12 +462,23 @@ impl Expression {
                     },
                     None => {}
                 }
+                // `Vec<T>` values keep the item type, `v[0] = "x"` or `v = ["x"]`
+                if let Some((path, expected, found)) =
+                    item_type(&value_type).and_then(|_| literal_mismatch(&value_type, &val))
+                {
+                    self.err().throw(
+                        E0x433,
+                        name.line,
+                        name.pos,
+                        vec![path, name.lexeme.clone(), expected, found],
+                    );
+                }
                 let ass_val = ValueType {
                     kind: ValueKind::Var(VarKind {
                         is_mut,
                         is_pub: false,
                         is_func: false,
-                        value_type: name.clone(),
+                        value_type,
                     }),
                     value: val.clone(),
                 };
@@ -269,7 +487,7 @@ impl Expression {
                     .assing(name.lexeme.clone(), ass_val, self.id());
 
                 if assigned {
-                    val
+                    item.unwrap_or(val)
                 } else {
                     self.err().throw(E0x414, name.line, name.pos, vec![]);
                     exit(1);
@@ -283,7 +501,7 @@ impl Expression {
                     v.value.clone()
                 } else if let Some(v) = env_borrow.values.borrow().get(lexeme) {
                     v.value.clone()
-                } else if env_borrow.enums.borrow().contains_key(lexeme) {
+                } else if env_borrow.has_enum(lexeme) {
                     LiteralType::Enum {
                         parent: name.clone(),
                         name: Token::null(),
@@ -330,37 +548,58 @@ impl Expression {
 
                         LiteralType::Null
                     }
+                    LiteralType::Mod(module) => {
+                        let member = match args.first() {
+                            Some(Expression::Var { name, .. }) => name,
+                            _ => return LiteralType::Null,
+                        };
+                        let value = module.env.borrow().get_int(&member.lexeme, None);
+                        match value.map(|v| v.value) {
+                            // `module::func(args)`
+                            Some(LiteralType::Func(func)) => run_func(func, &args[1..], env),
+                            Some(LiteralType::DeclrFunc(func)) => {
+                                self.unlabeled(
+                                    &format!("{}::{}", module.name, member.lexeme),
+                                    &args[1..],
+                                );
+                                let evals = args[1..]
+                                    .iter()
+                                    .map(|arg| Some(arg.eval(Rc::clone(&env))))
+                                    .collect();
+                                with_caller(&env, || (*func.func).call(evals))
+                            }
+                            Some(value) => value,
+                            None => {
+                                self.err().throw(
+                                    E0x304,
+                                    member.line,
+                                    member.pos,
+                                    vec![format!("{}::{}", module.name, member.lexeme)],
+                                );
+                                exit(1);
+                            }
+                        }
+                    }
                     LiteralType::Func(func) => run_func(func, args, env),
                     LiteralType::DeclrFunc(func) => {
+                        self.unlabeled(&name.to_string(), args);
                         let evals = args
                             .iter()
                             .map(|arg| Some(arg.eval(Rc::clone(&env))))
                             .collect();
 
-                        (*func.func).call(evals)
+                        with_caller(&env, || (*func.func).call(evals))
                     }
-                    LiteralType::Vec(res) => match args.get(0).unwrap() {
-                        Expression::Value { value, .. } => {
-                            if let LiteralType::Number(n) = value {
-                                res.get(*n as usize).unwrap().clone()
-                            } else {
-                                LiteralType::Null
-                            }
-                        }
-                        _ => LiteralType::Null,
-                    },
-                    LiteralType::Tuple(res) => match args.get(0).unwrap() {
-                        Expression::Value { value, .. } => {
-                            if let LiteralType::Number(n) = value {
-                                res.get(*n as usize).unwrap().clone()
-                            } else {
-                                LiteralType::Null
-                            }
-                        }
-                        _ => LiteralType::Null,
-                    },
-                    LiteralType::String(s) => match args.get(0).unwrap().eval(env) {
-                        LiteralType::Number(n) => {
+                    LiteralType::Vec(mut res) | LiteralType::Tuple(mut res) => {
+                        let i = self.index(name, args, res.len(), env);
+                        res.swap_remove(i)
+                    }
+                    LiteralType::Bytes(b) => {
+                        let i = self.index(name, args, b.len(), env);
+                        LiteralType::Number(b[i] as f32)
+                    }
+                    LiteralType::String(s) => match args.first().map(|arg| arg.eval(env)) {
+                        Some(LiteralType::Number(n)) => {
                             let mut res = LiteralType::Null;
                             for (i, c) in s.chars().enumerate() {
                                 if i == n as usize {
@@ -371,16 +610,17 @@ impl Expression {
                         }
                         _ => LiteralType::Null,
                     },
-                    LiteralType::Record(rec) => match args.get(0).unwrap() {
-                        Expression::Value { value, .. } => {
+                    LiteralType::Record(rec) => match args.first() {
+                        Some(Expression::Value { value, .. }) => {
                             if let LiteralType::String(s) = value {
-                                let mut res = LiteralType::Null;
-                                for (k, v) in rec {
-                                    if k == *s {
-                                        res = v.eval(Rc::clone(&env));
+                                match rec.iter().rfind(|(k, _)| k == s) {
+                                    Some((_, v)) => v.eval(Rc::clone(&env)),
+                                    // computed by a getter of the record's type
+                                    None => {
+                                        let declared = declared_type(name, &env);
+                                        getter(LiteralType::Record(rec.clone()), s, declared, env)
                                     }
                                 }
-                                res
                             } else {
                                 LiteralType::Null
                             }
@@ -393,32 +633,23 @@ impl Expression {
             Expression::Grouping { expression, .. } => expression.eval(env),
             Expression::Value { value, .. } => {
                 let v = match value.clone() {
-                    LiteralType::String(s) => {
+                    LiteralType::String(s) if s.contains(['{', '}']) => {
                         let mut result = String::new();
-                        let mut idx = 0;
-
-                        while let Some(start) = s[idx..].find('{') {
-                            result.push_str(&s[idx..idx + start]);
-                            let start_idx = idx + start + 1;
-
-                            if let Some(end) = s[start_idx..].find('}') {
-                                let expr = &s[start_idx..start_idx + end];
-                                let eval_result =
-                                    match parse_expr(expr, self.err()).eval(Rc::clone(&env)) {
+                        for part in interpolation(&s) {
+                            match part {
+                                Part::Text(text) => result.push_str(&text),
+                                Part::Expr(expr) => {
+                                    result.push_str(&match parse_expr(&expr, self.err())
+                                        .eval(Rc::clone(&env))
+                                    {
                                         LiteralType::String(eval_s) => eval_s,
                                         LiteralType::Number(eval_n) => eval_n.to_string(),
                                         LiteralType::Boolean(eval_b) => eval_b.to_string(),
                                         _ => "null".to_string(),
-                                    };
-
-                                result.push_str(&eval_result);
-                                idx = start_idx + end + 1;
-                            } else {
-                                break;
+                                    })
+                                }
                             }
                         }
-
-                        result.push_str(&s[idx..]);
                         LiteralType::String(result)
                     }
                     c => c,
@@ -438,37 +669,8 @@ impl Expression {
                 let call = FuncImpl {
                     name: name.lexeme.clone(),
                     value_type: value_type.clone(),
-                    body: FuncBody::Statements(match body {
-                        FuncBody::Statements(stmts) => stmts
-                            .iter()
-                            .map(|stmt| {
-                                if let Statement::Return { expr } = stmt {
-                                    let v = &(*expr).eval(Rc::clone(&env));
-                                    if !type_check(value_type, v, &env) {
-                                        self.err().throw(
-                                            E0x301,
-                                            name.line,
-                                            name.pos,
-                                            vec![value_type.clone().lexeme, v.to_string()],
-                                        );
-                                    }
-                                }
-                                stmt.clone()
-                            })
-                            .collect(),
-                        FuncBody::Expression(e) => {
-                            let v = &(*e).eval(Rc::clone(&env));
-                            if !type_check(value_type, v, &env) {
-                                self.err().throw(
-                                    E0x301,
-                                    name.line,
-                                    name.pos,
-                                    vec![value_type.clone().lexeme, v.to_string()],
-                                );
-                            }
-                            vec![Statement::Expression { expr: *e.clone() }]
-                        }
-                    }),
+                    // the return value is checked when called, the parameters are unbound here
+                    body: body.clone(),
                     params: params
                         .iter()
                         .map(|(name, value_type)| (name.clone(), value_type.clone()))
@@ -476,6 +678,8 @@ impl Expression {
                     is_pub: *is_pub,
                     is_async: *is_async,
                     env: Rc::clone(&env),
+                    bound: vec![],
+                    memo: None,
                 };
                 let func = LiteralType::Func(call);
                 func
@@ -495,6 +699,35 @@ impl Expression {
                     .clone(),
             ),
             Expression::Await { .. } => LiteralType::Null,
+            // a moved variable is left null, the resolver rejects reading it again
+            Expression::Move { name, .. } => match env.borrow().take(&name.lexeme, self.id()) {
+                Some(value) => value,
+                None => {
+                    self.err()
+                        .throw(E0x304, name.line, name.pos, vec![name.lexeme.clone()]);
+                    exit(1);
+                }
+            },
+            Expression::Method {
+                object, name, args, ..
+            } => {
+                let value = object.eval(Rc::clone(&env));
+                let method = env.borrow().get_method(&value.type_name(), &name.lexeme);
+                match method {
+                    Some(LiteralType::Func(func)) => {
+                        // the receiver is passed as `self`
+                        let mut args = args.clone();
+                        args.insert(0, Expression::Value { id: 0, value });
+                        run_func(func, &args, env)
+                    }
+                    _ => {
+                        self.err()
+                            .throw(E0x304, name.line, name.pos, vec![name.lexeme.clone()]);
+                        exit(1);
+                    }
+                }
+            }
+            Expression::Quote { body, .. } => LiteralType::Quote(QuoteImpl { body: body.clone() }),
             Expression::Binary {
                 left,
                 operator,
@@ -505,6 +738,107 @@ impl Expression {
         }
     }
 
+    /// `container` with the item or field at `path` replaced by `val`, applying the kind
+    /// of the assignment to the old one, and the new item
+    fn assign_path(
+        &self,
+        to: &PathAssign,
+        container: LiteralType,
+        path: &[Expression],
+        val: LiteralType,
+    ) -> (LiteralType, LiteralType) {
+        let PathAssign {
+            name,
+            target,
+            kind,
+            env,
+        } = *to;
+        let fail = |reason: String| -> ! {
+            self.err().throw(
+                E0x436,
+                name.line,
+                name.pos,
+                vec![target_name(name, target), reason],
+            );
+            exit(1);
+        };
+        let Some((key, rest)) = path.split_first() else {
+            let Some(item) = compound(kind, &container, val) else {
+                self.err().throw(E0x414, name.line, name.pos, vec![]);
+                exit(1);
+            };
+            return (item.clone(), item);
+        };
+        match (container, key.eval(Rc::clone(env))) {
+            (LiteralType::Vec(mut items), LiteralType::Number(n)) => {
+                if n < 0.0 || n.fract() != 0.0 || n as usize >= items.len() {
+                    fail(format!(
+                        "index {} is out of bounds for length {}",
+                        n,
+                        items.len()
+                    ));
+                }
+                let item = std::mem::replace(&mut items[n as usize], LiteralType::Null);
+                let (item, assigned) = self.assign_path(to, item, rest, val);
+                items[n as usize] = item;
+                (LiteralType::Vec(items), assigned)
+            }
+            (LiteralType::Record(mut fields), LiteralType::String(field)) => {
+                let Some(i) = fields.iter().position(|(k, _)| *k == field) else {
+                    fail(format!("the record has no field '{}'", field));
+                };
+                let old = fields[i].1.eval(Rc::clone(env));
+                let (value, assigned) = self.assign_path(to, old, rest, val);
+                fields[i].1 = Expression::Value {
+                    id: self.id(),
+                    value,
+                };
+                (LiteralType::Record(fields), assigned)
+            }
+            (container, key) => fail(format!(
+                "a {} can't be indexed by a {}",
+                container.type_name(),
+                key.type_name()
+            )),
+        }
+    }
+
+    /// the index of `name[i]` into a vector or tuple of `len` items,
+    /// indices are whole numbers below the length
+    fn index(
+        &self,
+        name: &Expression,
+        args: &[Expression],
+        len: usize,
+        env: Rc<RefCell<Env>>,
+    ) -> usize {
+        let name = match name {
+            Expression::Var { name, .. } => name.clone(),
+            _ => Token::null(),
+        };
+        let fail = |reason: String| -> ! {
+            self.err().throw(
+                E0x436,
+                name.line,
+                name.pos,
+                vec![target_name(&name, args), reason],
+            );
+            exit(1);
+        };
+        let [index] = args else {
+            fail(format!("an index is one value, got {}", args.len()));
+        };
+        match index.eval(env) {
+            LiteralType::Number(n) if n >= 0.0 && n.fract() == 0.0 && (n as usize) < len => {
+                n as usize
+            }
+            LiteralType::Number(n) => {
+                fail(format!("index {} is out of bounds for length {}", n, len))
+            }
+            key => fail(format!("an index can't be a {}", key.type_name())),
+        }
+    }
+
     fn eval_unary(
         &self,
         operator: &Token,
@@ -537,46 +871,32 @@ impl Expression {
                 if left.is_truthy() {
                     return left;
                 }
-                return right;
+                right
             }
             (_, DblAnd, _) => {
                 if !left.is_truthy() {
                     return left.is_truthy_literal();
                 }
-                return right;
-            }
-            (LiteralType::Number(a), Prcnt, LiteralType::Number(b)) => {
-                return LiteralType::Number(a % b);
-            }
-            (LiteralType::Number(a), Mul, LiteralType::Number(b)) => {
-                return LiteralType::Number(a * b);
-            }
-            (LiteralType::Number(a), Min, LiteralType::Number(b)) => {
-                return LiteralType::Number(a - b);
-            }
-            (LiteralType::Number(a), Div, LiteralType::Number(b)) => {
-                return LiteralType::Number(a / b);
-            }
-            (LiteralType::Number(a), Plus, LiteralType::Number(b)) => {
-                return LiteralType::Number(a + b);
-            }
-            (LiteralType::Number(a), Gr, LiteralType::Number(b)) => {
-                return LiteralType::Boolean(a > b);
+                right
             }
+            (LiteralType::Number(a), Prcnt, LiteralType::Number(b)) => LiteralType::Number(a % b),
+            (LiteralType::Number(a), Mul, LiteralType::Number(b)) => LiteralType::Number(a * b),
+            (LiteralType::Number(a), Min, LiteralType::Number(b)) => LiteralType::Number(a - b),
+            (LiteralType::Number(a), Div, LiteralType::Number(b)) => LiteralType::Number(a / b),
+            (LiteralType::Number(a), Plus, LiteralType::Number(b)) => LiteralType::Number(a + b),
+            (LiteralType::Number(a), Sqr, LiteralType::Number(b)) => LiteralType::Number(a.powf(b)),
+            (LiteralType::String(a), Plus, LiteralType::String(b)) => LiteralType::String(a + &b),
+            (LiteralType::Number(a), Gr, LiteralType::Number(b)) => LiteralType::Boolean(a > b),
             (LiteralType::Number(a), GrOrEq, LiteralType::Number(b)) => {
-                return LiteralType::Boolean(a >= b);
-            }
-            (LiteralType::Number(a), Ls, LiteralType::Number(b)) => {
-                return LiteralType::Boolean(a < b);
+                LiteralType::Boolean(a >= b)
             }
+            (LiteralType::Number(a), Ls, LiteralType::Number(b)) => LiteralType::Boolean(a < b),
             (LiteralType::Number(a), LsOrEq, LiteralType::Number(b)) => {
-                return LiteralType::Boolean(a <= b);
-            }
-            (LiteralType::Number(a), Eq, LiteralType::Number(b)) => {
-                return LiteralType::Boolean(a == b);
+                LiteralType::Boolean(a <= b)
             }
+            (LiteralType::Number(a), Eq, LiteralType::Number(b)) => LiteralType::Boolean(a == b),
             (LiteralType::Number(a), BangEq, LiteralType::Number(b)) => {
-                return LiteralType::Boolean(a != b);
+                LiteralType::Boolean(a != b)
             }
             (LiteralType::Number(a), Plus, LiteralType::Vec(v)) => {
                 let nums = v
@@ -590,7 +910,7 @@ impl Expression {
                     })
                     .collect();
 
-                return LiteralType::Vec(nums);
+                LiteralType::Vec(nums)
             }
             (LiteralType::Number(a), Min, LiteralType::Vec(v)) => {
                 let nums = v
@@ -604,7 +924,7 @@ impl Expression {
                     })
                     .collect();
 
-                return LiteralType::Vec(nums);
+                LiteralType::Vec(nums)
             }
             (LiteralType::Number(a), Mul, LiteralType::Vec(v)) => {
                 let nums = v
@@ -618,7 +938,7 @@ impl Expression {
                     })
                     .collect();
 
-                return LiteralType::Vec(nums);
+                LiteralType::Vec(nums)
             }
             (LiteralType::Number(a), Div, LiteralType::Vec(v)) => {
                 let nums = v
@@ -632,37 +952,47 @@ impl Expression {
                     })
                     .collect();
 
-                return LiteralType::Vec(nums);
-            }
-            (LiteralType::String(a), Eq, LiteralType::String(b)) => {
-                return LiteralType::Boolean(a == b);
+                LiteralType::Vec(nums)
             }
+            (LiteralType::String(a), Eq, LiteralType::String(b)) => LiteralType::Boolean(a == b),
             (LiteralType::String(a), BangEq, LiteralType::String(b)) => {
-                return LiteralType::Boolean(a != b);
-            }
-            (LiteralType::Char(a), Eq, LiteralType::Char(b)) => {
-                return LiteralType::Boolean(a == b);
+                LiteralType::Boolean(a != b)
             }
-            (LiteralType::Char(a), BangEq, LiteralType::Char(b)) => {
-                return LiteralType::Boolean(a != b);
+            (LiteralType::Char(a), Eq, LiteralType::Char(b)) => LiteralType::Boolean(a == b),
+            (LiteralType::Char(a), BangEq, LiteralType::Char(b)) => LiteralType::Boolean(a != b),
+            // strings and chars are ordered lexicographically, by code points
+            (LiteralType::String(a), Gr | GrOrEq | Ls | LsOrEq, LiteralType::String(b)) => {
+                LiteralType::Boolean(ordered(a.cmp(&b), &operator.token))
             }
-            (LiteralType::Boolean(a), Eq, LiteralType::Boolean(b)) => {
-                return LiteralType::Boolean(a == b);
+            (LiteralType::Char(a), Gr | GrOrEq | Ls | LsOrEq, LiteralType::Char(b)) => {
+                LiteralType::Boolean(ordered(a.cmp(&b), &operator.token))
             }
+            (LiteralType::Boolean(a), Eq, LiteralType::Boolean(b)) => LiteralType::Boolean(a == b),
             (LiteralType::Boolean(a), BangEq, LiteralType::Boolean(b)) => {
-                return LiteralType::Boolean(a != b);
+                LiteralType::Boolean(a != b)
             }
-            (LiteralType::Null, Eq, LiteralType::Null) => {
-                return LiteralType::Boolean(true);
+            (LiteralType::Null, Eq, LiteralType::Null) => LiteralType::Boolean(true),
+            (LiteralType::Null, BangEq, LiteralType::Null) => LiteralType::Boolean(false),
+            (_, Eq, _) => LiteralType::Boolean(left == right),
+            (_, BangEq, _) => LiteralType::Boolean(left != right),
+            // membership, `x in [x, y]`, `"ab" in "abc"`
+            (_, In, LiteralType::Vec(v)) | (_, In, LiteralType::Tuple(v)) => {
+                LiteralType::Boolean(v.contains(&left))
             }
-            (LiteralType::Null, BangEq, LiteralType::Null) => {
-                return LiteralType::Boolean(false);
+            (LiteralType::String(a), In, LiteralType::String(b)) => {
+                LiteralType::Boolean(b.contains(a.as_str()))
             }
-            (_, Eq, _) => {
-                return LiteralType::Boolean(false);
+            (LiteralType::Char(a), In, LiteralType::String(b)) => {
+                LiteralType::Boolean(b.contains(a))
             }
-            (_, BangEq, _) => {
-                return LiteralType::Boolean(false);
+            (_, In, _) => {
+                self.err().throw(
+                    E0x301,
+                    operator.line,
+                    operator.pos,
+                    vec!["vector or string".to_string(), right.type_name()],
+                );
+                exit(1);
             }
             _ => LiteralType::Null,
         }
@@ -672,9 +1002,33 @@ impl Expression {
 impl fmt::Display for Expression {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Expression::Range { lhs, rhs, .. } => {
-                write!(f, "{}..{}", lhs, rhs)
+            Expression::Range {
+                start,
+                end,
+                inclusive,
+                ..
+            } => {
+                write!(
+                    f,
+                    "{}{}{}",
+                    start,
+                    if *inclusive { ".." } else { "..<" },
+                    end
+                )
             }
+            Expression::Comprehension {
+                expr,
+                var,
+                iter,
+                filter,
+                ..
+            } => match filter {
+                Some(filter) => {
+                    write!(f, "[{} for {} in {} if {}]", expr, var.lexeme, iter, filter)
+                }
+                None => write!(f, "[{} for {} in {}]", expr, var.lexeme, iter),
+            },
+            Expression::Labeled { label, value, .. } => write!(f, "{}: {}", label.lexeme, value),
             Expression::If {
                 cond,
                 body,
@@ -700,7 +1054,9 @@ impl fmt::Display for Expression {
                 }
                 write!(f, "{{{}}}", fields_str)
             }
-            Expression::Assign { name, value, .. } => write!(f, "{} = {}", name.lexeme, value),
+            Expression::Assign {
+                name, path, value, ..
+            } => write!(f, "{} = {}", target_name(name, path), value),
             Expression::Var { name, .. } => write!(f, "{}", name.lexeme),
             Expression::Call { name, args, .. } => {
                 let mut args_str = String::new();
@@ -727,6 +1083,17 @@ impl fmt::Display for Expression {
                 write!(f, "({})", items_str)
             }
             Expression::Await { expr, .. } => write!(f, "await {}", expr),
+            Expression::Move { name, .. } => write!(f, "move {}", name.lexeme),
+            Expression::Method {
+                object, name, args, ..
+            } => {
+                let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+                write!(f, "{}.{}({})", object, name.lexeme, args.join(", "))
+            }
+            Expression::Quote { body, .. } => match body {
+                FuncBody::Expression(expr) => write!(f, "quote {{ {} }}", expr),
+                FuncBody::Statements(_) => write!(f, "quote {{ .. }}"),
+            },
             Expression::Binary {
                 left,
                 operator,
@@ -740,4 +1107,121 @@ impl fmt::Display for Expression {
     }
 }
 
+/// the value of a compound assignment, `x += n`, `None` when the operator doesn't apply
+fn compound(kind: &AssignKind, old: &LiteralType, val: LiteralType) -> Option<LiteralType> {
+    match (kind, old, val) {
+        (AssignKind::Normal, _, val) => Some(val),
+        (AssignKind::Plus, LiteralType::Number(a), LiteralType::Number(b)) => {
+            Some(LiteralType::Number(a + b))
+        }
+        (AssignKind::Minus, LiteralType::Number(a), LiteralType::Number(b)) => {
+            Some(LiteralType::Number(a - b))
+        }
+        (AssignKind::Mult, LiteralType::Number(a), LiteralType::Number(b)) => {
+            Some(LiteralType::Number(a * b))
+        }
+        (AssignKind::Div, LiteralType::Number(a), LiteralType::Number(b)) => {
+            Some(LiteralType::Number(a / b))
+        }
+        // numbers given a value of another type take it
+        (_, LiteralType::Number(_), val) => Some(val),
+        _ => None,
+    }
+}
+
+/// an assignment target as written, `v[0].x`
+pub fn target_name(name: &Token, path: &[Expression]) -> String {
+    path.iter()
+        .fold(name.lexeme.clone(), |target, key| match key {
+            Expression::Value {
+                value: LiteralType::String(field),
+                ..
+            } => format!("{}.{}", target, field),
+            key => format!("{}[{}]", target, key),
+        })
+}
+
+/// the type a variable or parameter is declared with, `B` of `let b: B = ...`
+fn declared_type(expr: &Expression, env: &Rc<RefCell<Env>>) -> Option<String> {
+    let Expression::Var { name, id } = expr else {
+        return None;
+    };
+    match env.borrow().get(name.lexeme.clone(), *id)?.kind {
+        ValueKind::Var(VarKind { value_type, .. }) => Some(value_type.lexeme),
+        // parameters are defined with the parameters of their function
+        ValueKind::Func(FuncKind { params, .. }) => params
+            .into_iter()
+            .find(|(param, _)| *param == name.lexeme)
+            .map(|(_, typ)| typ),
+        _ => None,
+    }
+}
+
+/// runs the getter `name` of the record type the value is declared with, or else of a
+/// record type the value has, records are typed by their fields, so the type with the
+/// most fields is taken when more have it
+fn getter(
+    record: LiteralType,
+    name: &str,
+    declared: Option<String>,
+    env: Rc<RefCell<Env>>,
+) -> LiteralType {
+    let declared = declared.and_then(|typ| match env.borrow().get_method(&typ, name) {
+        Some(LiteralType::Func(func)) => Some(func),
+        _ => None,
+    });
+    if let Some(func) = declared {
+        return run_func(
+            func,
+            &[Expression::Value {
+                id: 0,
+                value: record,
+            }],
+            env,
+        );
+    }
+    let methods = env.borrow().methods.borrow().clone();
+    let getter = methods
+        .iter()
+        .filter_map(|(typ, methods)| match methods.get(name) {
+            Some(LiteralType::Func(func)) => Some((typ, func)),
+            _ => None,
+        })
+        .filter_map(|(typ, func)| {
+            let (param, param_type) = func.params.first()?;
+            let Some(LiteralKind::Type(t)) = &param_type.value else {
+                return None;
+            };
+            let TypeKind::Record { fields } = t.as_ref() else {
+                return None;
+            };
+            if param.lexeme != "self" || !type_check(param_type, &record, &env) {
+                return None;
+            }
+            Some((fields.len(), typ, func))
+        })
+        .max_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(a.1)));
+    match getter {
+        Some((_, _, func)) => run_func(
+            func.clone(),
+            &[Expression::Value {
+                id: 0,
+                value: record,
+            }],
+            env,
+        ),
+        None => LiteralType::Null,
+    }
+}
+
+/// the result of an ordering operator, `>`, `>=`, `<` or `<=`
+fn ordered(ord: std::cmp::Ordering, operator: &TokenType) -> bool {
+    match operator {
+        Gr => ord.is_gt(),
+        GrOrEq => ord.is_ge(),
+        Ls => ord.is_lt(),
+        _ => ord.is_le(),
+    }
+}
+
 // @todo better organize it
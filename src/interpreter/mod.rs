@@ -2,15 +2,16 @@ pub mod env;
 pub mod expr;
 pub mod types;
 use crate::ast::{
-    Destruct, FuncBody, FuncImpl, LiteralKind, LiteralType,
+    Destruct, FuncBody, FuncImpl, LiteralKind, LiteralType, ModImpl, QuoteImpl,
     Statement::{self, *},
-    Token, TokenType,
+    TestKind, Token, TokenType,
 };
 use crate::bundler::interpreter_mod;
 use crate::errors::{raw, Error, ErrorCode::*};
-use crate::interpreter::types::type_check;
+use crate::interpreter::types::{bound_of, generic_of, record_in_order, tuple_types, type_check};
 use crate::manifest::Project;
 use crate::std::StdFunc;
+use coloredpp::Colorize;
 use env::{Env, FuncKind, ValueKind, VarKind};
 use expr::Expression;
 use std::cell::RefCell;
@@ -56,6 +57,8 @@ impl Interpreter {
         if !project.clone().disable_std && project.clone().load_std {
             let mut std_core_io = StdFunc::new(Rc::clone(&int.env), int.project.test);
             std_core_io.load_core_io();
+            std_core_io.load_core_meta();
+            std_core_io.load_core_cmp();
         }
         int
     }
@@ -80,6 +83,8 @@ impl Interpreter {
         if is_mod {
             let mut std_core_io = StdFunc::new(env, false);
             std_core_io.load_core_io();
+            std_core_io.load_core_meta();
+            std_core_io.load_core_cmp();
         }
         int
     }
@@ -89,6 +94,7 @@ impl Interpreter {
     /// set order to 1 if statement is inside the block
     pub fn interpret(&mut self, stmts: Vec<&Statement>, order: usize) -> Rc<RefCell<Env>> {
         self.order = order;
+        self.hoist(&stmts);
         for stmt in stmts {
             match stmt {
                 Statement::Expression { expr } => {
@@ -96,7 +102,8 @@ impl Interpreter {
                 }
                 Block { stmts } => self.block(stmts.clone()),
                 Var { .. } => self.variable(stmt),
-                Func { .. } => self.func(stmt),
+                // functions are hoisted
+                Func { .. } => {}
                 Return { expr } => {
                     let value = expr.eval(Rc::clone(&self.env));
                     self.specs.borrow_mut().insert("return".to_string(), value);
@@ -127,13 +134,42 @@ impl Interpreter {
                 } => self.types(name, value, *is_pub),
                 Statement::Record { .. } => self.record(stmt),
                 Mod { src, name } => self.mods(src, name.clone()),
-                Use { src, names, all } => self.uses(src, names.clone(), *all),
+                Use {
+                    src,
+                    names,
+                    all,
+                    alias,
+                } => match alias {
+                    Some(alias) => self.use_mod(src, alias),
+                    None => self.uses(src, names.clone(), *all),
+                },
                 Sh { cmd } => self.sh(cmd),
+                Impl { name, methods } => self.impls(name, methods),
+            }
+            // the rest of the block is skipped up to the loop or the function
+            if self.is_interrupted() {
+                break;
             }
         }
         Rc::clone(&self.env)
     }
 
+    /// checks if `break` or `return` has been executed, and not handled yet
+    fn is_interrupted(&self) -> bool {
+        let specs = self.specs.borrow();
+        specs.contains_key("break") || specs.contains_key("return")
+    }
+
+    /// defines functions before running the statements,
+    /// so they can be called before their declaration
+    fn hoist(&mut self, stmts: &[&Statement]) {
+        for stmt in stmts {
+            if let Func { .. } = stmt {
+                self.func(stmt);
+            }
+        }
+    }
+
     fn block(&mut self, stmts: Vec<Statement>) {
         let new_env = self.env.borrow_mut().enclose();
         let prev_env = Rc::clone(&self.env);
@@ -152,6 +188,7 @@ impl Interpreter {
             is_pub,
             pub_names,
             is_func,
+            constraint,
         } = stmt
         {
             if value.is_some() {
@@ -167,10 +204,48 @@ impl Interpreter {
                 }
 
                 let vl = value.clone().unwrap().eval(Rc::clone(&self.env));
+                // types of the destructured tuple entries
+                let types = tuple_types(value_type);
                 // @todo replace value_type with option<Token>
                 // don't type check during type inference
                 if value_type.token != TokenType::Null {
-                    if !type_check(&value_type, &vl, &self.env) {
+                    // check destructured tuples name by name
+                    if let (Some(Destruct::Tuple), LiteralType::Tuple(entries), Some(types)) =
+                        (destruct, &vl, &types)
+                    {
+                        for ((name, entry), typ) in names.iter().zip(entries).zip(types) {
+                            if !type_check(typ, entry, &self.env) {
+                                self.error.throw(
+                                    E0x301,
+                                    name.line,
+                                    name.pos,
+                                    vec![typ.lexeme.clone(), entry.to_string()],
+                                );
+                            }
+                        }
+                    } else if !type_check(value_type, &vl, &self.env) {
+                        // suggest the type of the value when it's one that can be declared
+                        if names.len() == 1
+                            && vl.is_primitive()
+                            && matches!(
+                                value_type.token,
+                                TokenType::NumIdent
+                                    | TokenType::StrIdent
+                                    | TokenType::CharIdent
+                                    | TokenType::BoolIdent
+                            )
+                        {
+                            self.error.throw(
+                                E0x432,
+                                names[0].line,
+                                names[0].pos,
+                                vec![
+                                    names[0].lexeme.clone(),
+                                    value_type.lexeme.clone(),
+                                    vl.type_name(),
+                                ],
+                            );
+                        }
                         self.error.throw(
                             E0x301,
                             names[0].line,
@@ -183,7 +258,7 @@ impl Interpreter {
                 // hande variables in modules
                 if self.is_mod && self.order == 0 && *is_pub {
                     // define variables in the module
-                    let val = value.as_ref().unwrap().eval(Rc::clone(&self.env));
+                    let val = record_in_order(value_type, vl.clone(), &self.env);
                     pub_names.iter().for_each(|name| {
                         self.env.borrow_mut().define_mod_var(
                             self.mod_src.clone().unwrap(),
@@ -238,7 +313,7 @@ impl Interpreter {
                 };
                 // hande normal variable
                 if !self.is_mod {
-                    let val = value.clone().unwrap().eval(Rc::clone(&self.env));
+                    let val = record_in_order(value_type, vl, &self.env);
 
                     // handle the name based on the value type for destructuring
                     for (index, name) in names.clone().iter().enumerate() {
@@ -280,18 +355,24 @@ impl Interpreter {
                                         .expect("failed to destructure a tuple")
                                         .clone();
 
+                                    // each name keeps the type of its entry
+                                    let mut var_kind = var_kind.clone();
+                                    if let Some(typ) = types.as_ref().and_then(|t| t.get(index)) {
+                                        var_kind.value_type = typ.clone();
+                                    }
+
                                     // handle publicty
                                     if *is_pub {
                                         self.env.borrow_mut().define_pub_var(
                                             name.lexeme.clone(),
                                             entry,
-                                            var_kind.clone(),
+                                            var_kind,
                                         );
                                     } else {
                                         self.env.borrow_mut().define_var(
                                             name.lexeme.clone(),
                                             entry,
-                                            var_kind.clone(),
+                                            var_kind,
                                         );
                                     }
                                 }
@@ -336,6 +417,18 @@ impl Interpreter {
                         }
                     }
 
+                    // check the constraint with the bound names
+                    if let Some(constraint) = constraint {
+                        if !constraint.eval(Rc::clone(&self.env)).is_truthy() {
+                            self.error.throw(
+                                E0x417,
+                                names[0].line,
+                                names[0].pos,
+                                vec![names[0].lexeme.clone(), constraint.to_string()],
+                            );
+                        }
+                    }
+
                     // public definition
                     if *is_pub {
                         for name in pub_names {
@@ -376,7 +469,6 @@ impl Interpreter {
     fn func(&mut self, stmt: &Statement) {
         if let Statement::Func {
             name,
-            body,
             params,
             is_async,
             is_pub,
@@ -386,21 +478,6 @@ impl Interpreter {
             let is_async = *is_async;
             let is_pub = *is_pub;
 
-            // evaluate return statement
-            match body {
-                FuncBody::Statements(statements) => {
-                    for statement in statements {
-                        if let Statement::Return { expr } = statement {
-                            expr.eval(Rc::clone(&self.env));
-                        }
-                    }
-                }
-                // for short functions
-                FuncBody::Expression(expression) => {
-                    expression.eval(Rc::clone(&self.env));
-                }
-            }
-
             // publicity is disabled in side effects
             if is_pub && !self.project.side_effects {
                 self.error.throw(E0x415, name.line, name.pos, vec![]);
@@ -447,6 +524,29 @@ impl Interpreter {
         }
     }
 
+    fn impls(&mut self, name: &Token, methods: &[Statement]) {
+        for method in methods {
+            let mut call = self.create_func(method);
+            // `self` takes the implemented type
+            if let Some((param, typ)) = call.params.first_mut() {
+                if param.lexeme == "self" && typ.token == TokenType::AnyIdent {
+                    *typ = name.clone();
+                }
+            }
+            let method_name = call.name.clone();
+            let defined = self.env.borrow().define_method(
+                name.lexeme.clone(),
+                method_name.clone(),
+                LiteralType::Func(call),
+            );
+            // extensions can't override each other
+            if !defined {
+                self.error
+                    .throw(E0x305, name.line, name.pos, vec![method_name]);
+            }
+        }
+    }
+
     fn ifs(&mut self, stmt: &Statement) {
         if let Statement::If {
             cond,
@@ -489,8 +589,9 @@ impl Interpreter {
                 Some(i) => {
                     for _ in 0..i.clone() {
                         self.interpret(body.iter().map(|x| x).collect(), 1);
-                        if self.specs.borrow_mut().get("break").is_some() {
-                            self.specs.borrow_mut().remove("break");
+                        if self.specs.borrow_mut().remove("break").is_some()
+                            || self.is_interrupted()
+                        {
                             break;
                         }
                     }
@@ -498,8 +599,7 @@ impl Interpreter {
                 // infinite loop
                 None => loop {
                     self.interpret(body.iter().map(|x| x).collect(), 1);
-                    if self.specs.borrow_mut().get("break").is_some() {
-                        self.specs.borrow_mut().remove("break");
+                    if self.specs.borrow_mut().remove("break").is_some() || self.is_interrupted() {
                         break;
                     }
                 },
@@ -512,8 +612,7 @@ impl Interpreter {
             // execute code while the condition is truthy
             while cond.eval(Rc::clone(&self.env)).is_truthy() {
                 self.interpret(body.iter().map(|x| x).collect(), 1);
-                if self.specs.borrow_mut().get("break").is_some() {
-                    self.specs.borrow_mut().remove("break");
+                if self.specs.borrow_mut().remove("break").is_some() || self.is_interrupted() {
                     break;
                 }
             }
@@ -565,8 +664,7 @@ impl Interpreter {
                     );
 
                     self.interpret(body.iter().map(|x| x).collect(), 1);
-                    if self.specs.borrow_mut().get("break").is_some() {
-                        self.specs.borrow_mut().remove("break");
+                    if self.specs.borrow_mut().remove("break").is_some() || self.is_interrupted() {
                         break;
                     }
                 }
@@ -582,8 +680,8 @@ impl Interpreter {
     fn matchs(
         &mut self,
         cond: &Expression,
-        cases: Vec<(Expression, FuncBody)>,
-        def_case: &FuncBody,
+        cases: Vec<(Expression, Option<Expression>, FuncBody)>,
+        def_case: &Option<FuncBody>,
     ) {
         if !self.is_mod {
             // if case has been executed
@@ -602,6 +700,9 @@ impl Interpreter {
             // - require def case
             // - don't allow any other case
 
+            // cases are tried in order, a guard is evaluated only after its
+            // pattern matches, and the first case whose guard holds runs
+
             // vector, tuple, record, .., other matching:
             // - don't allow pattern matching
             match condition.clone() {
@@ -611,7 +712,7 @@ impl Interpreter {
                         raw("please specify the enum name, you are trying to match");
                     }
 
-                    for (expr, body) in cases {
+                    for (expr, guard, body) in cases {
                         let body = match body {
                             FuncBody::Expression(ref expr) => {
                                 vec![Statement::Expression {
@@ -628,6 +729,9 @@ impl Interpreter {
                             // execute the body if case matches
                             if self
                                 .enum_equality(expr.eval(Rc::clone(&self.env)), condition.clone())
+                                && guard.is_none_or(|guard| {
+                                    guard.eval(Rc::clone(&self.env)).is_truthy()
+                                })
                             {
                                 self.interpret(body, 1);
                                 exec = true;
@@ -642,18 +746,229 @@ impl Interpreter {
                         }
                     }
                 }
+                LiteralType::String(text) => {
+                    for (case, guard, body) in cases {
+                        let Some(rest) = self.string_case(&case, &text) else {
+                            continue;
+                        };
+                        let body = match body {
+                            FuncBody::Expression(ref expr) => {
+                                vec![Statement::Expression {
+                                    expr: *expr.clone(),
+                                }]
+                            }
+                            FuncBody::Statements(ref stmts) => stmts.clone(),
+                        };
+                        // the rest of the string is bound in the case's own scope
+                        let prev_env = Rc::clone(&self.env);
+                        let case_env = self.env.borrow_mut().enclose();
+                        self.env = Rc::new(RefCell::new(case_env));
+                        if let Some((_, name, _)) = case
+                            .string_pattern()
+                            .filter(|(_, name, _)| name.token != TokenType::Underscore)
+                        {
+                            self.env.borrow_mut().define_var(
+                                name.lexeme.clone(),
+                                LiteralType::String(rest),
+                                VarKind {
+                                    is_pub: false,
+                                    is_mut: false,
+                                    is_func: false,
+                                    value_type: Token::empty(TokenType::StrIdent, "string", None),
+                                },
+                            );
+                        }
+                        // guards see the bound name
+                        if let Some(guard) = guard {
+                            if !guard.eval(Rc::clone(&self.env)).is_truthy() {
+                                self.env = prev_env;
+                                continue;
+                            }
+                        }
+                        self.interpret(body.iter().collect(), 1);
+                        self.env = prev_env;
+                        exec = true;
+                        break;
+                    }
+                }
+                LiteralType::Tuple(items) => {
+                    for (case, guard, body) in cases {
+                        let mut bound = vec![];
+                        if !self.tuple_case(&case, &items, &mut bound) {
+                            continue;
+                        }
+                        let body = match body {
+                            FuncBody::Expression(ref expr) => {
+                                vec![Statement::Expression {
+                                    expr: *expr.clone(),
+                                }]
+                            }
+                            FuncBody::Statements(ref stmts) => stmts.clone(),
+                        };
+                        // the items are bound in the case's own scope
+                        let prev_env = Rc::clone(&self.env);
+                        let case_env = self.env.borrow_mut().enclose();
+                        self.env = Rc::new(RefCell::new(case_env));
+                        for (name, item) in bound {
+                            self.env.borrow_mut().define_var(
+                                name.lexeme,
+                                item,
+                                VarKind {
+                                    is_pub: false,
+                                    is_mut: false,
+                                    is_func: false,
+                                    value_type: Token::empty(TokenType::AnyIdent, "any", None),
+                                },
+                            );
+                        }
+                        // guards see the bound names
+                        if let Some(guard) = guard {
+                            if !guard.eval(Rc::clone(&self.env)).is_truthy() {
+                                self.env = prev_env;
+                                continue;
+                            }
+                        }
+                        self.interpret(body.iter().collect(), 1);
+                        self.env = prev_env;
+                        exec = true;
+                        break;
+                    }
+                }
+                LiteralType::Number(_) | LiteralType::Char(_) => {
+                    for (case, guard, body) in cases {
+                        if !self.value_case(&case, &condition)
+                            || !guard
+                                .is_none_or(|guard| guard.eval(Rc::clone(&self.env)).is_truthy())
+                        {
+                            continue;
+                        }
+                        let body = match body {
+                            FuncBody::Expression(ref expr) => {
+                                vec![Statement::Expression {
+                                    expr: *expr.clone(),
+                                }]
+                            }
+                            FuncBody::Statements(ref stmts) => stmts.clone(),
+                        };
+                        self.interpret(body.iter().collect(), 1);
+                        exec = true;
+                        break;
+                    }
+                }
                 _ => raw(format!("pattern matching for '{:?}' isn't allowed", condition).as_str()),
             }
 
             if !exec {
                 match def_case.clone() {
-                    FuncBody::Statements(s) => {
+                    Some(FuncBody::Statements(s)) => {
                         self.interpret(s.iter().map(|x| x).collect(), 1);
                     }
-                    FuncBody::Expression(e) => {
+                    Some(FuncBody::Expression(e)) => {
                         self.interpret(vec![&Statement::Expression { expr: *e }], 1);
                     }
+                    None => {}
+                }
+            }
+        }
+    }
+
+    /// matches a string against a case, returns the rest of the string for a pattern
+    fn string_case(&mut self, case: &Expression, text: &str) -> Option<String> {
+        let Some((pattern, _, is_prefix)) = case.string_pattern() else {
+            return match case.eval(Rc::clone(&self.env)) {
+                LiteralType::String(case) => (case == text).then(String::new),
+                case => {
+                    raw(format!(
+                        "expected string in the match case, but received {}",
+                        case.type_name()
+                    )
+                    .as_str());
+                    None
+                }
+            };
+        };
+        let LiteralType::String(pattern) = pattern.eval(Rc::clone(&self.env)) else {
+            return None;
+        };
+        match is_prefix {
+            true => text.strip_prefix(pattern.as_str()),
+            false => text.strip_suffix(pattern.as_str()),
+        }
+        .map(|rest| rest.to_string())
+    }
+
+    /// matches a tuple against a case, a tuple pattern item by item, collecting
+    /// the items its names bind, other cases by equality
+    fn tuple_case(
+        &mut self,
+        case: &Expression,
+        items: &[LiteralType],
+        bound: &mut Vec<(Token, LiteralType)>,
+    ) -> bool {
+        let Expression::Tuple {
+            items: patterns, ..
+        } = case
+        else {
+            return case.eval(Rc::clone(&self.env)) == LiteralType::Tuple(items.to_vec());
+        };
+        if patterns.len() != items.len() {
+            return false;
+        }
+        for (pattern, item) in patterns.iter().zip(items) {
+            let is_match = match (pattern, item) {
+                (Expression::Var { name, .. }, item) => {
+                    if name.token != TokenType::Underscore {
+                        bound.push((name.clone(), item.clone()));
+                    }
+                    true
                 }
+                (Expression::Tuple { .. }, LiteralType::Tuple(inner)) => {
+                    self.tuple_case(pattern, inner, bound)
+                }
+                (Expression::Tuple { .. }, _) => false,
+                (pattern, item) => pattern.eval(Rc::clone(&self.env)) == *item,
+            };
+            if !is_match {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// matches a number or a char against a case, a range case by its bounds,
+    /// `'a'..'z'` or `0..<10`, other cases by equality
+    fn value_case(&mut self, case: &Expression, value: &LiteralType) -> bool {
+        let Expression::Range {
+            start,
+            end,
+            inclusive,
+            ..
+        } = case
+        else {
+            return case.eval(Rc::clone(&self.env)) == *value;
+        };
+        let start = start.eval(Rc::clone(&self.env));
+        let end = end.eval(Rc::clone(&self.env));
+        match (value, start, end) {
+            (LiteralType::Number(n), LiteralType::Number(start), LiteralType::Number(end)) => {
+                start <= *n && (*n < end || *inclusive && *n == end)
+            }
+            (LiteralType::Char(c), LiteralType::Char(start), LiteralType::Char(end)) => {
+                if start > end {
+                    self.error
+                        .throw(E0x437, 0, (0, 0), vec![start.to_string(), end.to_string()]);
+                    exit(1);
+                }
+                start <= *c && (*c < end || *inclusive && *c == end)
+            }
+            (value, start, _) => {
+                raw(format!(
+                    "expected {} range in the match case, but received {} range",
+                    value.type_name(),
+                    start.type_name()
+                )
+                .as_str());
+                false
             }
         }
     }
@@ -733,6 +1048,8 @@ impl Interpreter {
             extends,
             is_strict: _,
             fields,
+            getters,
+            ..
         } = stmt
         {
             let mut fields: Vec<(Token, TypeKind)> = fields
@@ -773,6 +1090,35 @@ impl Interpreter {
             self.env
                 .borrow_mut()
                 .define_type(name.clone().lexeme, value.clone());
+
+            // getters are methods of the record, `self` takes its type
+            for getter in getters {
+                let Func {
+                    name: getter_name, ..
+                } = getter
+                else {
+                    continue;
+                };
+                let mut call = self.create_func(getter);
+                call.params[0].1 = value.clone();
+                let is_field = fields
+                    .iter()
+                    .any(|(field, _)| field.lexeme == getter_name.lexeme);
+                if is_field
+                    || !self.env.borrow().define_method(
+                        name.lexeme.clone(),
+                        getter_name.lexeme.clone(),
+                        LiteralType::Func(call),
+                    )
+                {
+                    self.error.throw(
+                        E0x305,
+                        getter_name.line,
+                        getter_name.pos,
+                        vec![getter_name.lexeme.clone()],
+                    );
+                }
+            }
         }
     }
 
@@ -801,6 +1147,78 @@ impl Interpreter {
         }
     }
 
+    /// calls the `@entry` function, with the command line arguments when it takes them
+    pub fn entry(&mut self, stmts: &[Statement]) {
+        let Some(name) = stmts.iter().find_map(|stmt| match stmt {
+            Func {
+                name,
+                is_entry: true,
+                ..
+            } => Some(name),
+            _ => None,
+        }) else {
+            return;
+        };
+        let value = self.env.borrow().get_int(&name.lexeme, None);
+        if let Some(LiteralType::Func(func)) = value.map(|v| v.value) {
+            let args = match func.params.is_empty() {
+                true => vec![],
+                false => vec![LiteralType::Vec(
+                    self.project
+                        .args
+                        .iter()
+                        .map(|arg| LiteralType::String(arg.clone()))
+                        .collect(),
+                )],
+            };
+            call_func(func, &args, &self.env);
+        }
+    }
+
+    /// runs the `@test` functions in order with `--test`, each after the `@before`
+    /// functions and before the `@after` ones, printing the functions as they run
+    pub fn tests(&mut self, stmts: &[Statement]) {
+        if !self.project.test {
+            return;
+        }
+        let funcs = |kind: TestKind| -> Vec<&Token> {
+            stmts
+                .iter()
+                .filter_map(|stmt| match stmt {
+                    Func {
+                        name,
+                        test: Some(test),
+                        ..
+                    } if *test == kind => Some(name),
+                    _ => None,
+                })
+                .collect()
+        };
+        let (befores, afters) = (funcs(TestKind::Before), funcs(TestKind::After));
+        // each test starts from the globals the program left
+        let globals = self.env.borrow().snapshot();
+        for test in funcs(TestKind::Test) {
+            println!("{}", format!("test '{}'", test.lexeme).yellow());
+            for before in &befores {
+                println!("  {}", format!("before: '{}'", before.lexeme).blue());
+                self.call_test(before);
+            }
+            self.call_test(test);
+            for after in &afters {
+                println!("  {}", format!("after: '{}'", after.lexeme).blue());
+                self.call_test(after);
+            }
+            self.env.borrow().restore(globals.clone());
+        }
+    }
+
+    fn call_test(&mut self, name: &Token) {
+        let value = self.env.borrow().get_int(&name.lexeme, None);
+        if let Some(LiteralType::Func(func)) = value.map(|v| v.value) {
+            call_func(func, &[], &self.env);
+        }
+    }
+
     /// creates FuncImpl from function statement
     fn create_func(&self, stmt: &Statement) -> FuncImpl {
         if let Func {
@@ -810,6 +1228,8 @@ impl Interpreter {
             params,
             is_async,
             is_pub,
+            is_memo,
+            ..
         } = stmt
         {
             let params: Vec<(Token, Token)> = params
@@ -831,6 +1251,8 @@ impl Interpreter {
                 is_async: *is_async,
                 is_pub: *is_pub,
                 env: Rc::clone(&self.env),
+                bound: vec![],
+                memo: is_memo.then(|| Rc::new(RefCell::new(HashMap::new()))),
             }
         } else if let Var { value, is_func, .. } = stmt {
             if !is_func.clone() {
@@ -865,6 +1287,8 @@ impl Interpreter {
                     is_async,
                     is_pub,
                     env: Rc::clone(&self.env),
+                    bound: vec![],
+                    memo: None,
                 };
             }
             self.error.throw(E0x404, 0, (0, 0), vec![]);
@@ -929,71 +1353,211 @@ impl Interpreter {
 
                     if let LiteralType::Void = v.value {
                         if let ValueKind::Type(t) = v.kind.clone() {
-                            self.env
-                                .borrow_mut()
-                                .type_values
-                                .borrow_mut()
-                                .insert(name.clone(), t);
+                            self.env.borrow_mut().define_type(name.clone(), t);
                         }
                     } else {
-                        self.env
-                            .borrow_mut()
-                            .values
-                            .borrow_mut()
-                            .insert(name.clone(), v.clone());
+                        self.env.borrow_mut().define(name.clone(), v.clone());
                     }
                 }
             } else {
                 for (name, alias) in names {
                     if let Some((_, v)) = vals.iter().find(|(n, _)| n == &name.lexeme) {
                         let new_name = alias.as_ref().map_or(&name.lexeme, |t| &t.lexeme);
-                        self.env
-                            .borrow_mut()
-                            .values
-                            .borrow_mut()
-                            .insert(new_name.clone(), v.clone());
+                        self.env.borrow_mut().define(new_name.clone(), v.clone());
                     }
                 }
             }
         }
     }
+
+    /// binds the whole module under the alias
+    fn use_mod(&mut self, src: &String, alias: &Token) {
+        if !self.project.side_effects {
+            self.error.throw(E0x415, 0, (0, 0), vec![]);
+        }
+
+        let mod_env = Rc::new(RefCell::new(Env::new(HashMap::new())));
+        if src.contains("::") {
+            let mut int = Interpreter::new_with_env(Rc::clone(&mod_env), false, "", None, 1);
+            int.project = self.project.clone();
+            int.load_std(src.trim_matches('"').to_string(), vec![]);
+        } else {
+            let vals = match self.env.borrow_mut().mod_vals.borrow_mut().remove(src) {
+                Some(c) => c,
+                None => {
+                    self.error
+                        .throw(E0x416, alias.line, alias.pos, vec![src.clone()]);
+                    exit(1);
+                }
+            };
+            for (name, v) in vals {
+                if let (LiteralType::Void, ValueKind::Type(t)) = (&v.value, &v.kind) {
+                    mod_env.borrow_mut().define_type(name, t.clone());
+                } else {
+                    mod_env.borrow_mut().define(name, v);
+                }
+            }
+        }
+
+        self.env.borrow_mut().define_var(
+            alias.lexeme.clone(),
+            LiteralType::Mod(ModImpl {
+                name: src.trim_matches('"').to_string(),
+                env: mod_env,
+            }),
+            VarKind {
+                is_mut: false,
+                is_pub: false,
+                is_func: false,
+                value_type: alias.clone(),
+            },
+        );
+    }
+}
+
+thread_local! {
+    /// the environment the running std function was called from
+    static CALLER: RefCell<Option<Rc<RefCell<Env>>>> = const { RefCell::new(None) };
+}
+
+/// runs a std function call from the environment, `eval` runs quotes in it
+pub fn with_caller<T>(env: &Rc<RefCell<Env>>, call: impl FnOnce() -> T) -> T {
+    let encl = CALLER.with(|caller| caller.replace(Some(Rc::clone(env))));
+    let value = call();
+    CALLER.with(|caller| *caller.borrow_mut() = encl);
+    value
+}
+
+/// the environment the running std function was called from
+pub fn caller() -> Option<Rc<RefCell<Env>>> {
+    CALLER.with(|caller| caller.borrow().clone())
+}
+
+/// evaluates quoted code in the environment, quoted statements run in their own block
+pub fn run_quote(quote: QuoteImpl, env: Rc<RefCell<Env>>) -> LiteralType {
+    match quote.body {
+        FuncBody::Expression(expr) => expr.eval(env),
+        FuncBody::Statements(stmts) => {
+            let env = Rc::new(RefCell::new(env.borrow().enclose()));
+            let mut int = Interpreter::new_with_env(env, false, "", None, 1);
+            int.interpret(stmts.iter().collect(), 1);
+            LiteralType::Void
+        }
+    }
 }
 
 pub fn run_func(func: FuncImpl, args: &[Expression], env: Rc<RefCell<Env>>) -> LiteralType {
     let error = Error::new("", Project::new());
-    if args.len() != func.params.len() {
+    // arguments of a partial application already fill their parameters
+    let mut slots = if func.bound.is_empty() {
+        vec![None; func.params.len()]
+    } else {
+        func.bound.clone()
+    };
+    let free: Vec<usize> = (0..slots.len()).filter(|&i| slots[i].is_none()).collect();
+    if args.len() > free.len() || (args.is_empty() && !free.is_empty()) {
         error.throw(E0x405, 0, (0, 0), vec![]);
     }
 
-    let mut arg_values = vec![];
+    // arguments are evaluated in the call's order, labeled ones are placed by name
     for (i, arg) in args.iter().enumerate() {
+        let (i, arg) = match arg {
+            Expression::Labeled { label, value, .. } => {
+                match func
+                    .params
+                    .iter()
+                    .position(|(p, _)| p.lexeme == label.lexeme)
+                {
+                    Some(i) => (i, value.as_ref()),
+                    None => {
+                        error.throw(
+                            E0x422,
+                            label.line,
+                            label.pos,
+                            vec![func.name.clone(), label.lexeme.clone()],
+                        );
+                        exit(1);
+                    }
+                }
+            }
+            arg => (free[i], arg),
+        };
+        if slots[i].is_some() {
+            error.throw(
+                E0x423,
+                0,
+                (0, 0),
+                vec![func.params[i].0.lexeme.clone(), func.name.clone()],
+            );
+        }
         let arg_lit = arg.eval(Rc::clone(&env));
-        if !type_check(&func.params.iter().nth(i).unwrap().1, &arg_lit, &env) {
+        if !type_check(&func.params[i].1, &arg_lit, &env) {
+            if let Some((generic, bound)) = bound_of(&func.params[i].1) {
+                error.throw(
+                    E0x429,
+                    0,
+                    (0, 0),
+                    vec![
+                        arg_lit.type_name(),
+                        generic.lexeme.clone(),
+                        bound.lexeme.clone(),
+                    ],
+                );
+            }
             error.throw(
                 E0x301,
                 0,
                 (0, 0),
-                vec![
-                    func.params.iter().nth(i).unwrap().1.lexeme.clone(),
-                    arg_lit.to_string(),
-                ],
+                vec![func.params[i].1.lexeme.clone(), arg_lit.to_string()],
             );
         }
-        arg_values.push(arg_lit);
+        slots[i] = Some(arg_lit);
     }
-    let func_env = func.env.borrow_mut().enclose();
-    let func_env = Rc::new(RefCell::new(func_env));
-
-    for (i, val) in arg_values.iter().enumerate() {
-        if i < func.params.len() {
-            if !type_check(&func.value_type, &val, &env) {
+    // a type parameter takes the type of its first argument, the others must have it too
+    let mut generics: HashMap<&str, String> = HashMap::new();
+    for ((_, value_type), slot) in func.params.iter().zip(&slots) {
+        if let (Some(generic), Some(value)) = (generic_of(value_type), slot) {
+            let first = generics
+                .entry(&generic.lexeme)
+                .or_insert_with(|| value.type_name());
+            if *first != value.type_name() {
                 error.throw(
-                    E0x301,
+                    E0x442,
                     0,
                     (0, 0),
-                    vec![val.to_string(), arg_values[i].to_string()],
+                    vec![generic.lexeme.clone(), first.clone(), value.type_name()],
                 );
             }
+        }
+    }
+    // fewer arguments than parameters give a function taking the rest, `add(5)`
+    if slots.iter().any(Option::is_none) {
+        return LiteralType::Func(FuncImpl {
+            bound: slots,
+            ..func
+        });
+    }
+    let arg_values: Vec<LiteralType> = slots.into_iter().flatten().collect();
+    // `@memo` functions run once for equal arguments
+    let Some(memo) = func.memo.clone() else {
+        return call_func(func, &arg_values, &env);
+    };
+    if let Some(result) = memo.borrow().get(&arg_values) {
+        return result.clone();
+    }
+    let result = call_func(func, &arg_values, &env);
+    memo.borrow_mut().insert(arg_values, result.clone());
+    result
+}
+
+/// runs the body of a function with all of its arguments
+fn call_func(func: FuncImpl, arg_values: &[LiteralType], env: &Rc<RefCell<Env>>) -> LiteralType {
+    let error = Error::new("", Project::new());
+    let func_env = func.env.borrow_mut().enclose();
+    let func_env = Rc::new(RefCell::new(func_env));
+
+    for (i, val) in arg_values.iter().enumerate() {
+        if i < func.params.len() {
             let params = func
                 .params
                 .iter()
@@ -1016,19 +1580,17 @@ pub fn run_func(func: FuncImpl, args: &[Expression], env: Rc<RefCell<Env>>) -> L
     let mut int = Interpreter::new_with_env(Rc::clone(&func_env), false, "", None, 1);
     match func.body {
         FuncBody::Statements(body) => {
+            int.hoist(&body.iter().collect::<Vec<_>>());
             for stmt in body.clone() {
                 int.interpret(vec![&stmt], 1);
-                let mut val = {
+                let val = {
                     let specs = int.specs.borrow_mut();
                     specs.get("return").cloned()
                 };
-                if let Statement::Expression { expr } = body.first().unwrap() {
-                    val = Some(expr.eval(Rc::clone(&env)));
-                }
 
                 if val.is_some() {
                     let v = val.clone().unwrap().clone();
-                    if !type_check(&func.value_type, &v, &env) {
+                    if !type_check(&func.value_type, &v, env) {
                         // error.throw(
                         //     E0x301,
                         //     0,
@@ -1042,7 +1604,7 @@ pub fn run_func(func: FuncImpl, args: &[Expression], env: Rc<RefCell<Env>>) -> L
         }
         FuncBody::Expression(expr) => {
             let val = expr.eval(Rc::clone(&func_env));
-            if !type_check(&func.value_type, &val, &env) {
+            if !type_check(&func.value_type, &val, env) {
                 error.throw(
                     E0x301,
                     0,
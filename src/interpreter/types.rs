@@ -45,6 +45,73 @@ pub enum TypeKind {
         // "string" 5.21 false
         kind: LiteralKind,
     },
+    Generic {
+        // T: comparable, a type parameter of a function
+        name: Token,
+        bound: Option<Token>,
+    },
+}
+
+/// bounds of type parameters, `func max<T: comparable>(a: T, b: T) -> T`
+pub const BOUNDS: [&str; 3] = ["comparable", "numeric", "display"];
+
+/// the type parameter and its bound, if the type is a bounded type parameter
+pub fn bound_of(value_type: &Token) -> Option<(&Token, &Token)> {
+    match &value_type.value {
+        Some(LiteralKind::Type(t)) => match &**t {
+            TypeKind::Generic {
+                name,
+                bound: Some(bound),
+            } => Some((name, bound)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// the type parameter, if the type is one, bounded or not
+pub fn generic_of(value_type: &Token) -> Option<&Token> {
+    match &value_type.value {
+        Some(LiteralKind::Type(t)) => match &**t {
+            TypeKind::Generic { name, .. } => Some(name),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// if the types of a signature and a function match, `any` and type parameters match every type
+fn same_type(expected: &TypeKind, actual: &Token) -> bool {
+    let kind = |typ: &Token| typ.clone().token_to_typekind();
+    let expected = match expected {
+        TypeKind::Var { name } => kind(name),
+        typ => typ.clone(),
+    };
+    let actual = kind(actual);
+    let is_any = |typ: &TypeKind| match typ {
+        TypeKind::Var { name } => name.token == TokenType::AnyIdent,
+        TypeKind::Generic { .. } => true,
+        _ => false,
+    };
+    is_any(&expected) || is_any(&actual) || expected.to_string() == actual.to_string()
+}
+
+/// if the value supports what the bound requires, `comparable` values can be
+/// ordered with `<` and `>`, `numeric` ones used in arithmetic and `display`
+/// ones printed
+pub fn satisfies(bound: &str, val: &LiteralType) -> bool {
+    match bound {
+        "comparable" => matches!(
+            val,
+            LiteralType::Number(_) | LiteralType::String(_) | LiteralType::Char(_)
+        ),
+        "numeric" => matches!(val, LiteralType::Number(_)),
+        "display" => !matches!(
+            val,
+            LiteralType::Func(_) | LiteralType::DeclrFunc(_) | LiteralType::Void
+        ),
+        _ => false,
+    }
 }
 
 impl fmt::Display for TypeKind {
@@ -78,6 +145,7 @@ impl fmt::Display for TypeKind {
             TypeKind::Maybe { lhs } => write!(f, "{}?", lhs),
             TypeKind::Important { lhs } => write!(f, "{}!", lhs),
             TypeKind::Literal { kind } => write!(f, "{:?}", kind),
+            TypeKind::Generic { name, .. } => write!(f, "{}", name.lexeme),
             TypeKind::Callback { params, ret } => {
                 write!(f, "|")?;
                 for (i, p) in params.iter().enumerate() {
@@ -94,9 +162,39 @@ impl fmt::Display for TypeKind {
 
 pub fn type_check(value_type: &Token, val: &LiteralType, env: &Rc<RefCell<Env>>) -> bool {
     match value_type.token {
-        TokenType::FuncIdent => true,
+        // |T, T| T, a function taking as many arguments of the same types and returning the same type
+        TokenType::FuncIdent => match (&value_type.value, val) {
+            (Some(LiteralKind::Type(t)), LiteralType::Func(func)) => match t.as_ref() {
+                TypeKind::Callback { params, ret } => {
+                    // the parameters a partial application leaves
+                    let left: Vec<&Token> = func
+                        .params
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| func.bound.get(*i).is_none_or(|arg| arg.is_none()))
+                        .map(|(_, (_, typ))| typ)
+                        .collect();
+                    left.len() == params.len()
+                        && left
+                            .iter()
+                            .zip(params)
+                            .all(|(typ, param)| same_type(param, typ))
+                        && same_type(ret, &func.value_type)
+                }
+                _ => true,
+            },
+            (_, LiteralType::Func(_) | LiteralType::DeclrFunc(_)) => true,
+            _ => false,
+        },
         TokenType::Enum => {
             let d = env.borrow().get_enum(&value_type.lexeme);
+            // uppercase names can be records or type aliases as well
+            if d.is_empty() {
+                let t = env.borrow().get_type(&value_type.lexeme);
+                if t.token != TokenType::Null {
+                    return type_check(&t, val, env);
+                }
+            }
 
             if let LiteralType::Enum {
                 parent,
@@ -162,6 +260,9 @@ pub fn type_check(value_type: &Token, val: &LiteralType, env: &Rc<RefCell<Env>>)
                             return lhs_n;
                         }
                     }
+                    TypeKind::Generic { bound, .. } => {
+                        bound.is_none_or(|bound| satisfies(&bound.lexeme, val))
+                    }
                     TypeKind::Record { fields } => {
                         if let LiteralType::Record(ref rec) = *val {
                             let rec_map: HashMap<_, _> = rec.iter().cloned().collect();
@@ -205,20 +306,11 @@ pub fn type_check(value_type: &Token, val: &LiteralType, env: &Rc<RefCell<Env>>)
         TokenType::VecLit => {
             if let LiteralType::Vec(ref array) = *val {
                 if let Some(LiteralKind::Type(ref t)) = value_type.value {
-                    if let TypeKind::Vec { .. } = **t {
-                        return array.iter().all(|item| {
-                            type_check(
-                                &Token {
-                                    token: string_to_tokentype(&value_type.lexeme),
-                                    lexeme: value_type.lexeme.clone(),
-                                    value: None,
-                                    line: value_type.line,
-                                    pos: value_type.pos,
-                                },
-                                &item,
-                                env,
-                            )
-                        });
+                    // items are checked against the item type itself, which may be a vector
+                    if let TypeKind::Vec { ref kind } = **t {
+                        if let TypeKind::Var { ref name } = **kind {
+                            return array.iter().all(|item| type_check(name, item, env));
+                        }
                     }
                 }
                 false
@@ -276,12 +368,14 @@ pub fn type_check(value_type: &Token, val: &LiteralType, env: &Rc<RefCell<Env>>)
         | TokenType::StrLit
         | TokenType::TrueLit
         | TokenType::FalseLit
-        | TokenType::CharLit => {
+        | TokenType::CharLit
+        | TokenType::BytesLit => {
             match *val {
                 LiteralType::Number(ref n) => return check_num(n, value_type),
                 LiteralType::String(ref s) => return check_str(s, value_type),
                 LiteralType::Boolean(ref b) => return check_bool(b, value_type),
                 LiteralType::Char(ref c) => return check_char(c, value_type),
+                LiteralType::Bytes(ref b) => return check_bytes(b, value_type),
                 LiteralType::Null => return check_null(value_type),
                 _ => {}
             }
@@ -291,6 +385,26 @@ pub fn type_check(value_type: &Token, val: &LiteralType, env: &Rc<RefCell<Env>>)
     }
 }
 
+/// gets element types of a tuple type, `Tuple<(T, T)>`
+pub fn tuple_types(value_type: &Token) -> Option<Vec<Token>> {
+    if value_type.token != TokenType::TupleLit {
+        return None;
+    }
+    if let Some(LiteralKind::Type(t)) = &value_type.value {
+        if let TypeKind::Tuple { types } = t.as_ref() {
+            let types = types
+                .iter()
+                .map(|t| match t {
+                    TypeKind::Var { name } => name.clone(),
+                    _ => Token::empty(TokenType::AnyIdent, "any", None),
+                })
+                .collect();
+            return Some(types);
+        }
+    }
+    None
+}
+
 fn check_num(n: &f32, value_type: &Token) -> bool {
     matches!(value_type.token, TokenType::NumLit)
         && matches!(literalkind_to_literaltype(value_type.value.clone().unwrap_or(LiteralKind::Null)), LiteralType::Number(ref m) if m == n)
@@ -311,6 +425,11 @@ fn check_char(c: &char, value_type: &Token) -> bool {
         && matches!(literalkind_to_literaltype(value_type.value.clone().unwrap_or(LiteralKind::Null)), LiteralType::Char(ref n) if n == c)
 }
 
+fn check_bytes(b: &[u8], value_type: &Token) -> bool {
+    matches!(value_type.token, TokenType::BytesLit)
+        && matches!(literalkind_to_literaltype(value_type.value.clone().unwrap_or(LiteralKind::Null)), LiteralType::Bytes(ref n) if n == b)
+}
+
 fn check_null(value_type: &Token) -> bool {
     matches!(value_type.token, TokenType::Null)
         && matches!(
@@ -324,6 +443,7 @@ pub fn literalkind_to_literaltype(kind: LiteralKind) -> LiteralType {
         LiteralKind::Bool { value } => LiteralType::Boolean(value),
         LiteralKind::Null => LiteralType::Null,
         LiteralKind::Char { value } => LiteralType::Char(value),
+        LiteralKind::Bytes { value } => LiteralType::Bytes(value),
         LiteralKind::Number { value, .. } => LiteralType::Number(value),
         LiteralKind::String { value } => LiteralType::String(value),
         LiteralKind::Type(t) => typekind_to_literaltype(*t),
@@ -341,6 +461,7 @@ pub fn typekind_to_literaltype(kind: TypeKind) -> LiteralType {
         TypeKind::Either { lhs, .. } => typekind_to_literaltype(*lhs),
         TypeKind::Maybe { lhs } => typekind_to_literaltype(*lhs),
         TypeKind::Important { lhs } => typekind_to_literaltype(*lhs),
+        TypeKind::Generic { .. } => LiteralType::Null,
     }
 }
 
@@ -368,6 +489,33 @@ fn tuple_to_lt(types: Vec<TypeKind>) -> LiteralType {
     LiteralType::Tuple(tuple)
 }
 
+/// orders record fields as they're declared in the record type,
+/// undeclared fields are kept at the end
+pub fn record_in_order(
+    value_type: &Token,
+    val: LiteralType,
+    env: &Rc<RefCell<Env>>,
+) -> LiteralType {
+    let typ = match value_type.token {
+        TokenType::Ident | TokenType::Enum => env.borrow().get_type(&value_type.lexeme),
+        _ => value_type.clone(),
+    };
+    match (typ.value, val) {
+        (Some(LiteralKind::Type(t)), LiteralType::Record(mut rec)) => {
+            if let TypeKind::Record { fields } = *t {
+                rec.sort_by_key(|(name, _)| {
+                    fields
+                        .iter()
+                        .position(|(field, _)| field.lexeme == *name)
+                        .unwrap_or(fields.len())
+                });
+            }
+            LiteralType::Record(rec)
+        }
+        (_, val) => val,
+    }
+}
+
 pub fn string_to_tokentype(s: &str) -> TokenType {
     match s {
         "number" => TokenType::NumIdent,
@@ -379,3 +527,52 @@ pub fn string_to_tokentype(s: &str) -> TokenType {
         _ => TokenType::AnyIdent,
     }
 }
+
+/// item type of a vector type, `T` of `Vec<T>`
+pub fn item_type(typ: &Token) -> Option<&Token> {
+    let Some(LiteralKind::Type(typ)) = &typ.value else {
+        return None;
+    };
+    match typ.as_ref() {
+        TypeKind::Vec { kind } => match kind.as_ref() {
+            TypeKind::Var { name } => Some(name),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+pub fn type_name(typ: &Token) -> String {
+    match item_type(typ) {
+        Some(item) => format!("Vec<{}>", type_name(item)),
+        None => typ.lexeme.clone(),
+    }
+}
+
+/// the first item of a literal not matching a primitive or vector type, with the
+/// index path to it, `[1][0]`, the expected type and the found one
+pub fn literal_mismatch(typ: &Token, value: &LiteralType) -> Option<(String, String, String)> {
+    match (item_type(typ), value) {
+        (Some(item), LiteralType::Vec(items)) => items.iter().enumerate().find_map(|(i, value)| {
+            literal_mismatch(item, value).map(|(path, e, f)| (format!("[{i}]{path}"), e, f))
+        }),
+        (Some(_), value) if value.is_primitive() => {
+            Some((String::new(), type_name(typ), value.type_name()))
+        }
+        (None, value)
+            if is_primitive(typ)
+                && (value.is_primitive() || matches!(value, LiteralType::Vec(_)))
+                && value.type_name() != typ.lexeme =>
+        {
+            Some((String::new(), typ.lexeme.clone(), value.type_name()))
+        }
+        _ => None,
+    }
+}
+
+pub fn is_primitive(typ: &Token) -> bool {
+    matches!(
+        typ.token,
+        TokenType::NumIdent | TokenType::StrIdent | TokenType::CharIdent | TokenType::BoolIdent
+    )
+}
@@ -1,9 +1,12 @@
 mod ast;
 mod cli;
+mod hoister;
+mod inliner;
 mod interpreter;
 mod parser;
 mod resolver;
 mod std;
+mod transpiler;
 use cli::cli_new;
 use manifest::Project;
 mod bundler;
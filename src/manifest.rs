@@ -4,6 +4,7 @@ use std::process::exit;
 use toml::{from_str, Value};
 
 use super::errors::raw;
+use super::parser::MAX_DEPTH;
 
 #[derive(Debug, Clone)]
 pub struct Project {
@@ -14,6 +15,16 @@ pub struct Project {
     pub load_std: bool,
     pub log: bool,
     pub test: bool,
+    /// tokens the parser skips to after a syntax error, `[";", "}", "let"]`
+    pub sync: Vec<String>,
+    /// how deeply expressions and blocks may nest, `256`
+    pub max_depth: usize,
+    /// flags enabled for `@cfg(...)`, `["linux", "debug"]`
+    pub cfg: Vec<String>,
+    /// report warnings as errors
+    pub deny_warnings: bool,
+    /// command line arguments after the file, passed to the `@entry` function
+    pub args: Vec<String>,
 }
 
 impl Project {
@@ -26,6 +37,11 @@ impl Project {
             load_std: true,
             log: false,
             test: false,
+            sync: vec![],
+            max_depth: MAX_DEPTH,
+            cfg: vec![],
+            deny_warnings: false,
+            args: vec![],
         }
     }
 
@@ -57,6 +73,18 @@ impl Project {
                         if table.get("load_std").is_some() {
                             self.load_std = self.get_bool(table, "load_std");
                         }
+                        if table.get("sync").is_some() {
+                            self.sync = self.get_strs(table, "sync");
+                        }
+                        if table.get("max_depth").is_some() {
+                            self.max_depth = self.get_usize(table, "max_depth");
+                        }
+                        if table.get("cfg").is_some() {
+                            self.cfg = self.get_strs(table, "cfg");
+                        }
+                        if table.get("deny_warnings").is_some() {
+                            self.deny_warnings = self.get_bool(table, "deny_warnings");
+                        }
                     }
                 }
                 None => {
@@ -75,4 +103,16 @@ impl Project {
     fn get_int(&self, table: &Value, name: &str) -> i8 {
         table.get(name).unwrap().as_integer().unwrap() as i8
     }
+
+    fn get_usize(&self, table: &Value, name: &str) -> usize {
+        table.get(name).unwrap().as_integer().unwrap() as usize
+    }
+
+    fn get_strs(&self, table: &Value, name: &str) -> Vec<String> {
+        let values = table.get(name).unwrap().as_array().unwrap();
+        values
+            .iter()
+            .map(|v| v.as_str().unwrap().to_string())
+            .collect()
+    }
 }
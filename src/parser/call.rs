@@ -1,55 +1,55 @@
-use super::Parser;
+use super::{ParseResult, Parser};
 use crate::ast::CallType;
-use crate::ast::LiteralKind;
 use crate::ast::LiteralType;
 use crate::ast::TokenType::*;
-use crate::errors::ErrorCode::E0x103;
+use crate::errors::ErrorCode::{E0x103, E0x109};
 use crate::interpreter::expr::Expression;
 
 impl Parser {
-    pub fn call(&mut self) -> Expression {
+    pub fn call(&mut self) -> ParseResult<Expression> {
         if self.is_token(LBracket) && self.prev(1).token == Ident {
             self.advance();
-            let arr = self.vector_call();
-            self.consume(RBracket);
-            return arr;
+            let name = Expression::Var {
+                id: self.id(),
+                name: self.prev(2).clone(),
+            };
+            let arr = self.vector_call(name)?;
+            self.consume(RBracket)?;
+            return Ok(arr);
         }
 
-        let mut expr = self.primary();
+        let mut expr = self.primary()?;
         while let Some(token) = {
             self.advance();
             Some(self.prev(1).token)
         } {
-            match token {
-                DblColon => expr = self.enum_call(),
-                Dot => expr = self.obj_call(),
-                LParen => expr = self.func_call(),
-                LBracket => expr = self.vector_call(),
-                DblDot => expr = self.range(),
-                Ident => expr = self.call(),
+            expr = match token {
+                DblColon => self.enum_call(),
+                Dot => self.obj_call(expr),
+                LParen => self.func_call(),
+                LBracket => self.vector_call(expr),
                 _ => {
                     self.retreat();
                     break;
                 }
-            }
+            }?;
         }
-        expr
+        Ok(expr)
     }
 
-    pub fn enum_call(&mut self) -> Expression {
+    pub fn enum_call(&mut self) -> ParseResult<Expression> {
         let name = self.prev(2).clone();
-        let e = self.consume(Ident);
+        let e = self.consume(Ident)?;
         let mut args = vec![Expression::Var {
             id: self.id(),
             name: e,
         }];
+        // `Enum::A(value)` or `module::func(args)`
         if self.if_token_consume(LParen) {
-            let expr = self.expr();
-            self.consume(RParen);
-            args.push(expr);
+            args.extend(self.call_args()?);
         };
 
-        Expression::Call {
+        Ok(Expression::Call {
             id: self.id(),
             name: Box::new(Expression::Var {
                 id: self.id(),
@@ -57,80 +57,99 @@ impl Parser {
             }),
             args,
             call_type: CallType::Enum,
-        }
+        })
     }
 
-    pub fn vector_call(&mut self) -> Expression {
-        let name = self.prev(2).clone();
-        let e = self.expr();
+    /// `name[index]`, `name` is any expression, so indices chain, `m[1][0]`
+    pub fn vector_call(&mut self, name: Expression) -> ParseResult<Expression> {
+        let e = self.expr()?;
         let args = vec![e];
-        self.consume(RBracket);
-        Expression::Call {
+        self.consume(RBracket)?;
+        Ok(Expression::Call {
             id: self.id(),
-            name: Box::new(Expression::Var {
-                id: self.id(),
-                name,
-            }),
+            name: Box::new(name),
             args,
             call_type: CallType::Vector,
-        }
+        })
     }
 
-    pub fn range(&mut self) -> Expression {
-        let lhs = if let Some(LiteralKind::Number { value, .. }) = self.prev(2).clone().value {
-            value as usize
-        } else {
-            0
-        };
-        let rhs = if let Expression::Value { value, .. } = self.expr() {
-            if let LiteralType::Number(x) = value {
-                x as usize + 1
-            } else {
-                0
-            }
-        } else {
-            0
-        };
-        Expression::Range {
-            id: self.id(),
-            lhs,
-            rhs,
+    pub fn obj_call(&mut self, object: Expression) -> ParseResult<Expression> {
+        let e = self.consume(Ident)?;
+        // `value.method(args)`
+        if self.if_token_consume(LParen) {
+            let mut args = self.call_args()?;
+            self.trailing_closure(&mut args)?;
+            return Ok(Expression::Method {
+                id: self.id(),
+                object: Box::new(object),
+                name: e,
+                args,
+            });
         }
-    }
-
-    pub fn obj_call(&mut self) -> Expression {
-        let name = self.prev(2).clone();
-        let e = self.consume(Ident);
         let args = vec![Expression::Value {
             id: self.id(),
             value: LiteralType::String(e.lexeme),
         }];
-        Expression::Call {
+        Ok(Expression::Call {
             id: self.id(),
-            name: Box::new(Expression::Var {
-                id: self.id(),
-                name,
-            }),
+            name: Box::new(object),
             args,
             call_type: CallType::Struct,
-        }
+        })
     }
 
-    pub fn func_call(&mut self) -> Expression {
-        let name = self.prev(2).clone();
+    /// parses call arguments until `)`,
+    /// labeled arguments (`x: 1`) can only follow positional ones
+    fn call_args(&mut self) -> ParseResult<Vec<Expression>> {
         let mut args = vec![];
+        let mut is_labeled = false;
         while !self.is_token(RParen) {
-            let arg = self.expr();
+            let arg = if self.is_token(Ident)
+                && self.tokens.get(self.crnt + 1).map(|t| &t.token) == Some(&Colon)
+            {
+                is_labeled = true;
+                let label = self.advance();
+                self.advance();
+                Expression::Labeled {
+                    id: self.id(),
+                    label,
+                    value: Box::new(self.expr()?),
+                }
+            } else {
+                if is_labeled {
+                    return Err(self.error(E0x109, vec![]));
+                }
+                self.expr()?
+            };
             args.push(arg);
             if self.is_token(RParen) {
                 break;
             }
             if !self.if_token_consume(Comma) && !self.is_token(RParen) {
-                self.throw_error(E0x103, vec![self.peek().lexeme.clone()]);
+                return Err(self.error(E0x103, vec![self.peek().lexeme.clone()]));
+            }
+        }
+        self.consume(RParen)?;
+        Ok(args)
+    }
+
+    /// a closure right after the parentheses is the last argument,
+    /// `for_each(v) |x: number| void { print(x); };`, it's positional
+    fn trailing_closure(&mut self, args: &mut Vec<Expression>) -> ParseResult<()> {
+        if self.is_token(Pipe) {
+            if matches!(args.last(), Some(Expression::Labeled { .. })) {
+                return Err(self.error(E0x109, vec![]));
             }
+            args.push(self.func_expr()?);
         }
-        self.consume(RParen);
-        Expression::Call {
+        Ok(())
+    }
+
+    pub fn func_call(&mut self) -> ParseResult<Expression> {
+        let name = self.prev(2).clone();
+        let mut args = self.call_args()?;
+        self.trailing_closure(&mut args)?;
+        Ok(Expression::Call {
             id: self.id(),
             name: Box::new(Expression::Var {
                 id: self.id(),
@@ -138,6 +157,6 @@ impl Parser {
             }),
             args,
             call_type: CallType::Func,
-        }
+        })
     }
 }
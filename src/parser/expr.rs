@@ -1,21 +1,26 @@
 // parses expressions
-use super::Parser;
-use crate::ast::{FuncBody, Statement, Token, TokenType::*};
+use super::{ParseResult, Parser};
+use crate::ast::{
+    CallType, FuncBody, Statement, Token,
+    TokenType::{self, *},
+};
 use crate::errors::ErrorCode::{E0x103, E0x107};
 use crate::interpreter::expr::{AssignKind, Expression};
 
 impl Parser {
-    pub fn exprs(&mut self) -> Statement {
+    pub fn exprs(&mut self) -> ParseResult<Statement> {
         // retreat consumed keyword
         self.retreat();
-        let expr = self.expr();
-        self.consume(Semi);
-        Statement::Expression { expr }
+        let expr = self.expr()?;
+        self.consume(Semi)?;
+        Ok(Statement::Expression { expr })
     }
     // goes from most to least important expression
-    pub fn expr(&mut self) -> Expression {
-        let expr = self.binary();
+    pub fn expr(&mut self) -> ParseResult<Expression> {
+        self.nest()?;
+        let expr = self.ternary()?;
         self.advance();
+        self.depth -= 1;
         match self.prev(1).token {
             // assignments
             Assign => self.assign(&expr, AssignKind::Normal),
@@ -25,20 +30,20 @@ impl Parser {
             DivEq => self.assign(&expr, AssignKind::Div),
             _ => {
                 self.retreat();
-                expr
+                Ok(expr)
             }
         }
     }
 
-    pub fn primary(&mut self) -> Expression {
+    pub fn primary(&mut self) -> ParseResult<Expression> {
         let token = self.peek().clone();
         match token.token {
             Ident => {
                 self.advance();
-                Expression::Var {
+                Ok(Expression::Var {
                     id: self.id(),
                     name: token,
-                }
+                })
             }
             LBracket => {
                 self.advance();
@@ -61,64 +66,136 @@ impl Parser {
                 }
             }
             Pipe => self.func_expr(),
-            Await => self.await_expr(),
+            Quote => self.quote_expr(),
             If => self.if_expr(),
             _ => {
                 if self.is_literal() {
                     self.advance();
-                    Expression::Value {
+                    Ok(Expression::Value {
                         id: self.id(),
-                        value: self.to_value_type(token),
-                    }
+                        value: self.to_value_type(token)?,
+                    })
                 } else {
-                    self.throw_error(E0x103, vec![self.peek().lexeme.clone()]);
+                    Err(self.error(E0x103, vec![self.peek().lexeme.clone()]))
                 }
             }
         }
     }
 
-    fn if_expr(&mut self) -> Expression {
-        self.consume(If);
-        let cond = self.expr();
-        self.consume(Colon);
-        let body = self.expr();
+    /// `cond ? a : b`, an if expression, `a ? b : c ? d : e` nests to the right
+    fn ternary(&mut self) -> ParseResult<Expression> {
+        let cond = self.binary()?;
+        if !self.if_token_consume(Qstn) {
+            return Ok(cond);
+        }
+        let body = self.ternary()?;
+        self.consume(Colon)?;
+        let else_branch = self.ternary()?;
+        Ok(Expression::If {
+            id: self.id(),
+            cond: Box::new(cond),
+            body: Box::new(body),
+            else_branch: Some(Box::new(else_branch)),
+        })
+    }
+
+    fn if_expr(&mut self) -> ParseResult<Expression> {
+        self.consume(If)?;
+        let cond = self.expr()?;
+        self.consume(Colon)?;
+        // `?` separates the else branch here, `if c: a ? b`
+        let body = self.binary()?;
         let mut else_branch = None;
         if self.if_token_consume(Qstn) {
-            else_branch = Some(Box::new(self.expr()))
+            else_branch = Some(Box::new(self.expr()?))
         }
 
-        Expression::If {
+        Ok(Expression::If {
             id: self.id(),
             cond: Box::new(cond),
             body: Box::new(body),
             else_branch,
-        }
+        })
     }
 
-    fn unary(&mut self) -> Expression {
-        if self.are_tokens(&[Bang, DblBang, Qstn, Decr, Incr, Min]) {
+    fn unary(&mut self) -> ParseResult<Expression> {
+        // `await` is a prefix operator, `await f() + 1` awaits only `f()`
+        if self.if_token_consume(Await) {
+            self.nest()?;
+            let expr = self.unary()?;
+            self.depth -= 1;
+            Ok(Expression::Await {
+                id: self.id(),
+                expr: Box::new(expr),
+            })
+        // `move name`
+        } else if self.if_token_consume(Move) {
+            Ok(Expression::Move {
+                name: self.consume(Ident)?,
+                id: self.id(),
+            })
+        } else if self.are_tokens(&[Bang, DblBang, Qstn, Decr, Incr, Min]) {
             self.advance();
             let operator = self.prev(1).clone();
-            let rhs = self.unary();
+            // operators nest without going through `expr`, `- - - x`
+            self.nest()?;
+            let mut rhs = self.unary()?;
+            // `**` binds tighter than a minus, `-2 ** 2` is `-(2 ** 2)`
+            if operator.token == Min {
+                rhs = self.fold(rhs, precedence(&Sqr).unwrap_or_default())?;
+            }
+            self.depth -= 1;
             let id = self.id();
-            Expression::Unary {
+            Ok(Expression::Unary {
                 id,
                 left: Box::new(rhs),
                 operator,
-            }
+            })
         } else {
             self.call()
         }
     }
 
-    fn binary(&mut self) -> Expression {
-        let mut expr = self.unary();
-        while self.are_tokens(&[
-            Plus, Min, Mul, Div, Prcnt, DblAnd, Or, Eq, BangEq, Gr, GrOrEq, Ls, LsOrEq, Sqr, And,
-        ]) {
+    pub fn binary(&mut self) -> ParseResult<Expression> {
+        self.binary_from(1)
+    }
+
+    /// folds operators binding at least as tight as `min`,
+    /// `1 + 2 * 3` is `1 + (2 * 3)` and `a && b || c` is `(a && b) || c`
+    fn binary_from(&mut self, min: u8) -> ParseResult<Expression> {
+        let expr = self.unary()?;
+        self.fold(expr, min)
+    }
+
+    /// folds the operators binding at least as tight as `min` onto `expr`
+    fn fold(&mut self, mut expr: Expression, min: u8) -> ParseResult<Expression> {
+        while let Some(prec) = precedence(&self.peek().token) {
+            if prec < min {
+                break;
+            }
             self.advance();
             let operator = self.prev(1).clone();
-            let rhs = self.unary();
+            // `start..end` and `start..<end`, both ends are required
+            if matches!(operator.token, DblDot | DblDotLs) {
+                // open ranges would never end, `for i in 0.. {` isn't a record
+                if self.are_tokens(&[LBrace, RBrace, RParen, RBracket, Semi, Comma]) {
+                    return Err(self.error(E0x103, vec![self.peek().lexeme]));
+                }
+                let end = self.binary_from(prec + 1)?;
+                expr = Expression::Range {
+                    id: self.id(),
+                    start: Box::new(expr),
+                    end: Box::new(end),
+                    inclusive: operator.token == DblDot,
+                };
+                continue;
+            }
+            // `**` is right-associative, the rest are left-associative
+            let rhs = self.binary_from(if operator.token == Sqr {
+                prec
+            } else {
+                prec + 1
+            })?;
             expr = Expression::Binary {
                 id: self.id(),
                 left: Box::new(expr),
@@ -126,84 +203,112 @@ impl Parser {
                 right: Box::new(rhs),
             };
         }
-        expr
+        Ok(expr)
     }
 
-    fn assign(&mut self, expr: &Expression, kind: AssignKind) -> Expression {
-        let value = self.expr();
-        if let Expression::Var { name, .. } = expr {
-            Expression::Assign {
+    fn assign(&mut self, expr: &Expression, kind: AssignKind) -> ParseResult<Expression> {
+        let value = self.expr()?;
+        match target(expr) {
+            Some((name, path)) => Ok(Expression::Assign {
                 id: self.id(),
-                name: name.clone(),
+                name,
+                path,
                 value: Box::new(value),
                 kind,
-            }
-        } else {
-            self.throw_error(E0x107, vec![]);
+            }),
+            None => Err(self.error(E0x107, vec![expr.to_string()])),
         }
     }
 
-    fn obj_expr(&mut self) -> Expression {
+    fn obj_expr(&mut self) -> ParseResult<Expression> {
         let mut fields = vec![];
         while !self.if_token_consume(RBrace) {
-            let key = self.consume(Ident).clone();
-            self.consume(Colon);
-            let value = self.expr();
+            let key = self.consume(Ident)?.clone();
+            self.consume(Colon)?;
+            let value = self.expr()?;
             fields.push((key.lexeme, value));
             if !self.if_token_consume(Comma) && !self.is_token(RBrace) {
-                self.throw_error(E0x103, vec![self.peek().lexeme.clone()]);
+                return Err(self.error(E0x103, vec![self.peek().lexeme.clone()]));
             }
         }
-        Expression::Record {
+        Ok(Expression::Record {
             id: self.id(),
             fields,
-        }
+        })
     }
 
-    fn arr_expr(&mut self) -> Expression {
+    fn arr_expr(&mut self) -> ParseResult<Expression> {
         let mut items = vec![];
         while !self.if_token_consume(RBracket) {
-            let e = self.expr();
+            let e = self.expr()?;
+            // `[expr for x in iter if filter]`
+            if items.is_empty() && self.if_token_consume(For) {
+                return self.comprehension(e);
+            }
             items.push(e);
             if !self.if_token_consume(Comma) && !self.is_token(RBracket) {
-                self.throw_error(E0x103, vec![self.peek().lexeme.clone()]);
+                return Err(self.error(E0x103, vec![self.peek().lexeme.clone()]));
             }
         }
-        Expression::Vec {
+        Ok(Expression::Vec {
             id: self.id(),
             items,
-        }
+        })
+    }
+
+    fn comprehension(&mut self, expr: Expression) -> ParseResult<Expression> {
+        let var = self.consume(Ident)?;
+        self.consume(In)?;
+        let iter = self.expr()?;
+        let filter = if self.if_token_consume(If) {
+            Some(Box::new(self.expr()?))
+        } else {
+            None
+        };
+        self.consume(RBracket)?;
+        Ok(Expression::Comprehension {
+            id: self.id(),
+            expr: Box::new(expr),
+            var,
+            iter: Box::new(iter),
+            filter,
+        })
     }
 
-    fn tuple_expr(&mut self) -> Expression {
+    fn tuple_expr(&mut self) -> ParseResult<Expression> {
         let mut items = vec![];
         while !self.if_token_consume(RParen) {
-            let e = self.expr();
+            let e = self.expr()?;
             items.push(e);
             if !self.if_token_consume(Comma) && !self.is_token(RParen) {
-                self.throw_error(E0x103, vec![self.peek().lexeme.clone()]);
+                return Err(self.error(E0x103, vec![self.peek().lexeme.clone()]));
             }
         }
-        Expression::Tuple {
+        Ok(Expression::Tuple {
             id: self.id(),
             items,
-        }
+        })
     }
 
-    fn group_expr(&mut self) -> Expression {
+    fn group_expr(&mut self) -> ParseResult<Expression> {
         self.advance();
-        let expr = self.expr();
-        self.consume(RParen);
-        Expression::Grouping {
+        let expr = self.expr()?;
+        self.consume(RParen)?;
+        Ok(Expression::Grouping {
             id: self.id(),
             expression: Box::new(expr),
-        }
+        })
     }
 
-    fn func_expr(&mut self) -> Expression {
+    pub fn func_expr(&mut self) -> ParseResult<Expression> {
         self.advance();
         let mut is_inline = false;
-        let mut value_type = self.prev(3).clone();
+        // `let f: T = |x| ...` returns `T`, closures without types take and return `any`
+        let mut value_type = if self.prev(2).token == Assign && self.prev(4).token == Colon {
+            self.prev(3).clone()
+        } else {
+            Token::empty(AnyIdent, "any", None)
+        };
         let mut params = vec![];
         let is_async = false;
         let mut is_pub = false;
@@ -218,27 +323,26 @@ impl Parser {
         }
         let mut name = self.prev(10 + add).clone();
         if self.if_token_consume(Underscore) {
-            self.consume(Pipe);
+            self.consume(Pipe)?;
         } else {
             while !self.if_token_consume(Pipe) {
                 if self.is_token(Ident) {
-                    let param_name = self.consume(Ident);
+                    let param_name = self.consume(Ident)?;
                     if self.if_token_consume(Colon) {
-                        let param_type = self.consume_type();
+                        let param_type = self.consume_type()?;
                         params.push((param_name, param_type));
                         is_inline = true;
                     } else {
-                        let param_type = self.prev(8).clone();
-                        params.push((param_name, param_type))
+                        params.push((param_name, Token::empty(AnyIdent, "any", None)))
                     }
                 } else if self.if_token_consume(Comma) {
                 } else {
-                    self.throw_error(E0x103, vec![self.peek().lexeme.clone()]);
+                    return Err(self.error(E0x103, vec![self.peek().lexeme.clone()]));
                 }
             }
         }
         if is_inline {
-            value_type = self.consume_type();
+            value_type = self.consume_type()?;
             name = Token {
                 token: Ident,
                 lexeme: "func".to_string(),
@@ -248,8 +352,8 @@ impl Parser {
             };
         }
         if self.if_token_consume(Colon) {
-            let body = self.expr();
-            return Expression::Func {
+            let body = self.expr()?;
+            return Ok(Expression::Func {
                 id: self.id(),
                 name,
                 value_type,
@@ -257,11 +361,11 @@ impl Parser {
                 params,
                 is_async,
                 is_pub,
-            };
+            });
         }
-        self.consume(LBrace);
-        let body = self.block_stmts();
-        Expression::Func {
+        self.consume(LBrace)?;
+        let body = self.block_stmts()?;
+        Ok(Expression::Func {
             id: self.id(),
             name,
             value_type,
@@ -269,14 +373,69 @@ impl Parser {
             params,
             is_async,
             is_pub,
-        }
+        })
     }
 
-    fn await_expr(&mut self) -> Expression {
-        let expr = self.expr();
-        Expression::Await {
+    /// `quote { expr }` or `quote { stmt; stmt; }`
+    fn quote_expr(&mut self) -> ParseResult<Expression> {
+        self.advance();
+        self.consume(LBrace)?;
+        let body = if self.is_token(RBrace)
+            || self.are_tokens(&[
+                Let, Func, Return, If, For, While, Loop, Break, Match, Sh, Mod, Use, TypeStmt,
+                RecordStmt, Enum, Label,
+            ]) {
+            FuncBody::Statements(self.block_stmts()?)
+        } else {
+            let expr = self.expr()?;
+            // a single expression without `;` is quoted as an expression
+            if self.if_token_consume(RBrace) {
+                FuncBody::Expression(Box::new(expr))
+            } else {
+                self.consume(Semi)?;
+                let mut stmts = vec![Statement::Expression { expr }];
+                stmts.extend(self.block_stmts()?);
+                FuncBody::Statements(stmts)
+            }
+        };
+        Ok(Expression::Quote {
             id: self.id(),
-            expr: Box::new(expr),
+            body,
+        })
+    }
+}
+
+/// binding power of binary operators, higher binds tighter
+fn precedence(token: &TokenType) -> Option<u8> {
+    match token {
+        Or => Some(1),
+        DblAnd => Some(2),
+        Eq | BangEq => Some(3),
+        Gr | GrOrEq | Ls | LsOrEq | In => Some(4),
+        DblDot | DblDotLs => Some(5),
+        And => Some(6),
+        Plus | Min => Some(7),
+        Mul | Div | Prcnt => Some(8),
+        Sqr => Some(9),
+        _ => None,
+    }
+}
+
+/// the variable and the path of indices and fields an assignment target refers to,
+/// `None` when it can't be assigned to, like a literal or a call
+fn target(expr: &Expression) -> Option<(Token, Vec<Expression>)> {
+    match expr {
+        Expression::Var { name, .. } => Some((name.clone(), vec![])),
+        Expression::Call {
+            name,
+            args,
+            call_type: CallType::Vector | CallType::Struct,
+            ..
+        } => {
+            let (name, mut path) = target(name)?;
+            path.extend(args.first().cloned());
+            Some((name, path))
         }
+        _ => None,
     }
 }
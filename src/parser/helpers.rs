@@ -1,56 +1,62 @@
 // helper methods for parser
-use super::Parser;
+use super::{ParseResult, Parser, SyntaxError};
 use crate::ast::{
     LiteralKind, LiteralType, Token,
     TokenType::{self, *},
 };
-use crate::errors::ErrorCode::{self, E0x104, E0x106};
+use crate::errors::ErrorCode::{self, E0x104, E0x106, E0x115};
 use coloredpp::Colorize;
-use std::process::exit;
 
 impl Parser {
     /// extracts values from token
-    pub fn to_value_type(&mut self, token: Token) -> LiteralType {
-        match token.token {
+    pub fn to_value_type(&self, token: Token) -> ParseResult<LiteralType> {
+        Ok(match token.token {
             NumLit => {
                 if let Some(LiteralKind::Number { value, .. }) = token.value {
                     LiteralType::Number(value)
                 } else {
-                    self.throw_error(E0x104, vec![self.peek().lexeme])
+                    return Err(self.error(E0x104, vec![self.peek().lexeme]));
                 }
             }
             StrLit => {
                 if let Some(LiteralKind::String { value }) = token.value {
                     LiteralType::String(value)
                 } else {
-                    self.throw_error(E0x104, vec![self.peek().lexeme])
+                    return Err(self.error(E0x104, vec![self.peek().lexeme]));
                 }
             }
             CharLit => {
                 if let Some(LiteralKind::Char { value }) = token.value {
                     LiteralType::Char(value)
                 } else {
-                    self.throw_error(E0x104, vec![self.peek().lexeme])
+                    return Err(self.error(E0x104, vec![self.peek().lexeme]));
+                }
+            }
+            BytesLit => {
+                if let Some(LiteralKind::Bytes { value }) = token.value {
+                    LiteralType::Bytes(value)
+                } else {
+                    return Err(self.error(E0x104, vec![self.peek().lexeme]));
                 }
             }
             TrueLit => LiteralType::Boolean(true),
             FalseLit => LiteralType::Boolean(false),
             Null => LiteralType::Null,
             _ => LiteralType::Null,
-        }
+        })
     }
 
     #[inline]
     /// well, checks if token is literal
     pub fn is_literal(&self) -> bool {
-        self.are_tokens(&[NumLit, StrLit, CharLit, TrueLit, FalseLit, Null])
+        self.are_tokens(&[NumLit, StrLit, CharLit, BytesLit, TrueLit, FalseLit, Null])
     }
 
     #[inline]
     /// consumes token if its there
     pub fn if_token_consume(&mut self, token: TokenType) -> bool {
-        if self.is_token(token.clone()) {
-            self.consume(token);
+        if self.is_token(token) {
+            self.advance();
             true
         } else {
             false
@@ -89,21 +95,22 @@ impl Parser {
     }
 
     /// takes multiple tokens and consumes whichever matches first
-    pub fn consume_some(&mut self, ts: &[TokenType]) -> Token {
+    pub fn consume_some(&mut self, ts: &[TokenType]) -> ParseResult<Token> {
         for t in ts {
             if self.if_token_advance(t.clone()) {
-                return self.prev(1);
+                return Ok(self.prev(1));
             }
         }
-        self.throw_error(E0x106, vec![self.prev(1).lexeme])
+        let expected: Vec<String> = ts.iter().map(|t| t.to_string()).collect();
+        Err(self.error(E0x106, vec![expected.join("' or '")]))
     }
 
     /// self explanatory
-    pub fn consume(&mut self, t: TokenType) -> Token {
+    pub fn consume(&mut self, t: TokenType) -> ParseResult<Token> {
         if self.if_token_advance(t.clone()) {
-            self.prev(1)
+            Ok(self.prev(1))
         } else {
-            self.throw_error(E0x106, vec![t.to_string()])
+            Err(self.error(E0x106, vec![t.to_string()]))
         }
     }
 
@@ -172,6 +179,15 @@ impl Parser {
         self.id - 1
     }
 
+    /// goes one expression or block deeper, too deep nesting is an error
+    pub fn nest(&mut self) -> ParseResult<()> {
+        self.depth += 1;
+        if self.depth > self.max_depth {
+            return Err(self.error(E0x115, vec![self.max_depth.to_string()]));
+        }
+        Ok(())
+    }
+
     #[inline]
     pub fn start(&self, msg: &str) {
         if self.log {
@@ -186,9 +202,13 @@ impl Parser {
         }
     }
 
-    pub fn throw_error(&mut self, code: ErrorCode, args: Vec<String>) -> ! {
-        self.err
-            .throw(code, self.peek().line, self.peek().pos, args);
-        exit(1);
+    /// a syntax error at the current token
+    pub fn error(&self, code: ErrorCode, args: Vec<String>) -> SyntaxError {
+        SyntaxError {
+            code,
+            line: self.peek().line,
+            pos: self.peek().pos,
+            args,
+        }
     }
 }
@@ -1,10 +1,33 @@
 // Asburd Parser, transforms tokens into AST
 use crate::ast::{
-    Destruct, FuncBody, LiteralKind, LiteralType, RecordField, Statement, Token, TokenType::*,
+    Destruct, FuncBody, LiteralKind, LiteralType, RecordField, Statement, TestKind, Token,
+    TokenType::{self, *},
 };
-use crate::errors::{raw, Error, ErrorCode::*};
-use crate::interpreter::expr::Expression;
+use crate::errors::{
+    raw, Error,
+    ErrorCode::{self, *},
+};
+use crate::interpreter::{expr::Expression, types::TypeKind};
 use coloredpp::Colorize;
+
+/// tokens the parser skips to after a syntax error,
+/// `;` and `}` end a statement, the rest start one
+pub const SYNC_TOKENS: [TokenType; 14] = [
+    Semi, RBrace, Let, Func, If, For, While, Loop, Match, Return, Use, Mod, Enum, Impl,
+];
+/// a syntax error, `parse` reports it and goes on with the next statement
+#[derive(Debug, Clone)]
+pub struct SyntaxError {
+    pub code: ErrorCode,
+    pub line: usize,
+    pub pos: (usize, usize),
+    pub args: Vec<String>,
+}
+
+pub type ParseResult<T> = Result<T, SyntaxError>;
+
+/// how deeply expressions and blocks may nest, deeper ones would overflow the stack
+pub const MAX_DEPTH: usize = 256;
 mod call;
 pub mod expr;
 mod helpers;
@@ -18,6 +41,12 @@ pub struct Parser {
     crnt: usize,
     log: bool,
     id: usize,
+    sync: Vec<TokenType>,
+    /// type parameters of the functions being parsed
+    generics: Vec<(Token, Option<Token>)>,
+    /// expressions and blocks being parsed, one inside another
+    depth: usize,
+    max_depth: usize,
 }
 
 impl Parser {
@@ -28,18 +57,46 @@ impl Parser {
             log,
             crnt: 0,
             id: 0,
+            sync: SYNC_TOKENS.to_vec(),
+            generics: vec![],
+            depth: 0,
+            max_depth: MAX_DEPTH,
         }
     }
 
+    /// replaces the default `SYNC_TOKENS`
+    pub fn sync_on(&mut self, tokens: Vec<TokenType>) {
+        self.sync = tokens;
+    }
+
+    /// replaces the default `MAX_DEPTH`
+    pub fn max_depth(&mut self, depth: usize) {
+        self.max_depth = depth;
+    }
+
     /// parser entry function
-    pub fn parse(&mut self) -> Vec<Statement> {
+    /// parses the whole source, on errors the statements parsed around them are returned with them
+    pub fn parse(&mut self) -> Result<Vec<Statement>, (Vec<Statement>, Vec<SyntaxError>)> {
         let mut stmts = vec![];
+        let mut errors = vec![];
         if self.log {
             println!("  {}", "parsing statements...".yellow())
         }
-        // parse statements until the end of file (source)
+        // parse statements until the end of file (source),
+        // a statement with an error is skipped and parsing goes on
         while !self.check(Eof) {
-            stmts.push(self.stmt());
+            let start = self.crnt;
+            match self.stmt() {
+                Ok(stmt) => stmts.push(stmt),
+                Err(error) => {
+                    errors.push(error);
+                    self.depth = 0;
+                    self.synchronize(start);
+                }
+            }
+        }
+        if !errors.is_empty() {
+            return Err((stmts, errors));
         }
         if self.log {
             println!(
@@ -48,10 +105,62 @@ impl Parser {
             )
         }
         // returns collection of statements
-        stmts
+        Ok(stmts)
+    }
+
+    /// parses a single statement, tokens left after it are an error
+    // programs are parsed with `parse`, this is for tools parsing a statement on its own
+    #[allow(dead_code)]
+    pub fn parse_statement(&mut self) -> ParseResult<Statement> {
+        let stmt = self.stmt()?;
+        if !self.check(Eof) {
+            return Err(self.error(E0x103, vec![self.peek().lexeme]));
+        }
+        Ok(stmt)
+    }
+
+    /// parses a single expression, like the ones interpolated in strings,
+    /// tokens left after it are an error
+    pub fn parse_expression(&mut self) -> ParseResult<Expression> {
+        let expr = self.expr()?;
+        if !self.check(Eof) {
+            return Err(self.error(E0x103, vec![self.peek().lexeme]));
+        }
+        Ok(expr)
+    }
+
+    /// skips the rest of the statement that started at `start`,
+    /// the brackets opened in it are skipped past first, so a block goes as a whole,
+    /// then it stops after `;` or `}`, or before the next statement keyword
+    fn synchronize(&mut self, start: usize) {
+        let mut open = vec![];
+        for token in &self.tokens[start..self.crnt] {
+            close_bracket(&mut open, &token.token);
+        }
+        while !self.check(Eof) {
+            let token = self.peek().token;
+            let is_sync = open.is_empty() && self.sync.contains(&token);
+            if is_sync && !matches!(token, Semi | RBrace) {
+                return;
+            }
+            self.advance();
+            close_bracket(&mut open, &token);
+            if !open.is_empty() || !self.sync.contains(&token) {
+                continue;
+            }
+            match token {
+                Semi => return,
+                // an `if` with an error in its block goes on with `else`
+                RBrace if !self.are_tokens(&[Else, Elif]) => {
+                    self.if_token_advance(Semi);
+                    return;
+                }
+                _ => {}
+            }
+        }
     }
 
-    fn stmt(&mut self) -> Statement {
+    fn stmt(&mut self) -> ParseResult<Statement> {
         // advance to consume keywords
         self.advance();
         match self.prev(1).token {
@@ -67,23 +176,160 @@ impl Parser {
             Sh => self.shs(),
             Mod => self.mods(),
             Use => self.uses(),
-            LBrace => self.block_stmt(),
+            LBrace => {
+                let block = self.block_stmt()?;
+                self.consume(RBrace)?;
+                Ok(block)
+            }
             TypeStmt => self.types(),
             RecordStmt => self.record(),
             Enum => self.enums(),
             Label => self.label(),
+            Impl => self.impls(),
+            At => self.annotation(),
             _ => self.exprs(),
         }
     }
 
-    fn label(&mut self) -> Statement {
+    /// `@inline func name() -> T = expr;`, `@memo func name() -> T {}`,
+    /// `@entry func name() -> void {}`, `@pure func name() -> T {}`, `@io func name() -> T {}`,
+    /// `@test func name() -> void {}`, `@before` and `@after` for the functions run around tests,
+    /// `@cfg(flag) stmt` or `@deprecated("message") stmt`
+    fn annotation(&mut self) -> ParseResult<Statement> {
+        let annotation = self.consume(Ident)?;
+        match annotation.lexeme.as_str() {
+            "inline" => self.inline(),
+            "memo" => self.memo(),
+            "entry" => self.entry(),
+            "pure" => self.pure(),
+            "io" => self.io(),
+            "test" => self.test(TestKind::Test),
+            "before" => self.test(TestKind::Before),
+            "after" => self.test(TestKind::After),
+            "deprecated" => self.deprecated(),
+            "cfg" => {
+                self.consume(LParen)?;
+                let is_enabled = self.cfg()?;
+                self.consume(RParen)?;
+                // disabled statements are still parsed, then dropped
+                let stmt = self.stmt()?;
+                if is_enabled {
+                    Ok(stmt)
+                } else {
+                    Ok(Statement::Block { stmts: vec![] })
+                }
+            }
+            _ => Err(self.error(E0x103, vec![annotation.lexeme])),
+        }
+    }
+
+    /// evaluates a `@cfg` condition against the enabled flags,
+    /// `linux`, `not(debug)`, `and(linux, or(x86, arm))`
+    fn cfg(&mut self) -> ParseResult<bool> {
+        let flag = self.consume(Ident)?;
+        let op = flag.lexeme.as_str();
+        if !matches!(op, "not" | "and" | "or") || !self.if_token_consume(LParen) {
+            return Ok(self.err.project().cfg.contains(&flag.lexeme));
+        }
+        let mut values = vec![];
+        while !self.is_token(RParen) {
+            values.push(self.cfg()?);
+            if !self.if_token_consume(Comma) {
+                break;
+            }
+        }
+        self.consume(RParen)?;
+        match op {
+            "not" if values.len() == 1 => Ok(!values[0]),
+            "not" => Err(self.error(E0x108, vec!["1".to_string(), values.len().to_string()])),
+            "and" => Ok(values.iter().all(|value| *value)),
+            _ => Ok(values.iter().any(|value| *value)),
+        }
+    }
+
+    fn inline(&mut self) -> ParseResult<Statement> {
+        self.consume(Func)?;
+        let mut stmt = self.func()?;
+        if let Statement::Func { is_inline, .. } = &mut stmt {
+            *is_inline = true;
+        }
+        Ok(stmt)
+    }
+
+    fn memo(&mut self) -> ParseResult<Statement> {
+        self.consume(Func)?;
+        let mut stmt = self.func()?;
+        if let Statement::Func { is_memo, .. } = &mut stmt {
+            *is_memo = true;
+        }
+        Ok(stmt)
+    }
+
+    fn entry(&mut self) -> ParseResult<Statement> {
+        self.consume(Func)?;
+        let mut stmt = self.func()?;
+        if let Statement::Func { is_entry, .. } = &mut stmt {
+            *is_entry = true;
+        }
+        Ok(stmt)
+    }
+
+    fn pure(&mut self) -> ParseResult<Statement> {
+        self.consume(Func)?;
+        let mut stmt = self.func()?;
+        if let Statement::Func { is_pure, .. } = &mut stmt {
+            *is_pure = true;
+        }
+        Ok(stmt)
+    }
+
+    fn io(&mut self) -> ParseResult<Statement> {
+        self.consume(Func)?;
+        let mut stmt = self.func()?;
+        if let Statement::Func { is_io, .. } = &mut stmt {
+            *is_io = true;
+        }
+        Ok(stmt)
+    }
+
+    fn test(&mut self, kind: TestKind) -> ParseResult<Statement> {
+        self.consume(Func)?;
+        let mut stmt = self.func()?;
+        if let Statement::Func { test, .. } = &mut stmt {
+            *test = Some(kind);
+        }
+        Ok(stmt)
+    }
+
+    /// `@deprecated("use g") func f() -> T {}`, the message is optional
+    fn deprecated(&mut self) -> ParseResult<Statement> {
+        let mut message = String::new();
+        if self.if_token_consume(LParen) {
+            if let Some(LiteralKind::String { value }) = self.consume(StrLit)?.value {
+                message = value;
+            }
+            self.consume(RParen)?;
+        }
+        // other annotations may follow
+        if !self.are_tokens(&[Func, RecordStmt, At]) {
+            return Err(self.error(E0x103, vec![self.peek().lexeme]));
+        }
+        let mut stmt = self.stmt()?;
+        if let Statement::Func { deprecated, .. } | Statement::Record { deprecated, .. } = &mut stmt
+        {
+            *deprecated = Some(message);
+        }
+        Ok(stmt)
+    }
+
+    fn label(&mut self) -> ParseResult<Statement> {
         self.if_token_consume(Ident);
-        self.consume(Colon);
+        self.consume(Colon)?;
         self.stmt()
     }
 
     /// parses variable publicity and returns variable publicit
-    fn var_is_pub(&mut self, is_mut: bool) -> Vec<Token> {
+    fn var_is_pub(&mut self, is_mut: bool) -> ParseResult<Vec<Token>> {
         let mut pub_names = vec![];
         if !is_mut && self.if_token_consume(Pub) {
             // if `(` comes, public names will be parsed
@@ -101,21 +347,21 @@ impl Parser {
                             pos: self.peek().pos,
                         })
                     } else {
-                        let name = self.consume(Ident);
+                        let name = self.consume(Ident)?;
                         pub_names.push(name);
                     }
                     if !self.if_token_consume(Comma) || self.is_token(RParen) {
                         break;
                     }
                 }
-                self.consume(RParen);
+                self.consume(RParen)?;
             }
         }
-        pub_names
+        Ok(pub_names)
     }
 
     /// parses vector destruction
-    fn var_vec_dest(&mut self) -> Vec<Token> {
+    fn var_vec_dest(&mut self) -> ParseResult<Vec<Token>> {
         let mut names = vec![];
         while !self.if_token_consume(RBracket) {
             // allow empty values: [a, _, c]
@@ -128,7 +374,7 @@ impl Parser {
                     break;
                 }
             } else {
-                let name = self.consume(Ident);
+                let name = self.consume(Ident)?;
                 names.push(name);
             }
             if !self.is_token(Comma) || self.is_token(Colon) {
@@ -136,12 +382,12 @@ impl Parser {
             }
             self.advance();
         }
-        self.consume(RBracket);
-        names
+        self.consume(RBracket)?;
+        Ok(names)
     }
 
     /// parses record destruction
-    fn var_record_dest(&mut self) -> Vec<Token> {
+    fn var_record_dest(&mut self) -> ParseResult<Vec<Token>> {
         let mut names = vec![];
         while !self.if_token_consume(RBrace) {
             // {name, ..}
@@ -151,7 +397,7 @@ impl Parser {
                     break;
                 }
             } else {
-                let name = self.consume(Ident);
+                let name = self.consume(Ident)?;
                 names.push(name);
             }
             if !self.is_token(Comma) || self.is_token(Colon) {
@@ -159,19 +405,19 @@ impl Parser {
             }
             self.advance();
         }
-        self.consume(RBrace);
-        names
+        self.consume(RBrace)?;
+        Ok(names)
     }
 
     /// parses tuple destruction
-    fn var_tuple_dest(&mut self) -> Vec<Token> {
+    fn var_tuple_dest(&mut self) -> ParseResult<Vec<Token>> {
         let mut names = vec![];
         while !self.if_token_consume(LParen) {
             // allow empty values: (a, _)
             if self.if_token_consume(Underscore) {
                 names.push(Token::null());
             } else {
-                let name = self.consume(Ident);
+                let name = self.consume(Ident)?;
                 names.push(name);
             }
             if !self.is_token(Comma) || self.is_token(Colon) {
@@ -179,11 +425,11 @@ impl Parser {
             }
             self.advance();
         }
-        self.consume(RParen);
-        names
+        self.consume(RParen)?;
+        Ok(names)
     }
 
-    fn var(&mut self) -> Statement {
+    fn var(&mut self) -> ParseResult<Statement> {
         self.start("variable statement");
         let mut names = vec![];
         let is_mut = self.if_token_consume(Mut);
@@ -191,22 +437,23 @@ impl Parser {
         let mut destruct = None;
 
         // checks if variable is immutable and consumes `pub` keyword, if its there
-        let mut pub_names = self.var_is_pub(is_mut);
+        let has_pub = !is_mut && self.is_token(Pub);
+        let mut pub_names = self.var_is_pub(is_mut)?;
         let mut is_pub = !pub_names.is_empty();
         if self.if_token_consume(LBracket) {
-            names = self.var_vec_dest();
+            names = self.var_vec_dest()?;
             destruct = Some(Destruct::Vector)
         } else if self.if_token_consume(LBrace) {
-            names = self.var_record_dest();
+            names = self.var_record_dest()?;
             destruct = Some(Destruct::Record)
         } else if self.if_token_consume(LParen) {
-            names = self.var_tuple_dest();
+            names = self.var_tuple_dest()?;
             destruct = Some(Destruct::Tuple)
         } else {
             // normally parse through names.
             // if name ends with `;`, return null
             loop {
-                let name = self.consume(Ident);
+                let name = self.consume(Ident)?;
                 names.push(name);
 
                 if self.is_token(Semi) {
@@ -225,7 +472,7 @@ impl Parser {
         // publish by their local names
         if pub_names.is_empty() {
             pub_names = names.clone();
-            is_pub = false;
+            is_pub = has_pub;
         }
 
         let null_var = Statement::Var {
@@ -240,12 +487,13 @@ impl Parser {
             pub_names: pub_names.clone(),
             is_func: false,
             destruct: destruct.clone(),
+            constraint: None,
         };
 
         if is_null {
             self.advance();
             self.log("variable statement");
-            return null_var;
+            return Ok(null_var);
         }
 
         let mut value_type = Token {
@@ -257,46 +505,67 @@ impl Parser {
         };
         let mut is_inference = false;
         if self.if_token_consume(Colon) {
-            value_type = self.consume_type();
+            value_type = self.consume_type()?;
             if value_type.token == Null && self.peek().token != Assign {
                 self.log("variable statement");
-                self.consume(Semi);
-                return null_var;
+                self.consume(Semi)?;
+                return Ok(null_var);
             }
         } else {
             is_inference = true;
         }
 
+        // `let a: T where a > 0 = ...`, checked when bound
+        let constraint = if self.if_token_consume(Where) {
+            Some(self.binary()?)
+        } else {
+            None
+        };
+
+        // `let a: T, b: T = a, b;`
+        if !is_inference
+            && constraint.is_none()
+            && destruct.is_none()
+            && names.len() == 1
+            && self.is_token(Comma)
+        {
+            return self.var_typed(names, value_type, is_mut, is_pub, pub_names);
+        }
+
         // consume type after `:`
         if self.if_token_consume(Semi) {
             self.log("variable statement");
-            // differes from normal `null_var` with dynamic `value_type`
-            return Statement::Var {
+            // differes from normal `null_var` with dynamic `value_type`,
+            // the value is deferred and checked against `value_type` once assigned
+            return Ok(Statement::Var {
                 names: names.clone(),
                 value_type,
-                value: Some(Expression::Value {
-                    id: self.id(),
-                    value: LiteralType::Null,
-                }),
+                value: None,
                 is_mut,
                 is_pub,
                 pub_names: pub_names.clone(),
                 is_func: false,
                 destruct,
-            };
+                constraint,
+            });
         }
 
-        self.consume(Assign);
+        self.consume(Assign)?;
         // check if variable has a callback as a value
         let is_func = self.is_token(Pipe);
-        let value = self.expr();
+        let value = self.expr()?;
         if is_inference {
-            value_type = value.to_literal().to_token();
+            value_type = match value {
+                Expression::Vec { ref items, .. } if destruct.is_none() => {
+                    Self::inferred_vec(items)
+                }
+                _ => value.to_literal().to_token(),
+            };
         }
-        self.consume(Semi);
+        self.consume(Semi)?;
 
         self.log("variable statement");
-        Statement::Var {
+        Ok(Statement::Var {
             names,
             value_type,
             value: Some(value),
@@ -305,10 +574,70 @@ impl Parser {
             pub_names,
             is_func,
             destruct,
+            constraint,
+        })
+    }
+
+    /// parses names with their own types and values,
+    /// as a tuple destruction: `let a: T, b: T = a, b;`
+    fn var_typed(
+        &mut self,
+        mut names: Vec<Token>,
+        value_type: Token,
+        is_mut: bool,
+        is_pub: bool,
+        pub_names: Vec<Token>,
+    ) -> ParseResult<Statement> {
+        let mut types = vec![TypeKind::Var { name: value_type }];
+        while self.if_token_consume(Comma) {
+            names.push(self.consume(Ident)?);
+            self.consume(Colon)?;
+            types.push(TypeKind::Var {
+                name: self.consume_type()?,
+            });
         }
+
+        self.consume(Assign)?;
+        let mut items = vec![self.expr()?];
+        while self.if_token_consume(Comma) {
+            items.push(self.expr()?);
+        }
+        // every name needs its own value
+        if items.len() != names.len() {
+            return Err(self.error(
+                E0x108,
+                vec![names.len().to_string(), items.len().to_string()],
+            ));
+        }
+        self.consume(Semi)?;
+
+        let pub_names = if is_pub { pub_names } else { names.clone() };
+        let typ = TypeKind::Tuple { types };
+        let value_type = Token {
+            token: TupleLit,
+            lexeme: typ.to_string(),
+            value: Some(LiteralKind::Type(Box::new(typ))),
+            line: names[0].line,
+            pos: names[0].pos,
+        };
+        self.log("variable statement");
+        Ok(Statement::Var {
+            names,
+            destruct: Some(Destruct::Tuple),
+            value_type,
+            value: Some(Expression::Tuple {
+                id: self.id(),
+                items,
+            }),
+            is_mut,
+            is_pub,
+            pub_names,
+            is_func: false,
+            constraint: None,
+        })
     }
 
-    fn func(&mut self) -> Statement {
+    fn func(&mut self) -> ParseResult<Statement> {
         self.start("function statement");
         let mut params = vec![];
         let mut is_async = false;
@@ -327,56 +656,134 @@ impl Parser {
             }
         }
 
-        let name = self.consume(Ident);
+        let name = self.consume(Ident)?;
+
+        // handles type parameters, `...<T: bound, T>...`
+        let mut generics = vec![];
+        if self.if_token_consume(Ls) {
+            while !self.if_token_consume(Gr) {
+                let generic = self.consume(Ident)?;
+                let bound = match self.if_token_consume(Colon) {
+                    true => Some(self.consume(Ident)?),
+                    false => None,
+                };
+                generics.push((generic, bound));
+                if !self.if_token_consume(Comma) {
+                    self.consume(Gr)?;
+                    break;
+                }
+            }
+        }
+        // they're in scope in the parameters, the output type and the body
+        let encl_generics = self.generics.len();
+        self.generics.extend(generics.iter().cloned());
 
-        // handles parameters, `...(i: T, i: T)...`
-        self.consume(LParen);
+        // handles parameters, `...(i: T, /, i: T, *, i: T)...`
+        let mut positional_only = 0;
+        let mut keyword_only = None;
+        self.consume(LParen)?;
         while !self.if_token_consume(RParen) {
-            if self.is_token(Ident) {
-                let param_name = self.consume(Ident);
-                self.consume(Colon);
-                let param_type = self.consume_type();
+            // `/` ends the positional-only parameters, `*` starts the keyword-only ones,
+            // each comes once and `/` needs a parameter before it and can't follow `*`
+            if self.is_token(Div) {
+                if params.is_empty() || positional_only > 0 || keyword_only.is_some() {
+                    return Err(self.error(E0x103, vec![self.peek().lexeme]));
+                }
+                self.advance();
+                positional_only = params.len();
+            } else if self.is_token(Mul) {
+                if keyword_only.is_some() {
+                    return Err(self.error(E0x103, vec![self.peek().lexeme]));
+                }
+                self.advance();
+                keyword_only = Some(params.len());
+            } else if self.is_token(Ident) {
+                let param_name = self.consume(Ident)?;
+                // `self` in methods gets its type from the `impl`
+                if param_name.lexeme == "self" && !self.is_token(Colon) {
+                    params.push((param_name, Token::empty(AnyIdent, "any", None)));
+                    continue;
+                }
+                self.consume(Colon)?;
+                let param_type = self.consume_type()?;
                 params.push((param_name, param_type))
             } else if self.if_token_consume(Comma) {
             } else if !self.is_token(RParen) {
-                self.throw_error(E0x103, vec![self.peek().lexeme]);
+                return Err(self.error(E0x103, vec![self.peek().lexeme]));
             }
         }
 
         // consume function output type
-        self.consume(Arrow);
-        let value_type = self.consume_type();
+        self.consume(Arrow)?;
+        let value_type = self.consume_type()?;
 
         // parse as a short function
         if self.if_token_consume(Assign) {
-            let body = self.expr();
-            self.consume(Semi);
+            let body = self.expr()?;
+            self.consume(Semi)?;
+            self.generics.truncate(encl_generics);
             self.log("function statement");
-            return Statement::Func {
+            return Ok(Statement::Func {
                 name,
                 value_type,
                 body: FuncBody::Expression(Box::new(body)),
                 params,
+                positional_only,
+                keyword_only,
+                generics,
                 is_async,
                 is_pub,
-            };
+                is_inline: false,
+                is_memo: false,
+                is_entry: false,
+                is_pure: false,
+                is_io: false,
+                test: None,
+                deprecated: None,
+            });
         }
 
         // standard block parsing
-        self.consume(LBrace);
-        let body = self.block_stmts();
+        self.consume(LBrace)?;
+        let body = self.block_stmts()?;
+        self.generics.truncate(encl_generics);
         self.log("function statement");
-        Statement::Func {
+        Ok(Statement::Func {
             name,
             value_type,
             body: FuncBody::Statements(body),
             params,
+            positional_only,
+            keyword_only,
+            generics,
             is_async,
             is_pub,
+            is_inline: false,
+            is_memo: false,
+            is_entry: false,
+            is_pure: false,
+            is_io: false,
+            test: None,
+            deprecated: None,
+        })
+    }
+
+    /// `impl number { func name(self) -> T {} }`
+    fn impls(&mut self) -> ParseResult<Statement> {
+        self.start("impl statement");
+        // methods can only be added to the primitive types
+        let name = self.consume_some(&[NumIdent, StrIdent, CharIdent, BoolIdent])?;
+        self.consume(LBrace)?;
+        let mut methods = vec![];
+        while !self.if_token_consume(RBrace) {
+            self.consume(Func)?;
+            methods.push(self.func()?);
         }
+        self.log("impl statement");
+        Ok(Statement::Impl { name, methods })
     }
 
-    fn returns(&mut self) -> Statement {
+    fn returns(&mut self) -> ParseResult<Statement> {
         self.start("return statement");
         let expr = if self.is_token(Semi) {
             Expression::Value {
@@ -384,99 +791,126 @@ impl Parser {
                 value: LiteralType::Null,
             }
         } else {
-            self.expr()
+            self.expr()?
         };
-        self.consume(Semi);
+        self.consume(Semi)?;
         self.log("return statement");
-        Statement::Return { expr }
+        Ok(Statement::Return { expr })
     }
 
-    fn ifs(&mut self) -> Statement {
+    fn ifs(&mut self) -> ParseResult<Statement> {
         self.start("if statement");
-        let cond = self.expr();
-        let body = self.block_stmts();
+        let cond = self.expr()?;
+        let body = self.block_body()?;
         let mut else_if_branches = vec![];
-        // parse elifs
-        while self.if_token_consume(Elif) {
-            let elif_preds = self.expr();
-            let elif_stmt = self.block_stmts();
+        // parse elifs, `else if` is the same as `elif`
+        while self.if_token_consume(Elif) || self.else_if() {
+            let elif_preds = self.expr()?;
+            let elif_stmt = self.block_body()?;
             else_if_branches.push((elif_preds, elif_stmt))
         }
 
         // parse else, if avaiable
         let else_branch = if self.if_token_consume(Else) {
-            Some(self.block_stmts())
+            Some(self.block_body()?)
         } else {
             None
         };
         self.log("if statement");
-        Statement::If {
+        Ok(Statement::If {
             cond,
             body,
             else_if_branches,
             else_branch,
+        })
+    }
+
+    /// consumes `else if`, but not an `else` followed by its block
+    fn else_if(&mut self) -> bool {
+        if self.is_token(Else) && self.tokens.get(self.crnt + 1).map(|t| &t.token) == Some(&If) {
+            self.advance();
+            self.advance();
+            return true;
         }
+        false
     }
 
-    fn fors(&mut self) -> Statement {
+    fn fors(&mut self) -> ParseResult<Statement> {
         self.start("for statement");
-        let iterator = self.consume(Ident);
+        let iterator = self.consume(Ident)?;
         let index = if self.if_token_consume(Comma) {
-            Some(self.consume(Ident))
+            Some(self.consume(Ident)?)
         } else {
             None
         };
-        self.consume(In);
-        let expr = self.expr();
-        let body = self.block_stmts();
+        self.consume(In)?;
+        let expr = self.expr()?;
+        let body = self.block_body()?;
         self.log("for statement");
-        Statement::For {
+        Ok(Statement::For {
             iterator,
             index,
             expr,
             body,
-        }
+        })
     }
 
-    fn whiles(&mut self) -> Statement {
+    fn whiles(&mut self) -> ParseResult<Statement> {
         self.start("while statement");
         // everything is obvious, I guess.
-        let cond = self.expr();
-        let body = self.block_stmts();
+        let cond = self.expr()?;
+        let body = self.block_body()?;
         self.log("while statement");
-        Statement::While { cond, body }
+        Ok(Statement::While { cond, body })
     }
 
-    fn loops(&mut self) -> Statement {
+    fn loops(&mut self) -> ParseResult<Statement> {
         self.start("loop statement");
         // checks if iterator index is there
         let iter = if self.is_token(NumLit) {
-            let num = match self.consume(NumLit).value {
+            let num = match self.peek().value {
                 Some(LiteralKind::Number { value, .. }) => value,
-                _ => self.throw_error(E0x104, vec![self.peek().lexeme]),
+                _ => return Err(self.error(E0x104, vec![self.peek().lexeme])),
             };
-            if num < 0.0 {
-                Some(1)
-            } else {
-                Some(num as usize)
+            if num.fract() != 0.0 {
+                let error = self.error(E0x114, vec![self.peek().lexeme]);
+                self.advance();
+                return self.skip_loop(error);
             }
+            self.consume(NumLit)?;
+            Some(num as usize)
+        } else if self.is_token(Min)
+            && self.tokens.get(self.crnt + 1).map(|t| &t.token) == Some(&NumLit)
+        {
+            // negative counts are scanned as a minus and a number
+            let num = self.tokens[self.crnt + 1].lexeme.clone();
+            let error = self.error(E0x114, vec![format!("-{num}")]);
+            self.advance();
+            self.advance();
+            return self.skip_loop(error);
         } else {
             None
         };
-        let body = self.block_stmts();
+        let body = self.block_body()?;
         self.log("loop statement");
-        Statement::Loop { iter, body }
+        Ok(Statement::Loop { iter, body })
     }
 
-    fn breaks(&mut self) -> Statement {
+    /// parses the body of a loop with a bad count, so only the count is reported
+    fn skip_loop(&mut self, error: SyntaxError) -> ParseResult<Statement> {
+        self.block_body()?;
+        Err(error)
+    }
+
+    fn breaks(&mut self) -> ParseResult<Statement> {
         self.start("break statement");
-        self.consume(Semi);
+        self.consume(Semi)?;
         self.log("break statement");
-        Statement::Break {}
+        Ok(Statement::Break {})
     }
 
-    fn enums(&mut self) -> Statement {
-        let name = self.consume(Ident);
+    fn enums(&mut self) -> ParseResult<Statement> {
+        let name = self.consume(Ident)?;
         if !self.is_uppercase(name.clone()) {
             raw("enum name must start with uppercase alphabet");
         }
@@ -486,16 +920,16 @@ impl Parser {
         } else {
             false
         };
-        self.consume(LBrace);
+        self.consume(LBrace)?;
         let mut items = vec![];
         while !self.if_token_consume(RBrace) {
-            let name = self.consume(Ident);
+            let name = self.consume(Ident)?;
             if !self.is_uppercase(name.clone()) {
                 raw("enum name must start with uppercase alphabet");
             }
             if self.if_token_consume(LParen) {
-                let typ = self.consume_type();
-                self.consume(RParen);
+                let typ = self.consume_type()?;
+                self.consume(RParen)?;
                 items.push((name.clone(), Some(typ)))
             }
             items.push((name, None));
@@ -504,45 +938,54 @@ impl Parser {
             }
         }
 
-        Statement::Enum {
+        Ok(Statement::Enum {
             name,
             is_pub,
             items,
-        }
+        })
     }
 
-    fn matchs(&mut self) -> Statement {
+    fn matchs(&mut self) -> ParseResult<Statement> {
         self.start("match statement");
-        let cond = self.expr();
-        self.consume(LBrace);
+        let cond = self.expr()?;
+        self.consume(LBrace)?;
         let mut cases = vec![];
 
-        // match can only "match" literals and Enums
-        while self.is_literal() || self.is_uppercase_ident() {
-            let expr = self.expr();
-            self.consume(ArrowBig);
+        // match can only "match" literals, Enums, string and tuple patterns
+        while self.is_literal()
+            || self.is_uppercase_ident()
+            || self.is_string_pattern()
+            || self.is_token(LParen)
+        {
+            let expr = self.case()?;
+            // guard, `... if cond => ...`
+            let guard = match self.if_token_consume(If) {
+                true => Some(self.expr()?),
+                false => None,
+            };
+            self.consume(ArrowBig)?;
             // consume block
             if self.if_token_advance(LBrace) {
-                let body = self.block_stmts();
-                cases.push((expr, FuncBody::Statements(body)))
+                let body = self.block_stmts()?;
+                cases.push((expr, guard, FuncBody::Statements(body)))
             } else {
                 // consume expression
-                let body = self.expr();
-                self.consume(Comma);
-                cases.push((expr, FuncBody::Expression(Box::new(body))))
+                let body = self.expr()?;
+                self.consume(Comma)?;
+                cases.push((expr, guard, FuncBody::Expression(Box::new(body))))
             }
         }
 
-        let mut def_case = FuncBody::Statements(vec![]);
-        // default branch `_ => {}`
+        let mut def_case = None;
+        // default branch `_ => {}`, optional when every enum item is matched
         if self.if_token_consume(Underscore) {
-            self.consume(ArrowBig);
+            self.consume(ArrowBig)?;
             if self.if_token_consume(LBrace) {
-                let body = self.block_stmts();
-                def_case = FuncBody::Statements(body)
+                let body = self.block_stmts()?;
+                def_case = Some(FuncBody::Statements(body))
             } else {
-                let body = self.expr();
-                def_case = FuncBody::Expression(Box::new(body))
+                let body = self.expr()?;
+                def_case = Some(FuncBody::Expression(Box::new(body)))
             }
         }
         let stmt = Statement::Match {
@@ -550,49 +993,135 @@ impl Parser {
             cases,
             def_case,
         };
-        self.consume(RBrace);
+        self.consume(RBrace)?;
         self.log("match statement");
-        stmt
+        Ok(stmt)
+    }
+
+    /// checks if a string pattern case comes, `"GET " ++ path`
+    fn is_string_pattern(&self) -> bool {
+        self.tokens.get(self.crnt + 1).map(|t| &t.token) == Some(&Incr)
+    }
+
+    /// parses a match case, a string pattern binds the rest of the string to the name,
+    /// `"GET " ++ path` for a prefix and `name ++ ".abs"` for a suffix, `_` binds nothing
+    fn case(&mut self) -> ParseResult<Expression> {
+        if self.is_token(LParen) {
+            return self.tuple_pattern();
+        }
+        if !self.is_string_pattern() {
+            return self.expr();
+        }
+        let is_prefix = self.is_token(StrLit);
+        let left = self.pattern_part(is_prefix)?;
+        let operator = self.consume(Incr)?;
+        let right = self.pattern_part(!is_prefix)?;
+        Ok(Expression::Binary {
+            id: self.id(),
+            left: Box::new(left),
+            operator,
+            right: Box::new(right),
+        })
+    }
+
+    /// parses a tuple pattern, `(0, y)`, a name binds the item at its position,
+    /// `_` matches any item and other items are matched by equality
+    fn tuple_pattern(&mut self) -> ParseResult<Expression> {
+        self.consume(LParen)?;
+        let mut items = vec![self.tuple_pattern_item()?];
+        while self.if_token_consume(Comma) {
+            items.push(self.tuple_pattern_item()?);
+        }
+        self.consume(RParen)?;
+        Ok(Expression::Tuple {
+            id: self.id(),
+            items,
+        })
+    }
+
+    fn tuple_pattern_item(&mut self) -> ParseResult<Expression> {
+        let next = self.tokens.get(self.crnt + 1).map(|t| &t.token);
+        if self.is_token(LParen) {
+            self.tuple_pattern()
+        } else if (self.is_token(Ident) || self.is_token(Underscore))
+            && matches!(next, Some(Comma | RParen))
+        {
+            Ok(Expression::Var {
+                id: self.id(),
+                name: self.advance(),
+            })
+        } else {
+            self.expr()
+        }
+    }
+
+    fn pattern_part(&mut self, is_text: bool) -> ParseResult<Expression> {
+        Ok(if is_text {
+            let text = self.consume(StrLit)?;
+            Expression::Value {
+                id: self.id(),
+                value: self.to_value_type(text)?,
+            }
+        } else {
+            Expression::Var {
+                id: self.id(),
+                name: self.consume_some(&[Ident, Underscore])?,
+            }
+        })
     }
 
     // very simple syntax
-    fn shs(&mut self) -> Statement {
+    fn shs(&mut self) -> ParseResult<Statement> {
         self.start("sh statement");
-        let cmd = self.consume(StrLit).lexeme;
-        self.consume(Semi);
+        let cmd = self.consume(StrLit)?.lexeme;
+        self.consume(Semi)?;
         self.log("sh statement");
-        Statement::Sh { cmd }
+        Ok(Statement::Sh { cmd })
     }
 
-    fn mods(&mut self) -> Statement {
+    fn mods(&mut self) -> ParseResult<Statement> {
         self.start("mod statement");
-        let src = self.consume(StrLit).lexeme;
+        let src = self.consume(StrLit)?.lexeme;
         let name = if self.if_token_consume(As) {
-            Some(self.consume(Ident).lexeme)
+            Some(self.consume(Ident)?.lexeme)
         } else {
             None
         };
-        self.consume(Semi);
+        self.consume(Semi)?;
         self.log("mod statement");
-        Statement::Mod { src, name }
+        Ok(Statement::Mod { src, name })
     }
 
-    fn uses(&mut self) -> Statement {
+    fn uses(&mut self) -> ParseResult<Statement> {
         self.start("use statement");
         let mut names = vec![];
         let mut all = false;
+        // `use "" as m`, imports the module itself
+        if self.is_mod_src() {
+            let src = self.use_src()?;
+            self.consume(As)?;
+            let alias = Some(self.consume(Ident)?);
+            self.consume(Semi)?;
+            self.log("use statement");
+            return Ok(Statement::Use {
+                src,
+                names,
+                all,
+                alias,
+            });
+        }
         // `use * from ""`, imports everything
         if self.if_token_advance(Mul) {
             all = true;
-            self.consume(From);
+            self.consume(From)?;
         } else {
             // use i from ""
             // use i, i from ""
             // use i as i, ii from ""
             while !self.if_token_advance(From) {
-                let name = self.consume(Ident);
+                let name = self.consume(Ident)?;
                 if self.if_token_consume(As) {
-                    let as_name = self.consume(Ident);
+                    let as_name = self.consume(Ident)?;
                     names.push((name, Some(as_name)))
                 } else {
                     names.push((name, None))
@@ -600,49 +1129,74 @@ impl Parser {
                 self.if_token_consume(Comma);
             }
         }
-        let src = if self.is_token(Ident) {
-            let mut lex = self.consume(Ident).lexeme;
+        let src = self.use_src()?;
+        self.consume(Semi)?;
+        self.log("use statement");
+        Ok(Statement::Use {
+            src,
+            names,
+            all,
+            alias: None,
+        })
+    }
+
+    /// checks if a module source comes, instead of imported names
+    fn is_mod_src(&self) -> bool {
+        self.is_token(StrLit)
+            || (self.peek().lexeme == "std"
+                && self
+                    .tokens
+                    .get(self.crnt + 1)
+                    .is_some_and(|t| t.token == DblColon))
+    }
+
+    /// parses a module source, `"src"` or `std::module`
+    fn use_src(&mut self) -> ParseResult<String> {
+        Ok(if self.is_token(Ident) {
+            let mut lex = self.consume(Ident)?.lexeme;
             if lex == "std" {
                 while self.if_token_consume(DblColon) {
-                    let n = self.consume(Ident);
+                    // `std::literal::string`, type names are keywords
+                    let n = if self.are_tokens(&[StrIdent, NumIdent]) {
+                        self.advance()
+                    } else {
+                        self.consume(Ident)?
+                    };
                     lex.push_str("::");
                     lex.push_str(n.lexeme.as_str());
                 }
             }
             format!("\"{}\"", lex)
         } else {
-            self.consume(StrLit).lexeme
-        };
-        self.consume(Semi);
-        self.log("use statement");
-        Statement::Use { src, names, all }
+            self.consume(StrLit)?.lexeme
+        })
     }
 
-    fn types(&mut self) -> Statement {
+    fn types(&mut self) -> ParseResult<Statement> {
         self.start("type statement");
         let is_pub = self.if_token_consume(Pub);
-        let name = self.consume(Ident);
-        self.consume(Assign);
-        let value = self.consume_type();
-        self.consume(Semi);
+        let name = self.consume(Ident)?;
+        self.consume(Assign)?;
+        let value = self.consume_type()?;
+        self.consume(Semi)?;
         self.log("type statement");
-        Statement::Type {
+        Ok(Statement::Type {
             name,
             value,
             is_pub,
-        }
+        })
     }
 
-    fn record(&mut self) -> Statement {
-        let name = self.consume(Ident);
+    fn record(&mut self) -> ParseResult<Statement> {
+        let name = self.consume(Ident)?;
 
         let mut extends = vec![];
         if self.if_token_consume(Extends) {
-            extends.push(self.consume(Ident));
+            extends.push(self.consume(Ident)?);
 
             if self.if_token_consume(Comma) {
                 while !self.if_token_consume(Comma) {
-                    extends.push(self.consume(Ident));
+                    extends.push(self.consume(Ident)?);
                     if !self.if_token_consume(Comma) {
                         break;
                     }
@@ -651,10 +1205,20 @@ impl Parser {
         }
 
         let mut fields = vec![];
-        self.consume(LBrace);
+        let mut getters = vec![];
+        self.consume(LBrace)?;
 
         while !self.if_token_consume(RBrace) {
-            let name = self.consume(Ident);
+            // `get name -> T { ... }`, a field named `get` is followed by `:`
+            if self.peek().lexeme == "get"
+                && self.tokens.get(self.crnt + 1).map(|t| &t.token) == Some(&Ident)
+            {
+                self.advance();
+                getters.push(self.getter(&name)?);
+                self.if_token_consume(Comma);
+                continue;
+            }
+            let name = self.consume(Ident)?;
             let mut is_strict = false;
             let mut is_optional = false;
             if self.if_token_consume(Qstn) {
@@ -663,12 +1227,12 @@ impl Parser {
                 is_strict = true;
             }
 
-            self.consume(Colon);
-            let value = self.consume_type();
+            self.consume(Colon)?;
+            let value = self.consume_type()?;
             let mut default_value = None;
 
             if self.if_token_consume(Eq) {
-                default_value = Some(self.expr())
+                default_value = Some(self.expr()?)
             }
 
             fields.push(RecordField {
@@ -680,37 +1244,364 @@ impl Parser {
             });
 
             if !self.if_token_consume(Comma) {
-                self.consume(RBrace);
+                self.consume(RBrace)?;
                 break;
             }
         }
 
-        Statement::Record {
+        Ok(Statement::Record {
             name,
             extends,
             is_strict: false,
             fields,
-        }
+            getters,
+            deprecated: None,
+        })
+    }
+
+    /// `get area -> number { return self.w * self.h; }`, a method of `self` read as a field
+    fn getter(&mut self, record: &Token) -> ParseResult<Statement> {
+        self.start("getter");
+        let name = self.consume(Ident)?;
+        self.consume(Arrow)?;
+        let value_type = self.consume_type()?;
+        self.consume(LBrace)?;
+        let body = self.block_stmts()?;
+        let param = Token {
+            lexeme: "self".to_string(),
+            ..name.clone()
+        };
+        self.log("getter");
+        Ok(Statement::Func {
+            name,
+            value_type,
+            body: FuncBody::Statements(body),
+            params: vec![(param, record.clone())],
+            positional_only: 0,
+            keyword_only: None,
+            generics: vec![],
+            is_async: false,
+            is_pub: false,
+            is_inline: false,
+            is_memo: false,
+            is_entry: false,
+            is_pure: false,
+            is_io: false,
+            test: None,
+            deprecated: None,
+        })
     }
 
-    fn block_stmts(&mut self) -> Vec<Statement> {
-        match self.block_stmt() {
+    /// parses a braced body, which runs in its own block
+    fn block_body(&mut self) -> ParseResult<Vec<Statement>> {
+        self.consume(LBrace)?;
+        let block = self.block_stmt()?;
+        self.consume(RBrace)?;
+        Ok(vec![block])
+    }
+
+    fn block_stmts(&mut self) -> ParseResult<Vec<Statement>> {
+        match self.block_stmt()? {
             Statement::Block { stmts } => {
-                self.consume(RBrace);
-                stmts
+                self.consume(RBrace)?;
+                Ok(stmts)
             }
-            _ => self.throw_error(E0x105, vec!["a block statement".to_string()]),
+            _ => Err(self.error(E0x105, vec!["a block statement".to_string()])),
         }
     }
 
-    fn block_stmt(&mut self) -> Statement {
+    fn block_stmt(&mut self) -> ParseResult<Statement> {
         self.start("block statement");
+        self.nest()?;
         let mut stmts = vec![];
         while !self.is_token(RBrace) && !self.is_token(Eof) {
-            let stmt = self.stmt();
+            let stmt = self.stmt()?;
             stmts.push(stmt);
         }
+        self.depth -= 1;
         self.log("block statement");
-        Statement::Block { stmts }
+        Ok(Statement::Block { stmts })
+    }
+}
+
+/// keeps the brackets left open, a closing bracket also closes the ones opened inside it,
+/// and `;` closes the parentheses and brackets of its block, `{ f(1; }` leaves nothing open
+fn close_bracket(open: &mut Vec<TokenType>, token: &TokenType) {
+    let closes = |opening: &TokenType| match token {
+        RParen => *opening == LParen,
+        RBracket => *opening == LBracket,
+        RBrace => *opening == LBrace,
+        _ => false,
+    };
+    match token {
+        LParen | LBracket | LBrace => open.push(token.clone()),
+        Semi => {
+            let block = open.iter().rposition(|opening| *opening == LBrace);
+            open.truncate(block.map_or(0, |i| i + 1));
+        }
+        _ => {
+            if let Some(i) = open.iter().rposition(closes) {
+                open.truncate(i);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{manifest::Project, parser::scanner::Scanner};
+
+    fn parser(src: &str) -> Parser {
+        let err = Error::new(src, Project::new());
+        let tokens = Scanner::new(src, err.clone(), false).scan().clone();
+        Parser::new(tokens, err, false)
+    }
+
+    #[test]
+    fn statement_rejects_the_rest() {
+        let stmt = parser("let x: number = 1;").parse_statement().unwrap();
+        assert!(matches!(stmt, Statement::Var { ref names, .. } if names[0].lexeme == "x"));
+        let error = parser("let x: number = 1; print(x);")
+            .parse_statement()
+            .unwrap_err();
+        assert!(matches!(error.code, E0x103));
+        assert_eq!(error.args, ["print"]);
+    }
+
+    #[test]
+    fn expression_rejects_the_rest() {
+        let expr = parser("1 + 2").parse_expression().unwrap();
+        assert!(matches!(expr, Expression::Binary { .. }));
+        let error = parser("1 + 2 3").parse_expression().unwrap_err();
+        assert!(matches!(error.code, E0x103));
+        assert_eq!(error.args, ["3"]);
+    }
+
+    #[test]
+    fn per_name_types() {
+        let stmt = parser("let a: number, b: string = 1, \"x\";")
+            .parse_statement()
+            .unwrap();
+        let Statement::Var {
+            names,
+            value_type,
+            value: Some(Expression::Tuple { items, .. }),
+            ..
+        } = stmt
+        else {
+            panic!("expected a declaration of a tuple");
+        };
+        assert_eq!(names.len(), 2);
+        assert_eq!(items.len(), 2);
+        let types = crate::interpreter::types::tuple_types(&value_type).unwrap();
+        let types: Vec<_> = types.iter().map(|t| t.lexeme.as_str()).collect();
+        assert_eq!(types, ["number", "string"]);
+    }
+
+    #[test]
+    fn impl_on_a_primitive() {
+        let stmt = parser("impl number { func double(self) -> number = self * 2; }")
+            .parse_statement()
+            .unwrap();
+        let Statement::Impl { name, methods } = stmt else {
+            panic!("expected an impl");
+        };
+        assert_eq!(name.lexeme, "number");
+        assert!(matches!(
+            methods.as_slice(),
+            [Statement::Func { name, params, .. }] if name.lexeme == "double" && params.len() == 1
+        ));
+    }
+
+    #[test]
+    fn module_alias() {
+        let stmt = parser("use \"math\" as m;").parse_statement().unwrap();
+        assert!(matches!(
+            stmt,
+            Statement::Use { ref src, alias: Some(ref alias), ref names, .. }
+                if src == "\"math\"" && alias.lexeme == "m" && names.is_empty()
+        ));
+        // names are imported by the other form
+        let stmt = parser("use sqrt as root from \"math\";")
+            .parse_statement()
+            .unwrap();
+        assert!(matches!(stmt, Statement::Use { alias: None, .. }));
+    }
+
+    #[test]
+    fn where_clause() {
+        let stmt = parser("let age: number where age >= 0 = 5;")
+            .parse_statement()
+            .unwrap();
+        assert!(matches!(
+            stmt,
+            Statement::Var { constraint: Some(Expression::Binary { ref operator, .. }), .. }
+                if operator.token == GrOrEq
+        ));
+        let stmt = parser("let age: number = 5;").parse_statement().unwrap();
+        assert!(matches!(
+            stmt,
+            Statement::Var {
+                constraint: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn filtered_comprehension() {
+        let expr = parser("[x * 2 for x in arr if x > 0]")
+            .parse_expression()
+            .unwrap();
+        let Expression::Comprehension {
+            expr,
+            var,
+            iter,
+            filter,
+            ..
+        } = expr
+        else {
+            panic!("expected a comprehension");
+        };
+        assert_eq!(var.lexeme, "x");
+        assert!(matches!(*expr, Expression::Binary { .. }));
+        assert!(matches!(*iter, Expression::Var { ref name, .. } if name.lexeme == "arr"));
+        assert!(matches!(filter.as_deref(), Some(Expression::Binary { .. })));
+        let expr = parser("[x for x in 1..4]").parse_expression().unwrap();
+        assert!(matches!(
+            expr,
+            Expression::Comprehension { filter: None, .. }
+        ));
+    }
+
+    #[test]
+    fn membership() {
+        let expr = parser("x in [1, 2, 3]").parse_expression().unwrap();
+        assert!(matches!(
+            expr,
+            Expression::Binary { ref operator, ref right, .. }
+                if operator.token == In && matches!(**right, Expression::Vec { .. })
+        ));
+        // at comparison precedence, below arithmetic
+        let expr = parser("1 + 1 in v").parse_expression().unwrap();
+        assert!(matches!(
+            expr,
+            Expression::Binary { ref operator, ref left, .. }
+                if operator.token == In && matches!(**left, Expression::Binary { .. })
+        ));
+    }
+
+    #[test]
+    fn optional_default_case() {
+        let stmt = parser("match c { Color::Red => print(1), Color::Green => print(2), }")
+            .parse_statement()
+            .unwrap();
+        assert!(matches!(
+            stmt,
+            Statement::Match { ref cases, def_case: None, .. } if cases.len() == 2
+        ));
+        let stmt = parser("match c { Color::Red => print(1), _ => print(2) }")
+            .parse_statement()
+            .unwrap();
+        assert!(matches!(
+            stmt,
+            Statement::Match { ref cases, def_case: Some(_), .. } if cases.len() == 1
+        ));
+    }
+
+    #[test]
+    fn labeled_arguments() {
+        let expr = parser("area(2, h: 3)").parse_expression().unwrap();
+        assert!(matches!(
+            expr,
+            Expression::Call { ref args, .. }
+                if matches!(args[..], [
+                    Expression::Value { .. },
+                    Expression::Labeled { ref label, .. },
+                ] if label.lexeme == "h")
+        ));
+        let expr = parser("area(h: 3, w: 2)").parse_expression().unwrap();
+        assert!(matches!(
+            expr,
+            Expression::Call { ref args, .. }
+                if args.iter().all(|arg| matches!(arg, Expression::Labeled { .. }))
+        ));
+    }
+
+    #[test]
+    fn cfg_attribute() {
+        let mut project = Project::new();
+        project.cfg.push("linux".to_string());
+        let parse = |src: &str| {
+            let err = Error::new(src, project.clone());
+            let tokens = Scanner::new(src, err.clone(), false).scan().clone();
+            Parser::new(tokens, err, false).parse_statement().unwrap()
+        };
+        assert!(matches!(
+            parse("@cfg(linux) func f() -> void {}"),
+            Statement::Func { .. }
+        ));
+        assert!(matches!(
+            parse("@cfg(and(linux, not(debug))) let x = 1;"),
+            Statement::Var { .. }
+        ));
+        // disabled statements become empty blocks
+        assert!(matches!(
+            parse("@cfg(or(debug, not(linux))) func f() -> void {}"),
+            Statement::Block { ref stmts } if stmts.is_empty()
+        ));
+    }
+
+    #[test]
+    fn string_patterns() {
+        let stmt =
+            parser("match r { \"GET \" ++ path => print(path), name ++ \".abs\" => print(name), }")
+                .parse_statement()
+                .unwrap();
+        let Statement::Match { cases, .. } = stmt else {
+            panic!("expected a match");
+        };
+        let patterns: Vec<_> = cases
+            .iter()
+            .map(|(case, _, _)| {
+                case.string_pattern()
+                    .map(|(_, name, is_prefix)| (name.lexeme.as_str(), is_prefix))
+            })
+            .collect();
+        assert_eq!(patterns, [Some(("path", true)), Some(("name", false))]);
+    }
+
+    #[test]
+    fn await_binds_to_the_call() {
+        let expr = parser("await f() + 1").parse_expression().unwrap();
+        assert!(matches!(
+            expr,
+            Expression::Binary { ref left, ref operator, .. }
+                if operator.token == Plus
+                    && matches!(**left, Expression::Await { ref expr, .. }
+                        if matches!(**expr, Expression::Call { .. }))
+        ));
+        let expr = parser("await (f() + 1)").parse_expression().unwrap();
+        assert!(matches!(
+            expr,
+            Expression::Await { ref expr, .. } if matches!(**expr, Expression::Grouping { .. })
+        ));
+    }
+
+    #[test]
+    fn parse_returns_the_statements_around_errors() {
+        let (stmts, errors) = parser("let x = ;\nprint(1);\nlet y = 1 +;\nprint(2);\n")
+            .parse()
+            .unwrap_err();
+        assert_eq!(stmts.len(), 2);
+        assert!(stmts
+            .iter()
+            .all(|stmt| matches!(stmt, Statement::Expression { .. })));
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|error| matches!(error.code, E0x103)));
+        assert_eq!(errors[0].line, 1);
+        assert_eq!(errors[1].line, 3);
+        assert_eq!(parser("print(1);").parse().unwrap().len(), 1);
     }
 }
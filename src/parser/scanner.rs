@@ -3,7 +3,10 @@ use crate::ast::{
     Base, LiteralKind, Token,
     TokenType::{self, *},
 };
-use crate::errors::{Error, ErrorCode::*};
+use crate::errors::{
+    Error,
+    ErrorCode::{self, *},
+};
 use coloredpp::Colorize;
 use std::collections::HashMap;
 use unicode_xid::UnicodeXID;
@@ -12,6 +15,8 @@ use unicode_xid::UnicodeXID;
 pub struct Scanner<'a> {
     src: &'a str,
     tokens: Vec<Token>,
+    /// the token scanned by the last step, until `next_token` returns it
+    peeked: Option<Token>,
     kwds: HashMap<&'static str, TokenType>,
     line: usize,
     pos: usize,
@@ -19,6 +24,9 @@ pub struct Scanner<'a> {
     crnt: usize,
     err: Error,
     log: bool,
+    /// if 'end of file' token has been returned
+    is_done: bool,
+    errors: usize,
 }
 
 impl<'a> Scanner<'a> {
@@ -27,6 +35,7 @@ impl<'a> Scanner<'a> {
             src,
             err,
             tokens: vec![],
+            peeked: None,
             kwds: HashMap::from([
                 ("record", RecordStmt),
                 ("extends", Extends),
@@ -51,6 +60,10 @@ impl<'a> Scanner<'a> {
                 ("from", From),
                 ("async", Async),
                 ("await", Await),
+                ("move", Move),
+                ("quote", Quote),
+                ("impl", Impl),
+                ("where", Where),
                 ("type", TypeStmt),
                 ("pub", Pub),
                 ("mut", Mut),
@@ -74,6 +87,8 @@ impl<'a> Scanner<'a> {
             start: 0,
             crnt: 0,
             log,
+            is_done: false,
+            errors: 0,
         }
     }
 
@@ -82,27 +97,55 @@ impl<'a> Scanner<'a> {
         if self.log {
             println!("  {}", "collecting tokens...".yellow())
         }
-        // advance until the end of the file
+        // collect tokens until the end of the file
+        self.tokens = self.tokens().collect();
+        if self.log {
+            println!(
+                "  {}",
+                format!("completed collecting {} tokens", self.tokens.len()).green()
+            )
+        }
+        // return collected tokens
+        &self.tokens
+    }
+
+    /// scans the next token, ends with 'end of file' token
+    pub fn next_token(&mut self) -> Option<Token> {
         while !self.is_eof() {
             self.start = self.crnt;
             self.advance_token();
+            // whitespaces and comments don't scan tokens
+            if let Some(token) = self.peeked.take() {
+                return Some(token);
+            }
         }
-        // push 'end of file' token, after reaching it
-        self.tokens.push(Token {
+        if self.is_done {
+            return None;
+        }
+        self.is_done = true;
+        Some(Token {
             token: Eof,
             lexeme: "\0".to_string(),
             value: None,
             line: self.line,
             pos: (0, 0),
-        });
-        if self.log {
-            println!(
-                "  {}",
-                format!("completed collecting {} tokens", self.tokens.len()).green()
-            )
-        }
-        // return collected tokens
-        &self.tokens
+        })
+    }
+
+    /// lazily yields tokens one at a time
+    pub fn tokens(&mut self) -> Tokens<'_, 'a> {
+        Tokens { scanner: self }
+    }
+
+    /// if errors were reported while scanning
+    pub fn has_errors(&self) -> bool {
+        self.errors > 0
+    }
+
+    /// reports an error and goes on scanning, so one bad token doesn't hide the rest
+    fn error(&mut self, code: ErrorCode, line: usize, pos: (usize, usize), args: Vec<String>) {
+        self.errors += 1;
+        self.err.clone().recoverable().throw(code, line, pos, args);
     }
 
     /// checks if end of file is reached
@@ -125,6 +168,7 @@ impl<'a> Scanner<'a> {
             ';' => self.push(Semi, None),
             ',' => self.push(Comma, None),
             '?' => self.push(Qstn, None),
+            '@' => self.push(At, None),
             // double character tokens
             ':' => self.dbl_char(':', Colon, DblColon),
             '!' => self.mult_char(Bang, &[('=', BangEq), ('!', DblBang)]),
@@ -137,8 +181,12 @@ impl<'a> Scanner<'a> {
             '.' => {
                 if self.peek().is_numeric() {
                     self.numlit('0')
+                } else if self.peek() == '.' {
+                    self.advance();
+                    // `..` and `..<`
+                    self.dbl_char('<', DblDot, DblDotLs)
                 } else {
-                    self.dbl_char('.', Dot, DblDot)
+                    self.push(Dot, None)
                 }
             }
             '<' => self.dbl_char('=', Ls, LsOrEq),
@@ -157,8 +205,20 @@ impl<'a> Scanner<'a> {
             // literals and identifiers
             '\'' => self.charlit(),
             '"' => self.strlit(),
+            'b' if self.peek() == '"' => self.byteslit(),
             _ if c.is_ascii_digit() => self.numlit(c),
             _ if UnicodeXID::is_xid_start(c) || c == '_' => self.ident(),
+            // `$`, `~`, `^`, `` ` `` and control characters, the character is skipped
+            _ if c.is_ascii() => {
+                self.error(
+                    E0x110,
+                    self.line,
+                    (self.pos, self.pos + 1),
+                    vec![c.escape_default().to_string()],
+                );
+                self.pos += 1;
+            }
+            // emoji
             _ => self.push(Ident, None),
         };
     }
@@ -208,170 +268,182 @@ impl<'a> Scanner<'a> {
         self.pos = 1;
     }
 
-    /// ignores characters in comment blocks
+    /// ignores characters in comment blocks, which can be nested
     /// `/*`, `*/`
     fn block_comment(&mut self) {
-        while !self.is_eof() {
-            if self.peek() == '*' && self.peek_next() == '/' {
-                self.advance();
-                self.advance();
-                break;
-            }
-            if self.peek() == '\n' {
-                self.line += 1;
-                self.pos = 1;
+        let (line, pos) = (self.line, self.pos);
+        // the opening `*`
+        self.advance();
+        let mut depth = 1;
+        while depth > 0 && !self.is_eof() {
+            match (self.advance(), self.peek()) {
+                ('/', '*') => {
+                    self.advance();
+                    depth += 1;
+                }
+                ('*', '/') => {
+                    self.advance();
+                    depth -= 1;
+                }
+                ('\n', _) => self.line += 1,
+                _ => {}
             }
-            self.advance();
         }
+        if depth > 0 {
+            self.error(E0x113, line, (pos, pos + 2), vec![]);
+        }
+        self.pos = self.column();
     }
 
     /// handles 'c'haracters
     fn charlit(&mut self) {
         let mut value = String::new();
-        let mut in_escape = false;
-        let mut unicode_escape = false;
-        let mut unicode_buffer = String::new();
-
-        while self.peek() != '\'' && !self.is_eof() {
-            if in_escape {
-                if unicode_escape {
-                    match self.peek() {
-                        '{' => {
-                            unicode_buffer.clear();
-                            unicode_escape = false;
-                        }
-                        '}' => {
-                            if let Ok(code_point) = u32::from_str_radix(&unicode_buffer, 16) {
-                                if let Some(ch) = char::from_u32(code_point) {
-                                    value.push(ch);
-                                } else {
-                                    value.push_str("\\u{");
-                                    value.push_str(&unicode_buffer);
-                                    value.push('}');
-                                }
-                            } else {
-                                value.push_str("\\u{");
-                                value.push_str(&unicode_buffer);
-                                value.push('}');
-                            }
-                            unicode_escape = false;
-                        }
-                        _ => {
-                            unicode_buffer.push(self.peek());
-                        }
-                    }
-                } else {
-                    match self.peek() {
-                        'n' => value.push('\n'),
-                        't' => value.push('\t'),
-                        '\\' => value.push('\\'),
-                        '"' => value.push('"'),
-                        'r' => value.push('\r'),
-                        'u' => {
-                            unicode_escape = true;
-                        }
-                        _ => value.push('\\'),
-                    }
-                    in_escape = false;
-                }
-            } else if self.peek() == '\\' {
-                in_escape = true;
-            } else {
-                value.push(self.peek());
-            }
-            self.advance();
-        }
+        let errors = self.errors;
 
-        if self.peek() != '\'' {
-            self.err
-                .throw(E0x101, self.line, (self.pos - 1, self.pos), vec![]);
-            return;
+        // an unterminated char ends at the end of the line
+        while self.peek() != '\'' && self.peek() != '\n' && !self.is_eof() {
+            match self.advance() {
+                '\\' => value.push_str(&self.escape()),
+                c => value.push(c),
+            }
         }
-        self.advance();
 
-        if value.len() != 1 {
-            self.err
-                .throw(E0x101, self.line, (self.pos - 1, self.pos), vec![]);
-            return;
+        let is_closed = self.if_char_consume('\'');
+        // a bad escape is already reported
+        if (!is_closed || value.chars().count() != 1) && self.errors == errors {
+            let len = self.src[self.start..self.crnt].chars().count();
+            self.error(E0x101, self.line, (self.pos, self.pos + len), vec![]);
         }
 
-        // Convert single-character string to char
+        // Convert single-character string to char,
+        // a malformed char is kept as '\0' for the parser to go on
         self.push(
             CharLit,
             Some(LiteralKind::Char {
-                value: value.chars().next().unwrap(),
+                value: match value.chars().count() {
+                    1 => value.chars().next().unwrap(),
+                    _ => '\0',
+                },
             }),
         );
     }
 
     /// handles "strings"
     fn strlit(&mut self) {
+        let (line, pos) = (self.line, self.pos);
         let mut value = String::new();
-        let mut in_escape = false;
-        let mut unicode_escape = false;
-        let mut unicode_buffer = String::new();
 
         while self.peek() != '"' && !self.is_eof() {
-            if self.peek() == '\n' {
-                self.line += 1;
-                self.pos = 1;
-            } else if in_escape {
-                if unicode_escape {
-                    match self.peek() {
-                        '(' => {
-                            unicode_buffer.clear();
-                            unicode_escape = false;
-                        }
-                        ')' => {
-                            if let Ok(code_point) = u32::from_str_radix(&unicode_buffer, 16) {
-                                if let Some(ch) = char::from_u32(code_point) {
-                                    value.push(ch);
-                                } else {
-                                    value.push_str("\\u(");
-                                    value.push_str(&unicode_buffer);
-                                    value.push(')');
-                                }
-                            } else {
-                                value.push_str("\\u(");
-                                value.push_str(&unicode_buffer);
-                                value.push(')');
-                            }
-                            unicode_escape = false;
-                        }
-                        _ => {
-                            unicode_buffer.push(self.peek());
-                        }
+            match self.advance() {
+                '\n' => self.line += 1,
+                // escaped braces stay literal in interpolation, as `{{` and `}}`
+                '\\' => match self.escape().as_str() {
+                    "{" => value.push_str("{{"),
+                    "}" => value.push_str("}}"),
+                    c => value.push_str(c),
+                },
+                c => value.push(c),
+            }
+        }
+
+        // an unterminated string takes the rest of the source
+        if !self.if_char_consume('"') {
+            self.error(E0x102, line, (pos, pos + 1), vec![]);
+        }
+        self.push(StrLit, Some(LiteralKind::String { value }));
+    }
+
+    /// handles b"byte strings", UTF-8 encoded, `\xFF` escapes a single byte
+    fn byteslit(&mut self) {
+        let (line, pos) = (self.line, self.pos);
+        let mut value = vec![];
+        self.advance();
+
+        while self.peek() != '"' && !self.is_eof() {
+            match self.advance() {
+                '\n' => {
+                    self.line += 1;
+                    value.push(b'\n');
+                }
+                '\\' if self.peek() == 'x' => {
+                    let (start, pos) = (self.crnt - 1, self.column() - 1);
+                    self.advance();
+                    let digits = self.crnt;
+                    while self.crnt - digits < 2 && self.peek().is_ascii_hexdigit() {
+                        self.advance();
                     }
-                } else {
-                    match self.peek() {
-                        'n' => value.push('\n'),
-                        't' => value.push('\t'),
-                        '\\' => value.push('\\'),
-                        '"' => value.push('"'),
-                        'r' => value.push('\r'),
-                        'u' => {
-                            unicode_escape = true;
+                    match u8::from_str_radix(&self.src[digits..self.crnt], 16) {
+                        Ok(byte) if self.crnt - digits == 2 => value.push(byte),
+                        _ => {
+                            let text = self.src[start..self.crnt].to_string();
+                            let len = text.chars().count();
+                            self.error(E0x111, self.line, (pos, pos + len), vec![text]);
                         }
-                        _ => value.push('\\'),
                     }
-                    in_escape = false;
                 }
-            } else if self.peek() == '\\' {
-                in_escape = true;
-            } else {
-                value.push(self.peek());
+                '\\' => value.extend(self.escape().bytes()),
+                c => value.extend(c.to_string().bytes()),
             }
-            self.advance();
         }
 
-        if self.is_eof() {
-            self.err
-                .throw(E0x102, self.line, (self.pos - 1, self.pos), vec![]);
-            return;
+        if !self.if_char_consume('"') {
+            self.error(E0x102, line, (pos, pos + 2), vec![]);
         }
+        self.push(BytesLit, Some(LiteralKind::Bytes { value }));
+    }
 
-        self.advance();
-        self.push(StrLit, Some(LiteralKind::String { value }));
+    /// decodes an escape sequence after its `\`, `\n`, `\t`, `\r`, `\0`, `\\`, `\"`, `\'`
+    /// and `\u{1F600}`, unknown ones are reported and kept as they are written
+    fn escape(&mut self) -> String {
+        let (start, pos) = (self.crnt - 1, self.column() - 1);
+        let decoded = if self.peek() == '\n' || self.is_eof() {
+            None
+        } else {
+            match self.advance() {
+                'n' => Some('\n'),
+                't' => Some('\t'),
+                'r' => Some('\r'),
+                '0' => Some('\0'),
+                '\\' => Some('\\'),
+                '"' => Some('"'),
+                '\'' => Some('\''),
+                '{' => Some('{'),
+                '}' => Some('}'),
+                'u' if self.if_char_consume('{') => {
+                    let digits = self.crnt;
+                    while self.peek().is_ascii_hexdigit() {
+                        self.advance();
+                    }
+                    let code = u32::from_str_radix(&self.src[digits..self.crnt], 16)
+                        .ok()
+                        .and_then(char::from_u32);
+                    if self.if_char_consume('}') {
+                        code
+                    } else {
+                        None
+                    }
+                }
+                _ => None,
+            }
+        };
+        match decoded {
+            Some(c) => c.to_string(),
+            None => {
+                let text = self.src[start..self.crnt].to_string();
+                let len = text.chars().count();
+                self.error(E0x111, self.line, (pos, pos + len), vec![text.clone()]);
+                text
+            }
+        }
+    }
+
+    /// column of the current character, in tokens spanning multiple lines as well
+    fn column(&self) -> usize {
+        let text = &self.src[self.start..self.crnt];
+        match text.rfind('\n') {
+            Some(i) => text[i + 1..].chars().count() + 1,
+            None => self.pos + text.chars().count(),
+        }
     }
 
     /// handles identifier, Unicode emoji, '_' or alphanumeric
@@ -443,6 +515,26 @@ impl<'a> Scanner<'a> {
             }
         }
 
+        // letters and digits right after the number are a part of it, `0b12`, `12px`
+        while UnicodeXID::is_xid_continue(self.peek()) {
+            self.advance();
+        }
+
+        // underscores may only separate digits, `1_000` but not `1__000`, `1_` or `0x_1`
+        let digits = &self.src[self.start + if radix == 10 { 0 } else { 2 }..self.crnt];
+        let chars: Vec<char> = digits.chars().collect();
+        let misplaced = chars.iter().enumerate().any(|(i, &c)| {
+            c == '_'
+                && !(i > 0
+                    && chars[i - 1].is_digit(radix)
+                    && chars.get(i + 1).is_some_and(|c| c.is_digit(radix)))
+        });
+        if misplaced {
+            let lexeme = self.src[self.start..self.crnt].to_string();
+            let len = lexeme.chars().count();
+            self.error(E0x112, self.line, (self.pos, self.pos + len), vec![lexeme]);
+        }
+
         // Filter out underscores and prepare the numeric value string
         let sub: String = self.src[self.start..self.crnt]
             .chars()
@@ -451,27 +543,39 @@ impl<'a> Scanner<'a> {
 
         // Parse the value based on the radix
         let value = if radix == 10 {
-            sub.parse::<f32>().unwrap_or(0.0)
+            sub.parse::<f32>().ok()
+        } else if sub.len() > 2 {
+            i32::from_str_radix(&sub[2..], radix).ok().map(|v| v as f32)
         } else {
-            if sub.len() > 2 {
-                i32::from_str_radix(&sub[2..], radix)
-                    .map(|v| v as f32)
-                    .unwrap_or(0.0)
-            } else {
-                0.0
-            }
+            None
         };
+        // a malformed number is kept as 0 for the parser to go on
+        if value.is_none() {
+            let lexeme = self.src[self.start..self.crnt].to_string();
+            let len = lexeme.chars().count();
+            self.error(E0x104, self.line, (self.pos, self.pos + len), vec![lexeme]);
+        }
 
         // Push the parsed number literal
-        self.push(NumLit, Some(LiteralKind::Number { base, value }));
+        self.push(
+            NumLit,
+            Some(LiteralKind::Number {
+                base,
+                value: value.unwrap_or(0.0),
+            }),
+        );
     }
 
-    /// just as name says, pushes tokens
+    /// keeps the scanned token for `next_token`, a token spanning multiple lines
+    /// is placed on its last line
     fn push(&mut self, token: TokenType, value: Option<LiteralKind>) {
         let lexeme = &self.src[self.start..self.crnt];
-        let pos = (self.pos, self.pos + lexeme.chars().count());
-        self.pos += lexeme.chars().count();
-        self.tokens.push(Token {
+        let pos = match lexeme.contains('\n') {
+            true => (1, self.column()),
+            false => (self.pos, self.column()),
+        };
+        self.pos = pos.1;
+        self.peeked = Some(Token {
             token,
             lexeme: lexeme.to_string(),
             line: self.line,
@@ -480,6 +584,15 @@ impl<'a> Scanner<'a> {
         });
     }
 
+    /// advances past the next character if it matches
+    fn if_char_consume(&mut self, c: char) -> bool {
+        if self.is_eof() || self.peek() != c {
+            return false;
+        }
+        self.advance();
+        true
+    }
+
     /// checks the next character
     fn peek(&self) -> char {
         self.src[self.crnt..].chars().next().unwrap_or('\0')
@@ -497,3 +610,66 @@ impl<'a> Scanner<'a> {
         c
     }
 }
+
+/// streaming iterator over scanned tokens
+pub struct Tokens<'s, 'a> {
+    scanner: &'s mut Scanner<'a>,
+}
+
+impl Iterator for Tokens<'_, '_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        self.scanner.next_token()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::Project;
+
+    #[test]
+    fn tokens_match_scan() {
+        let src = concat!(
+            "// a comment\n",
+            "let mut x: number = 1_000 + 2.5e3; /* a /* nested */ comment */\n",
+            "print(\"a\\n{x}\", 'c', x >= 2 && x != 3);\n",
+        );
+        let err = Error::new(src, Project::new());
+        let scanned = Scanner::new(src, err.clone(), false).scan().clone();
+        let streamed: Vec<Token> = Scanner::new(src, err, false).tokens().collect();
+        assert_eq!(streamed, scanned);
+        assert_eq!(streamed.last().map(|t| &t.token), Some(&Eof));
+        assert!(streamed.len() > 20);
+    }
+
+    /// the kinds and lexemes of the scanned tokens, and if errors were reported
+    fn scan(src: &str) -> (Vec<(TokenType, String)>, bool) {
+        let err = Error::new(src, Project::new());
+        let mut scanner = Scanner::new(src, err, false);
+        let tokens = scanner
+            .scan()
+            .iter()
+            .map(|t| (t.token.clone(), t.lexeme.clone()))
+            .collect();
+        (tokens, scanner.has_errors())
+    }
+
+    #[test]
+    fn unknown_char_keeps_the_tokens_around_it() {
+        let (tokens, has_errors) = scan("let x = 1 $ + 2;\nprint(x);\n");
+        assert!(has_errors);
+        assert_eq!(tokens, scan("let x = 1 + 2;\nprint(x);\n").0);
+    }
+
+    #[test]
+    fn malformed_literals_keep_the_next_lines() {
+        let (tokens, has_errors) = scan("let c = 'ab';\nlet n = 12px;\nprint(c);\n");
+        assert!(has_errors);
+        let kinds: Vec<&TokenType> = tokens.iter().map(|(kind, _)| kind).collect();
+        assert_eq!(kinds[3], &CharLit);
+        assert_eq!(kinds[8], &NumLit);
+        assert_eq!(&tokens[10..], &scan("print(c);\n").0[..]);
+    }
+}
@@ -1,16 +1,16 @@
 // Absurd type parser
-use super::Parser;
+use super::{ParseResult, Parser};
 use crate::{
     ast::{LiteralKind, Token, TokenType::*},
-    interpreter::types::TypeKind,
+    interpreter::{expr::Expression, types::TypeKind},
 };
 
 impl Parser {
-    pub fn consume_type(&mut self) -> Token {
-        let mut lhs = self.primary_type();
+    pub fn consume_type(&mut self) -> ParseResult<Token> {
+        let mut lhs = self.primary_type()?;
         // T || T
         if self.if_token_consume(Or) {
-            let mut right = self.consume_type();
+            let mut right = self.consume_type()?;
             let value = Some(LiteralKind::Type(Box::new(TypeKind::Either {
                 lhs: Box::new(lhs.token_to_typekind()),
                 rhs: Box::new(right.token_to_typekind()),
@@ -45,10 +45,10 @@ impl Parser {
                 line: lhs.line,
             };
         }
-        lhs
+        Ok(lhs)
     }
 
-    fn primary_type(&mut self) -> Token {
+    fn primary_type(&mut self) -> ParseResult<Token> {
         match self.peek().token {
             // Record<{i: T, i: T}>
             Record => self.object(),
@@ -57,42 +57,42 @@ impl Parser {
             // Tuple<(T, T)>
             Tuple => self.tuple(),
             // |i, i| i
-            Pipe => self.callback(),
+            Pipe | Or => self.callback(),
             // literal types
-            StrLit | NumLit | CharLit | Null | TrueLit | FalseLit => self.literal(),
+            StrLit | NumLit | CharLit | Null | TrueLit | FalseLit => Ok(self.literal()),
             // standard types
             AnyIdent | BoolIdent | CharIdent | VoidIdent | ArrayIdent | NumIdent | StrIdent => {
                 self.builtin()
             }
             // for calling aliases
             Ident => self.ident(),
-            c => Token {
+            c => Ok(Token {
                 token: c,
                 lexeme: self.peek().lexeme.clone(),
                 pos: self.peek().pos,
                 value: None,
                 line: self.peek().line,
-            },
+            }),
         }
     }
 
-    fn object(&mut self) -> Token {
+    fn object(&mut self) -> ParseResult<Token> {
         // Record<{i: T, i: T}>
         let mut fields = vec![];
-        self.consume(Record);
-        self.consume(Ls);
-        self.consume(LBrace);
+        self.consume(Record)?;
+        self.consume(Ls)?;
+        self.consume(LBrace)?;
         while !self.if_token_consume(RBrace) {
-            let ident = self.consume(Ident);
-            self.consume(Colon);
-            let value = self.consume_type();
+            let ident = self.consume(Ident)?;
+            self.consume(Colon)?;
+            let value = self.consume_type()?;
             fields.push((ident, TypeKind::Var { name: value }));
             if !self.if_token_consume(Comma) {
-                self.consume(RBrace);
+                self.consume(RBrace)?;
                 break;
             }
         }
-        self.consume(Gr);
+        self.consume(Gr)?;
         let value = Some(LiteralKind::Type(Box::new(TypeKind::Record {
             fields: fields.clone(),
         })));
@@ -100,54 +100,72 @@ impl Parser {
             .iter()
             .map(|(i, v)| format!("{}: {}, ", i.lexeme.clone(), v.clone()))
             .collect();
-        Token {
+        Ok(Token {
             token: Type,
             lexeme: format!("{{ {}}}", s),
             value,
             line: self.peek().line,
             pos: self.peek().pos,
-        }
+        })
     }
 
-    fn builtin(&mut self) -> Token {
+    fn builtin(&mut self) -> ParseResult<Token> {
         let token = self.consume_some(&[
             AnyIdent, BoolIdent, CharIdent, Null, VoidIdent, ArrayIdent, NumIdent, StrIdent,
-        ]);
+        ])?;
         let value = Some(LiteralKind::Type(Box::new(TypeKind::Var {
             name: token.clone(),
         })));
-        Token {
+        Ok(Token {
             token: token.token,
             lexeme: token.lexeme,
             value,
             line: token.line,
             pos: token.pos,
-        }
+        })
     }
 
-    fn ident(&mut self) -> Token {
-        let token = self.consume(Ident);
+    fn ident(&mut self) -> ParseResult<Token> {
+        let token = self.consume(Ident)?;
+        // type parameters of the enclosing functions, the innermost first
+        if let Some((name, bound)) = self
+            .generics
+            .iter()
+            .rev()
+            .find(|(name, _)| name.lexeme == token.lexeme)
+        {
+            return Ok(Token {
+                token: Type,
+                lexeme: token.lexeme.clone(),
+                value: Some(LiteralKind::Type(Box::new(TypeKind::Generic {
+                    name: name.clone(),
+                    bound: bound.clone(),
+                }))),
+                line: token.line,
+                pos: token.pos,
+            });
+        }
         let value = Some(LiteralKind::Type(Box::new(TypeKind::Var {
             name: token.clone(),
         })));
 
         if self.is_uppercase(token.clone()) {
-            return Token {
+            return Ok(Token {
                 token: Enum,
                 lexeme: token.lexeme,
                 value: None,
                 line: token.line,
                 pos: token.pos,
-            };
+            });
         }
 
-        Token {
+        Ok(Token {
             token: Ident,
             lexeme: token.lexeme,
             value,
             line: token.line,
             pos: token.pos,
-        }
+        })
     }
 
     fn literal(&mut self) -> Token {
@@ -165,39 +183,43 @@ impl Parser {
         }
     }
 
-    fn callback(&mut self) -> Token {
-        self.consume(Pipe);
-        let mut params: Vec<TypeKind> = vec![];
-        while !self.if_token_consume(Pipe) {
-            let param = self.consume_type();
-            params.push(TypeKind::Var {
-                name: param.clone(),
-            });
-            if !self.if_token_consume(Comma) {
-                break;
+    fn callback(&mut self) -> ParseResult<Token> {
+        // |T, T| T
+        let start = self.peek().clone();
+        let mut params = vec![];
+        // `|| T` takes no parameters
+        if !self.if_token_consume(Or) {
+            self.consume(Pipe)?;
+            while !self.if_token_consume(Pipe) {
+                let param = self.consume_type()?;
+                params.push(TypeKind::Var { name: param });
+                if !self.if_token_consume(Comma) {
+                    self.consume(Pipe)?;
+                    break;
+                }
             }
         }
-        self.advance();
-        let return_type = self.consume_type();
-        Token {
+        let return_type = self.consume_type()?;
+        let kind = TypeKind::Callback {
+            params,
+            ret: Box::new(TypeKind::Var { name: return_type }),
+        };
+        Ok(Token {
             token: FuncIdent,
-            lexeme: "callback".to_string(),
-            value: Some(LiteralKind::Type(Box::new(TypeKind::Callback {
-                params,
-                ret: Box::new(TypeKind::Var { name: return_type }),
-            }))),
-            line: self.peek().line,
-            pos: self.peek().pos,
-        }
+            lexeme: kind.to_string(),
+            value: Some(LiteralKind::Type(Box::new(kind))),
+            line: start.line,
+            pos: start.pos,
+        })
     }
 
-    fn vec(&mut self) -> Token {
+    fn vec(&mut self) -> ParseResult<Token> {
         // Vec<T>
-        self.consume(VecT);
-        self.consume(Ls);
-        let typ = self.consume_type();
-        self.consume(Gr);
-        Token {
+        self.consume(VecT)?;
+        self.consume(Ls)?;
+        let typ = self.consume_type()?;
+        self.consume(Gr)?;
+        Ok(Token {
             token: VecLit,
             lexeme: typ.clone().lexeme,
             pos: self.peek().pos,
@@ -205,32 +227,63 @@ impl Parser {
                 kind: Box::new(TypeKind::Var { name: typ }),
             }))),
             line: self.peek().line,
+        })
+    }
+
+    /// infers `Vec<T>` for an unannotated vector literal,
+    /// widened to `Vec<any>` when the items have different or unknown types
+    pub fn inferred_vec(items: &[Expression]) -> Token {
+        let types: Vec<String> = items
+            .iter()
+            .map(|item| match item {
+                Expression::Value { value, .. } => value.type_name(),
+                _ => "any".to_string(),
+            })
+            .collect();
+        let typ = match types.first() {
+            Some(first) if types.iter().all(|t| t == first) => match first.as_str() {
+                "number" => Token::empty(NumIdent, "number", None),
+                "string" => Token::empty(StrIdent, "string", None),
+                "char" => Token::empty(CharIdent, "char", None),
+                "bool" => Token::empty(BoolIdent, "bool", None),
+                _ => Token::empty(AnyIdent, "any", None),
+            },
+            _ => Token::empty(AnyIdent, "any", None),
+        };
+        Token {
+            token: VecLit,
+            lexeme: typ.lexeme.clone(),
+            pos: (0, 0),
+            value: Some(LiteralKind::Type(Box::new(TypeKind::Vec {
+                kind: Box::new(TypeKind::Var { name: typ }),
+            }))),
+            line: 0,
         }
     }
 
-    fn tuple(&mut self) -> Token {
+    fn tuple(&mut self) -> ParseResult<Token> {
         // Tuple<(T, T)>
-        self.consume(Tuple);
-        self.consume(Ls);
-        self.consume(LParen);
+        self.consume(Tuple)?;
+        self.consume(Ls)?;
+        self.consume(LParen)?;
         let mut types = vec![];
         while !self.is_token(RParen) {
             types.push(TypeKind::Var {
-                name: self.consume_type(),
+                name: self.consume_type()?,
             });
             if !self.if_token_consume(Comma) {
-                self.consume(RParen);
+                self.consume(RParen)?;
                 break;
             }
         }
 
-        self.consume(Gr);
-        Token {
+        self.consume(Gr)?;
+        Ok(Token {
             token: TupleLit,
             lexeme: "tuple".to_string(),
             pos: self.peek().pos,
             value: Some(LiteralKind::Type(Box::new(TypeKind::Tuple { types }))),
             line: self.peek().line,
-        }
+        })
     }
 }
@@ -1,18 +1,56 @@
 // Absurd resolver, it resolves statements and returns locals
-use crate::ast::{FuncBody, Statement, Token};
+use crate::ast::{CallType, FuncBody, LiteralType, Statement, TestKind, Token, TokenType};
+use crate::bundler::{interpolation, Part};
 use crate::errors::{Error, ErrorCode::*};
+use crate::hoister::{bindings, walk_body, walk_stmts, Node};
 use crate::interpreter::env::Env;
-use crate::interpreter::expr::Expression;
+use crate::interpreter::expr::{AssignKind, Expression};
+use crate::interpreter::types::{
+    bound_of, generic_of, is_primitive, item_type, literal_mismatch, satisfies, tuple_types, BOUNDS,
+};
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
 #[derive(Debug, Clone)]
 pub struct Resolver {
     locals: HashMap<usize, usize>,
     scopes: Vec<HashMap<String, bool>>,
+    /// locals holding values which can't be called, a set for each of `scopes`
+    uncallable: Vec<HashSet<String>>,
     is_crnt_fnc: bool,
     is_crnt_loop: bool,
+    /// enum names with their items, for match exhaustiveness
+    enums: HashMap<String, Vec<String>>,
+    /// top level variables, false until their first initializer is resolved
+    globals: HashMap<String, bool>,
+    /// names of the variable whose initializer is being resolved
+    crnt_init: Vec<String>,
+    /// parameter names of top level functions, for labeled arguments
+    funcs: HashMap<String, Vec<String>>,
+    /// top level `@deprecated` functions and records, with their messages
+    deprecated: HashMap<String, String>,
+    /// positional-only count and keyword-only start of top level functions using `/` or `*`
+    markers: HashMap<String, (usize, Option<usize>)>,
+    /// parameter types of top level functions with type parameters
+    generic: HashMap<String, Vec<Token>>,
+    /// moved variables, with the depth of the scope declaring them
+    moved: HashSet<(usize, String)>,
+    /// depth of the innermost loop or function, variables declared at or
+    /// above it can't be moved, the body may run again
+    move_boundary: Option<usize>,
+    /// `let` bindings not read yet, a map for each of `scopes`
+    unread: Vec<HashMap<String, Token>>,
+    /// names read outside of any local binding, for unread top level variables
+    global_reads: HashSet<String>,
+    /// functions, records, enums, types and imports declared anywhere
+    declared: HashSet<String>,
+    /// names read in quotes, any binding of them may be read by `eval`
+    quoted: HashSet<String>,
+    /// `use *` and unnamed `mod`s bring in names which aren't known here
+    is_open: bool,
+    /// names in quotes are looked up where `eval` runs them
+    is_quoted: bool,
     err: Error,
 }
 
@@ -21,8 +59,24 @@ impl Resolver {
         Resolver {
             locals: HashMap::new(),
             scopes: Vec::new(),
+            uncallable: Vec::new(),
             is_crnt_fnc: false,
             is_crnt_loop: false,
+            enums: HashMap::new(),
+            globals: HashMap::new(),
+            crnt_init: Vec::new(),
+            funcs: HashMap::new(),
+            markers: HashMap::new(),
+            deprecated: HashMap::new(),
+            generic: HashMap::new(),
+            moved: HashSet::new(),
+            move_boundary: None,
+            unread: Vec::new(),
+            global_reads: HashSet::new(),
+            declared: HashSet::new(),
+            quoted: HashSet::new(),
+            is_open: false,
+            is_quoted: false,
             err,
         }
     }
@@ -33,7 +87,62 @@ impl Resolver {
         stmts: &[Statement],
         env: &Rc<RefCell<Env>>,
     ) -> HashMap<usize, usize> {
+        // enums are collected first, functions are hoisted above them
+        stmts.iter().for_each(|stmt| match stmt {
+            Statement::Enum { name, items, .. } => {
+                self.enums.insert(
+                    name.lexeme.clone(),
+                    items.iter().map(|(item, _)| item.lexeme.clone()).collect(),
+                );
+            }
+            Statement::Var { names, .. } => names.iter().for_each(|name| {
+                self.globals.insert(name.lexeme.clone(), false);
+            }),
+            Statement::Func {
+                name,
+                params,
+                positional_only,
+                keyword_only,
+                deprecated,
+                ..
+            } => {
+                self.funcs.insert(
+                    name.lexeme.clone(),
+                    params
+                        .iter()
+                        .map(|(param, _)| param.lexeme.clone())
+                        .collect(),
+                );
+                if let Some(message) = deprecated {
+                    self.deprecated.insert(name.lexeme.clone(), message.clone());
+                }
+                if *positional_only > 0 || keyword_only.is_some() {
+                    self.markers
+                        .insert(name.lexeme.clone(), (*positional_only, *keyword_only));
+                }
+                if params.iter().any(|(_, t)| generic_of(t).is_some()) {
+                    self.generic.insert(
+                        name.lexeme.clone(),
+                        params.iter().map(|(_, t)| t.clone()).collect(),
+                    );
+                }
+            }
+            Statement::Record {
+                name,
+                deprecated: Some(message),
+                ..
+            } => {
+                self.deprecated.insert(name.lexeme.clone(), message.clone());
+            }
+            _ => {}
+        });
+        self.memos(stmts);
+        self.effects(stmts);
+        self.entries(stmts);
+        self.tests(stmts);
+        self.declarations(stmts);
         stmts.iter().for_each(|stmt| self.resolve_stmt(stmt, env));
+        self.unread_globals(stmts);
         self.locals.clone()
     }
 
@@ -55,7 +164,23 @@ impl Resolver {
             Statement::Block { stmts } => self.block(stmts, env),
             Statement::Break {} => self.breaks(),
             Statement::Expression { expr } => self.expr(expr, env),
-            Statement::Func { body, params, .. } => self.func(body, params.as_slice(), env),
+            Statement::Func {
+                name,
+                value_type,
+                body,
+                params,
+                generics,
+                ..
+            } => {
+                self.bounds(generics);
+                self.types(params.iter().map(|(_, t)| t).chain([value_type]));
+                self.func(body, params.as_slice(), env);
+                if let FuncBody::Statements(stmts) = body {
+                    if value_type.lexeme != "void" && !self.always_returns(stmts) {
+                        self.err.throw(E0x406, name.line, name.pos, vec![]);
+                    }
+                }
+            }
             Statement::Loop { body, .. } => self.loops(body, env),
             Statement::Match {
                 cond,
@@ -63,9 +188,35 @@ impl Resolver {
                 def_case,
             } => self.matchs(cond, def_case, cases, env),
             Statement::Return { expr } => self.returns(expr, env),
-            Statement::Use { names, .. } => self.uses(names),
-            Statement::Var { names, value, .. } => self.var(names, value, env),
+            Statement::Use { names, alias, .. } => self.uses(names, alias),
+            Statement::Var {
+                names,
+                value,
+                value_type,
+                constraint,
+                ..
+            } => {
+                self.types([value_type]);
+                self.items(names, value_type, value);
+                self.annotation(names, value_type, value);
+                self.var(names, value_type, value, env);
+                // constraints see the bound names
+                if let Some(constraint) = constraint {
+                    self.expr(constraint, env);
+                }
+            }
             Statement::While { body, cond } => self.whiles(body, cond, env),
+            Statement::Impl { methods, .. } => self.resolve_many(methods, env),
+            Statement::Record {
+                extends,
+                fields,
+                getters,
+                ..
+            } => {
+                self.types(extends.iter().chain(fields.iter().map(|f| &f.value)));
+                self.resolve_many(getters, env);
+            }
+            Statement::Type { value, .. } => self.types([value]),
             _ => {}
         }
     }
@@ -74,14 +225,14 @@ impl Resolver {
         &mut self,
         iterator: &Token,
         index: &Option<Token>,
-        body: &Vec<Statement>,
+        body: &[Statement],
         expr: &Expression,
         env: &Rc<RefCell<Env>>,
     ) {
-        
         self.expr(expr, env);
         let encl_loop = self.is_crnt_loop;
         self.is_crnt_loop = true;
+        let encl_boundary = self.move_boundary.replace(self.scopes.len());
         self.scope_start();
         self.declare(iterator);
         self.define(iterator);
@@ -91,10 +242,15 @@ impl Resolver {
         }
         self.resolve_many(body, env);
         self.scope_end();
+        self.move_boundary = encl_boundary;
         self.is_crnt_loop = encl_loop;
     }
 
-    fn uses(&mut self, names: &Vec<(Token, Option<Token>)>) {
+    fn uses(&mut self, names: &Vec<(Token, Option<Token>)>, alias: &Option<Token>) {
+        if let Some(alias) = alias {
+            self.declare(alias);
+            self.define(alias);
+        }
         for (old, new) in names {
             if let Some(new_name) = new {
                 self.declare(new_name);
@@ -106,24 +262,315 @@ impl Resolver {
         }
     }
 
-    fn var(&mut self, names: &Vec<Token>, value: &Option<Expression>, env: &Rc<RefCell<Env>>) {
+    fn var(
+        &mut self,
+        names: &Vec<Token>,
+        value_type: &Token,
+        value: &Option<Expression>,
+        env: &Rc<RefCell<Env>>,
+    ) {
+        let encl_init = std::mem::replace(
+            &mut self.crnt_init,
+            names.iter().map(|name| name.lexeme.clone()).collect(),
+        );
         for name in names {
             self.declare(name);
+            self.moved.remove(&(self.scopes.len(), name.lexeme.clone()));
             if let Some(value) = value {
                 self.expr(value, env);
             }
             self.define(name);
+            if let Some(unread) = self.unread.last_mut() {
+                unread.insert(name.lexeme.clone(), name.clone());
+            }
+            if is_uncallable(value_type, value.as_ref()) {
+                self.set_uncallable(name);
+            }
+        }
+        self.crnt_init = encl_init;
+        if self.scopes.is_empty() {
+            names.iter().for_each(|name| {
+                self.globals.insert(name.lexeme.clone(), true);
+            });
+        }
+    }
+
+    /// checks literal items of a vector against its annotated item type, nested
+    /// vectors included, unannotated vectors are inferred as `Vec<any>` when their items differ
+    fn items(&mut self, names: &[Token], value_type: &Token, value: &Option<Expression>) {
+        // constant vectors are already folded by the hoister
+        let Some(
+            value @ (Expression::Vec { .. }
+            | Expression::Value {
+                value: LiteralType::Vec(_),
+                ..
+            }),
+        ) = value
+        else {
+            return;
+        };
+        if let Some((path, expected, found)) = mismatch(value_type, value) {
+            self.err.throw(
+                E0x433,
+                names[0].line,
+                names[0].pos,
+                vec![path, names[0].lexeme.clone(), expected, found],
+            );
         }
     }
 
-    fn whiles(&mut self, body: &Vec<Statement>, cond: &Expression, env: &Rc<RefCell<Env>>) {
+    /// checks that `@memo` functions only depend on their arguments,
+    /// their results are cached by them
+    fn memos(&self, stmts: &[Statement]) {
+        let (funcs, mutable) = bodies(stmts);
+        for stmt in stmts {
+            let Statement::Func {
+                name,
+                params,
+                is_async,
+                is_memo: true,
+                ..
+            } = stmt
+            else {
+                continue;
+            };
+            let reason = if *is_async {
+                Some("it's async".to_string())
+            } else if let Some((param, _)) =
+                params.iter().find(|(_, t)| t.token == TokenType::FuncIdent)
+            {
+                Some(format!("the parameter '{}' is a function", param.lexeme))
+            } else {
+                impurity(&name.lexeme, &funcs, &mutable, true, &mut HashSet::new())
+            };
+            if let Some(reason) = reason {
+                self.err.throw(
+                    E0x434,
+                    name.line,
+                    name.pos,
+                    vec![name.lexeme.clone(), reason],
+                );
+            }
+        }
+    }
+
+    /// checks that `@pure` functions only depend on their arguments, following the functions
+    /// they call, and that there are no `@io` functions while side effects are disabled
+    fn effects(&self, stmts: &[Statement]) {
+        let (funcs, mutable) = bodies(stmts);
+        for stmt in stmts {
+            match stmt {
+                Statement::Func {
+                    name,
+                    is_pure: true,
+                    ..
+                } => {
+                    if let Some(reason) =
+                        impurity(&name.lexeme, &funcs, &mutable, false, &mut HashSet::new())
+                    {
+                        self.err.throw(
+                            E0x439,
+                            name.line,
+                            name.pos,
+                            vec![name.lexeme.clone(), reason],
+                        );
+                    }
+                }
+                Statement::Func {
+                    name, is_io: true, ..
+                } if !self.err.project().side_effects => {
+                    self.err.throw(E0x415, name.line, name.pos, vec![]);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// checks that there's at most one `@entry` function, declared at the top level and
+    /// taking nothing or the command line arguments, `args: Vec<string>`
+    fn entries(&self, stmts: &[Statement]) {
+        let mut entry: Option<&Token> = None;
+        walk_stmts(stmts, &mut |node| {
+            let Node::Stmt(
+                stmt @ Statement::Func {
+                    name,
+                    params,
+                    is_entry: true,
+                    ..
+                },
+            ) = node
+            else {
+                return;
+            };
+            let is_args =
+                |typ: &Token| item_type(typ).is_some_and(|item| item.token == TokenType::StrIdent);
+            let reason = if !stmts.iter().any(|top| std::ptr::eq(top, stmt)) {
+                Some("it isn't declared at the top level".to_string())
+            } else if let Some(entry) = entry {
+                Some(format!("'{}' is already the entry", entry.lexeme))
+            } else if params.len() > 1 || params.first().is_some_and(|(_, t)| !is_args(t)) {
+                Some("it can only take the command line arguments, 'args: Vec<string>'".to_string())
+            } else {
+                entry = Some(name);
+                None
+            };
+            if let Some(reason) = reason {
+                self.err.throw(
+                    E0x438,
+                    name.line,
+                    name.pos,
+                    vec![name.lexeme.clone(), reason],
+                );
+            }
+        });
+    }
+
+    /// checks that `@test`, `@before` and `@after` functions can be called by the test runner
+    fn tests(&self, stmts: &[Statement]) {
+        walk_stmts(stmts, &mut |node| {
+            let Node::Stmt(
+                stmt @ Statement::Func {
+                    name,
+                    params,
+                    test: Some(kind),
+                    ..
+                },
+            ) = node
+            else {
+                return;
+            };
+            let reason = if !stmts.iter().any(|top| std::ptr::eq(top, stmt)) {
+                "it isn't declared at the top level"
+            } else if !params.is_empty() {
+                "it's called without arguments"
+            } else {
+                return;
+            };
+            let annotation = match kind {
+                TestKind::Test => "test",
+                TestKind::Before => "before",
+                TestKind::After => "after",
+            };
+            self.err.throw(
+                E0x440,
+                name.line,
+                name.pos,
+                vec![
+                    name.lexeme.clone(),
+                    annotation.to_string(),
+                    reason.to_string(),
+                ],
+            );
+        });
+    }
+
+    /// collects the names which can be read before the resolver reaches them
+    fn declarations(&mut self, stmts: &[Statement]) {
+        let mut declared = HashSet::new();
+        let mut quoted = HashSet::new();
+        let mut is_open = false;
+        walk_stmts(stmts, &mut |node| match node {
+            Node::Stmt(
+                Statement::Func { name, .. }
+                | Statement::Record { name, .. }
+                | Statement::Enum { name, .. }
+                | Statement::Type { name, .. },
+            ) => {
+                declared.insert(name.lexeme.clone());
+            }
+            Node::Stmt(Statement::Use {
+                names, all, alias, ..
+            }) => {
+                is_open |= *all;
+                declared.extend(alias.iter().map(|alias| alias.lexeme.clone()));
+                declared.extend(
+                    names
+                        .iter()
+                        .map(|(old, new)| new.as_ref().unwrap_or(old).lexeme.clone()),
+                );
+            }
+            Node::Stmt(Statement::Mod { name, .. }) => match name {
+                Some(name) => {
+                    declared.insert(name.clone());
+                }
+                None => is_open = true,
+            },
+            Node::Expr(Expression::Quote { body, .. }) => walk_body(body, &mut |node| {
+                if let Node::Expr(Expression::Var { name, .. }) = node {
+                    quoted.insert(name.lexeme.clone());
+                }
+            }),
+            _ => {}
+        });
+        self.declared = declared;
+        self.quoted = quoted;
+        self.is_open = is_open;
+    }
+
+    /// warns about top level variables which are never read, public ones
+    /// are read by other modules
+    fn unread_globals(&self, stmts: &[Statement]) {
+        for stmt in stmts {
+            if let Statement::Var {
+                names,
+                is_pub: false,
+                ..
+            } = stmt
+            {
+                names
+                    .iter()
+                    .filter(|name| !self.global_reads.contains(&name.lexeme))
+                    .for_each(|name| self.unread(name));
+            }
+        }
+    }
+
+    /// checks literal initializers against primitive annotations, name by name
+    /// when each has its own type, `let a: number, b: string = 1, "x";`
+    fn annotation(&self, names: &[Token], value_type: &Token, value: &Option<Expression>) {
+        match (value, names) {
+            (Some(Expression::Value { value, .. }), [name]) => {
+                self.annotated(name, value_type, value)
+            }
+            (Some(Expression::Tuple { items, .. }), names) => {
+                let Some(types) = tuple_types(value_type) else {
+                    return;
+                };
+                for ((name, typ), item) in names.iter().zip(&types).zip(items) {
+                    if let Expression::Value { value, .. } = item {
+                        self.annotated(name, typ, value);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// checks a literal against a primitive annotation,
+    /// suggesting the type of the value, `let x: number = "a";`
+    fn annotated(&self, name: &Token, value_type: &Token, value: &LiteralType) {
+        // inferred types carry the value as their lexeme, so compare type names
+        let declared = value_type.token.to_string();
+        if is_primitive(value_type) && value.is_primitive() && value.type_name() != declared {
+            self.err.throw(
+                E0x432,
+                name.line,
+                name.pos,
+                vec![name.lexeme.clone(), declared, value.type_name()],
+            );
+        }
+    }
+
+    fn whiles(&mut self, body: &[Statement], cond: &Expression, env: &Rc<RefCell<Env>>) {
         let encl_loop = self.is_crnt_loop;
+        let encl_boundary = self.move_boundary.replace(self.scopes.len());
         self.expr(cond, env);
         self.is_crnt_loop = true;
         self.scope_start();
         self.resolve_many(body, env);
         self.scope_end();
         self.is_crnt_loop = encl_loop;
+        self.move_boundary = encl_boundary;
     }
 
     fn breaks(&mut self) {
@@ -135,11 +582,11 @@ impl Resolver {
     fn func(&mut self, body: &FuncBody, params: &[(Token, Token)], env: &Rc<RefCell<Env>>) {
         let encl_func = self.is_crnt_fnc;
         self.is_crnt_fnc = true;
+        // functions can refer to the variable they initialize, `let f = |n| f(n - 1);`
+        let encl_init = std::mem::take(&mut self.crnt_init);
+        let encl_boundary = self.move_boundary.replace(self.scopes.len());
         self.scope_start();
-        params.iter().for_each(|(name, _)| {
-            self.declare(name);
-            self.define(name);
-        });
+        self.params(params);
         match body {
             FuncBody::Statements(body) => {
                 self.resolve_many(body, env);
@@ -152,29 +599,155 @@ impl Resolver {
             FuncBody::Expression(expr) => self.expr(expr, env),
         }
         self.scope_end();
+        self.move_boundary = encl_boundary;
         self.is_crnt_fnc = encl_func;
+        self.crnt_init = encl_init;
+    }
+
+    /// if every path through the statements ends in a return
+    fn always_returns(&self, stmts: &[Statement]) -> bool {
+        stmts.iter().any(|stmt| match stmt {
+            Statement::Return { .. } => true,
+            Statement::Block { stmts } => self.always_returns(stmts),
+            // `loop` only leaves through `break`, which is checked at runtime
+            Statement::Loop { .. } => true,
+            Statement::Expression {
+                expr: Expression::Call { name, .. },
+            } => matches!(&**name, Expression::Var { name, .. } if name.lexeme == "panic"),
+            Statement::If {
+                body,
+                else_if_branches,
+                else_branch: Some(else_branch),
+                ..
+            } => {
+                self.always_returns(body)
+                    && else_if_branches
+                        .iter()
+                        .all(|(_, body)| self.always_returns(body))
+                    && self.always_returns(else_branch)
+            }
+            Statement::Match {
+                cases, def_case, ..
+            } => {
+                let arms_return = cases.iter().all(|(_, _, body)| match body {
+                    FuncBody::Statements(stmts) => self.always_returns(stmts),
+                    FuncBody::Expression(_) => false,
+                });
+                let def_returns = match def_case {
+                    Some(FuncBody::Statements(stmts)) => self.always_returns(stmts),
+                    Some(FuncBody::Expression(_)) => false,
+                    None => {
+                        matches!(self.enum_cases(cases), Some((_, missing)) if missing.is_empty())
+                    }
+                };
+                arms_return && def_returns
+            }
+            _ => false,
+        })
     }
 
-    fn loops(&mut self, body: &Vec<Statement>, env: &Rc<RefCell<Env>>) {
+    /// returns the enum the match cases are items of, with the items they don't name
+    fn enum_cases<'c>(
+        &self,
+        cases: &'c [(Expression, Option<Expression>, FuncBody)],
+    ) -> Option<(&'c Token, Vec<String>)> {
+        let mut parent: Option<&Token> = None;
+        let mut items = vec![];
+        for (case, guard, _) in cases {
+            if let Expression::Call {
+                name,
+                args,
+                call_type: CallType::Enum,
+                ..
+            } = case
+            {
+                if let (Expression::Var { name, .. }, Some(Expression::Var { name: item, .. })) =
+                    (&**name, args.first())
+                {
+                    if parent.is_some_and(|p| p.lexeme != name.lexeme) {
+                        return None;
+                    }
+                    parent.get_or_insert(name);
+                    // a guarded case may not run, so it doesn't cover its item
+                    if guard.is_none() {
+                        items.push(&item.lexeme);
+                    }
+                    continue;
+                }
+            }
+            return None;
+        }
+        let parent = parent?;
+        let all = self.enums.get(&parent.lexeme)?;
+        let missing = all
+            .iter()
+            .filter(|item| !items.contains(item))
+            .cloned()
+            .collect();
+        Some((parent, missing))
+    }
+
+    fn loops(&mut self, body: &[Statement], env: &Rc<RefCell<Env>>) {
+        let encl_boundary = self.move_boundary.replace(self.scopes.len());
         self.scope_start();
         let encl_loop = self.is_crnt_loop;
         self.is_crnt_loop = true;
         self.resolve_many(body, env);
         self.is_crnt_loop = encl_loop;
         self.scope_end();
+        self.move_boundary = encl_boundary;
     }
 
     fn matchs(
         &mut self,
         cond: &Expression,
-        def_case: &FuncBody,
-        cases: &Vec<(Expression, FuncBody)>,
+        def_case: &Option<FuncBody>,
+        cases: &[(Expression, Option<Expression>, FuncBody)],
         env: &Rc<RefCell<Env>>,
     ) {
+        if let Some((parent, missing)) = self.enum_cases(cases) {
+            match def_case {
+                Some(_) if missing.is_empty() => {
+                    self.err
+                        .throw(E0x418, parent.line, parent.pos, vec![parent.lexeme.clone()]);
+                }
+                None if !missing.is_empty() => {
+                    let missing: Vec<String> = missing
+                        .iter()
+                        .map(|item| format!("{}::{}", parent.lexeme, item))
+                        .collect();
+                    self.err.throw(
+                        E0x419,
+                        parent.line,
+                        parent.pos,
+                        vec![parent.lexeme.clone(), missing.join(", ")],
+                    );
+                }
+                _ => {}
+            }
+        }
         self.expr(cond, env);
-        cases.iter().for_each(|(case, body)| {
+        let before = self.moved.clone();
+        let mut after = match def_case {
+            Some(_) => HashSet::new(),
+            None => before.clone(),
+        };
+        cases.iter().for_each(|(case, guard, body)| {
+            self.moved = before.clone();
             self.scope_start();
-            self.expr(case, env);
+            match case.string_pattern() {
+                // the name is bound to the rest of the string
+                Some((_, name, _)) => {
+                    self.declare(name);
+                    self.define(name);
+                    self.set_uncallable(name);
+                }
+                None if matches!(case, Expression::Tuple { .. }) => self.tuple_pattern(case, env),
+                None => self.expr(case, env),
+            }
+            if let Some(guard) = guard {
+                self.expr(guard, env);
+            }
             match body {
                 FuncBody::Statements(stmts) => {
                     self.resolve_many(&stmts, env);
@@ -184,19 +757,41 @@ impl Resolver {
                 }
             }
             self.scope_end();
+            after.extend(self.moved.drain());
         });
 
+        self.moved = before;
         match def_case {
-            FuncBody::Statements(stmts) => {
-                if !stmts.is_empty() {
-                    self.scope_start();
-                    self.resolve_many(stmts, env);
-                    self.scope_end();
-                }
+            Some(FuncBody::Statements(stmts)) if !stmts.is_empty() => {
+                self.scope_start();
+                self.resolve_many(stmts, env);
+                self.scope_end();
             }
-            FuncBody::Expression(expr) => {
+            Some(FuncBody::Expression(expr)) => {
                 self.expr(expr, env);
             }
+            _ => {}
+        }
+        after.extend(self.moved.drain());
+        self.moved = after;
+    }
+
+    /// the names of a tuple pattern are bound to the items at their positions,
+    /// the other items are values
+    fn tuple_pattern(&mut self, case: &Expression, env: &Rc<RefCell<Env>>) {
+        let Expression::Tuple { items, .. } = case else {
+            return;
+        };
+        for item in items {
+            match item {
+                Expression::Var { name, .. } if name.token != TokenType::Underscore => {
+                    self.declare(name);
+                    self.define(name);
+                }
+                Expression::Var { .. } => {}
+                Expression::Tuple { .. } => self.tuple_pattern(item, env),
+                _ => self.expr(item, env),
+            }
         }
     }
 
@@ -211,29 +806,39 @@ impl Resolver {
     fn ifs(
         &mut self,
         cond: &Expression,
-        body: &Vec<Statement>,
-        else_if_branches: &Vec<(Expression, Vec<Statement>)>,
+        body: &[Statement],
+        else_if_branches: &[(Expression, Vec<Statement>)],
         else_branch: &Option<Vec<Statement>>,
         env: &Rc<RefCell<Env>>,
     ) {
         self.expr(cond, env);
+        // a variable is moved after the branches if any of them moved it
+        let before = self.moved.clone();
+        let mut after = match else_branch {
+            Some(_) => HashSet::new(),
+            None => before.clone(),
+        };
         self.scope_start();
         self.resolve_many(body, env);
         self.scope_end();
         else_if_branches.iter().for_each(|(elif_pred, elif_stmt)| {
+            after.extend(std::mem::replace(&mut self.moved, before.clone()));
             self.expr(elif_pred, env);
             self.scope_start();
             self.resolve_many(elif_stmt, env);
             self.scope_end();
         });
         if let Some(branch) = else_branch {
+            after.extend(std::mem::replace(&mut self.moved, before.clone()));
             self.scope_start();
             self.resolve_many(branch, env);
             self.scope_end();
         }
+        after.extend(self.moved.drain());
+        self.moved = after;
     }
 
-    fn block(&mut self, stmts: &Vec<Statement>, env: &Rc<RefCell<Env>>) {
+    fn block(&mut self, stmts: &[Statement], env: &Rc<RefCell<Env>>) {
         self.scope_start();
         self.resolve_many(stmts, env);
         self.scope_end();
@@ -254,45 +859,320 @@ impl Resolver {
             } => {
                 let cond = *(cond.clone());
                 self.expr(&cond, env);
+                let before = self.moved.clone();
                 let body = *(body.clone());
                 self.expr(&body, env);
                 if else_branch.is_some() {
+                    let moved = std::mem::replace(&mut self.moved, before);
                     let branch = *(else_branch.as_ref().unwrap().clone());
                     self.expr(&branch, env);
+                    self.moved.extend(moved);
+                } else {
+                    self.moved.extend(before);
+                }
+            }
+            Expression::Assign {
+                name,
+                path,
+                value,
+                kind,
+                ..
+            } => {
+                path.iter().for_each(|key| self.expr(key, env));
+                self.expr(value, env);
+                // assigning an item keeps the rest of the value
+                if *kind == AssignKind::Normal && path.is_empty() {
+                    self.moved.remove(&(self.depth(name), name.lexeme.clone()));
+                } else {
+                    self.moved_read(name);
+                    self.mark_read(&name.lexeme);
                 }
             }
-            Expression::Assign { value, .. } => self.expr(value, env),
             Expression::Vec { items, .. } => {
                 items.iter().for_each(|item| self.expr(item, env));
             }
             Expression::Tuple { items, .. } => {
                 items.iter().for_each(|item| self.expr(item, env));
             }
-            Expression::Var { .. } => self.varexpr(expr),
-            Expression::Call { name, args, .. } => {
+            Expression::Var { name, .. } => {
+                self.deprecated(name);
+                self.moved_read(name);
+                self.read(name, env);
+                self.varexpr(expr)
+            }
+            Expression::Move { name, .. } => {
+                self.read(name, env);
+                let moved = (self.depth(name), name.lexeme.clone());
+                if self.moved.contains(&moved) {
+                    self.err
+                        .throw(E0x426, name.line, name.pos, vec![name.lexeme.clone()]);
+                } else if self.move_boundary.is_some_and(|depth| moved.0 <= depth) {
+                    self.err
+                        .throw(E0x427, name.line, name.pos, vec![name.lexeme.clone()]);
+                }
+                self.moved.insert(moved);
+            }
+            Expression::Call {
+                name,
+                args,
+                call_type,
+                ..
+            } => {
                 self.expr(name.as_ref(), env);
-                args.iter().for_each(|arg| self.expr(arg, env));
+                // the first argument of `Enum::A` and `module::func` is the item's name
+                let skip = usize::from(*call_type == CallType::Enum);
+                args.iter().skip(skip).for_each(|arg| self.expr(arg, env));
+                if let (CallType::Func, Expression::Var { name, .. }) = (call_type, name.as_ref()) {
+                    self.shadowed(name);
+                    self.labels(name, args);
+                    self.generic_args(name, args);
+                }
             }
+            Expression::Labeled { value, .. } => self.expr(value, env),
             Expression::Func { body, params, .. } => self.callback(body, params, env),
             Expression::Await { expr, .. } => self.expr(expr, env),
+            Expression::Method { object, args, .. } => {
+                self.expr(object, env);
+                args.iter().for_each(|arg| self.expr(arg, env));
+            }
+            Expression::Quote { body, .. } => {
+                let encl_quoted = std::mem::replace(&mut self.is_quoted, true);
+                match body {
+                    // quoted statements run in their own block
+                    FuncBody::Statements(stmts) => self.block(stmts, env),
+                    FuncBody::Expression(expr) => self.expr(expr, env),
+                }
+                self.is_quoted = encl_quoted;
+            }
+            Expression::Comprehension {
+                expr,
+                var,
+                iter,
+                filter,
+                ..
+            } => {
+                self.expr(iter, env);
+                let encl_boundary = self.move_boundary.replace(self.scopes.len());
+                self.scope_start();
+                self.declare(var);
+                self.define(var);
+                if let Some(filter) = filter {
+                    self.expr(filter, env);
+                }
+                self.expr(expr, env);
+                self.scope_end();
+                self.move_boundary = encl_boundary;
+            }
             Expression::Unary { left, .. } => self.expr(left, env),
-            Expression::Binary { left, right, .. } => {
+            Expression::Binary {
+                left,
+                operator,
+                right,
+                ..
+            } => {
                 self.expr(left, env);
                 self.expr(right, env);
+                if let Err((left, right)) =
+                    binary_type(&operator.token, operand_type(left), operand_type(right))
+                {
+                    self.err.throw(
+                        E0x435,
+                        operator.line,
+                        operator.pos,
+                        vec![operator.lexeme.clone(), left, right],
+                    );
+                }
+            }
+            Expression::Range { start, end, .. } => {
+                self.expr(start, env);
+                self.expr(end, env);
             }
             Expression::Grouping { expression, .. } => self.expr(expression, env),
+            Expression::Value {
+                value: LiteralType::String(s),
+                ..
+            } => self.interpolated(s),
             _ => {}
         }
     }
 
+    /// locals shadow functions of the same name, so `name(...)` calls the local,
+    /// a local which can't be called is an error, `let twice = 5; twice(3);`
+    fn shadowed(&self, name: &Token) {
+        if !self.funcs.contains_key(&name.lexeme) {
+            return;
+        }
+        let scope = self
+            .scopes
+            .iter()
+            .rposition(|scope| scope.contains_key(&name.lexeme));
+        if let Some(scope) = scope {
+            if self.uncallable[scope].contains(&name.lexeme) {
+                self.err
+                    .throw(E0x425, name.line, name.pos, vec![name.lexeme.clone()]);
+            }
+        }
+    }
+
+    /// checks labeled arguments against the parameters of the called function,
+    /// functions shadowed by a local are checked when called
+    fn labels(&self, func: &Token, args: &[Expression]) {
+        let params = match self.funcs.get(&func.lexeme) {
+            Some(params) if !self.scopes.iter().any(|s| s.contains_key(&func.lexeme)) => params,
+            _ => return,
+        };
+        // positional arguments fill the first parameters
+        let positional = args
+            .iter()
+            .filter(|arg| !matches!(arg, Expression::Labeled { .. }))
+            .count();
+        let (positional_only, keyword_only) =
+            self.markers.get(&func.lexeme).copied().unwrap_or((0, None));
+        if let Some(param) = keyword_only
+            .filter(|&start| positional > start)
+            .and_then(|start| params.get(start))
+        {
+            self.err.throw(
+                E0x431,
+                func.line,
+                func.pos,
+                vec![param.clone(), func.lexeme.clone()],
+            );
+        }
+        let mut given: Vec<&String> = params.iter().take(positional).collect();
+        for arg in args {
+            if let Expression::Labeled { label, .. } = arg {
+                if params[..positional_only].contains(&label.lexeme) {
+                    self.err.throw(
+                        E0x430,
+                        label.line,
+                        label.pos,
+                        vec![label.lexeme.clone(), func.lexeme.clone()],
+                    );
+                } else if !params.contains(&label.lexeme) {
+                    self.err.throw(
+                        E0x422,
+                        label.line,
+                        label.pos,
+                        vec![func.lexeme.clone(), label.lexeme.clone()],
+                    );
+                } else if given.contains(&&label.lexeme) {
+                    self.err.throw(
+                        E0x423,
+                        label.line,
+                        label.pos,
+                        vec![label.lexeme.clone(), func.lexeme.clone()],
+                    );
+                }
+                given.push(&label.lexeme);
+            }
+        }
+    }
+
+    /// checks the bounds of type parameters against the known ones
+    fn bounds(&self, generics: &[(Token, Option<Token>)]) {
+        for bound in generics.iter().filter_map(|(_, bound)| bound.as_ref()) {
+            if !BOUNDS.contains(&bound.lexeme.as_str()) {
+                self.err.throw(
+                    E0x428,
+                    bound.line,
+                    bound.pos,
+                    vec![bound.lexeme.clone(), BOUNDS.join(", ")],
+                );
+            }
+        }
+    }
+
+    /// checks literal arguments of type parameters against their bounds and each other,
+    /// other arguments are checked when the function is called
+    fn generic_args(&self, func: &Token, args: &[Expression]) {
+        let (types, params) = match (self.generic.get(&func.lexeme), self.funcs.get(&func.lexeme)) {
+            (Some(types), Some(params))
+                if !self.scopes.iter().any(|s| s.contains_key(&func.lexeme)) =>
+            {
+                (types, params)
+            }
+            _ => return,
+        };
+        // positional arguments fill the first parameters
+        let mut positional = 0;
+        // the types of the first literal arguments of each type parameter
+        let mut literals: HashMap<&str, String> = HashMap::new();
+        for arg in args {
+            let (i, arg) = match arg {
+                Expression::Labeled { label, value, .. } => {
+                    match params.iter().position(|p| *p == label.lexeme) {
+                        Some(i) => (i, value.as_ref()),
+                        None => continue,
+                    }
+                }
+                arg => {
+                    positional += 1;
+                    (positional - 1, arg)
+                }
+            };
+            if let (Some((generic, bound)), Expression::Value { value, .. }) =
+                (types.get(i).and_then(bound_of), arg)
+            {
+                if !satisfies(&bound.lexeme, value) {
+                    self.err.throw(
+                        E0x429,
+                        func.line,
+                        func.pos,
+                        vec![
+                            value.type_name(),
+                            generic.lexeme.clone(),
+                            bound.lexeme.clone(),
+                        ],
+                    );
+                }
+            }
+            if let (Some(generic), Expression::Value { value, .. }) =
+                (types.get(i).and_then(generic_of), arg)
+            {
+                let first = literals
+                    .entry(&generic.lexeme)
+                    .or_insert_with(|| value.type_name());
+                if *first != value.type_name() {
+                    self.err.throw(
+                        E0x442,
+                        func.line,
+                        func.pos,
+                        vec![generic.lexeme.clone(), first.clone(), value.type_name()],
+                    );
+                }
+            }
+        }
+    }
+
+    /// warns about uses of `@deprecated` records in type annotations
+    fn types<'t>(&self, types: impl IntoIterator<Item = &'t Token>) {
+        types.into_iter().for_each(|t| self.deprecated(t));
+    }
+
+    /// warns about a use of a `@deprecated` function or record,
+    /// unless a local shadows it
+    fn deprecated(&self, name: &Token) {
+        if let Some(message) = self.deprecated.get(&name.lexeme) {
+            if !self.scopes.iter().any(|s| s.contains_key(&name.lexeme)) {
+                self.err.warn(
+                    E0x424,
+                    name.line,
+                    name.pos,
+                    vec![name.lexeme.clone(), message.clone()],
+                );
+            }
+        }
+    }
+
     fn callback(&mut self, body: &FuncBody, params: &[(Token, Token)], env: &Rc<RefCell<Env>>) {
+        self.types(params.iter().map(|(_, t)| t));
         let encl_func = self.is_crnt_fnc;
         self.is_crnt_fnc = true;
+        let encl_init = std::mem::take(&mut self.crnt_init);
+        let encl_boundary = self.move_boundary.replace(self.scopes.len());
         self.scope_start();
-        params.iter().for_each(|(name, _)| {
-            self.declare(name);
-            self.define(name);
-        });
+        self.params(params);
         match body {
             FuncBody::Statements(body) => {
                 self.resolve_many(body, env);
@@ -309,18 +1189,35 @@ impl Resolver {
         }
 
         self.scope_end();
+        self.move_boundary = encl_boundary;
         self.is_crnt_fnc = encl_func;
+        self.crnt_init = encl_init;
     }
 
     fn varexpr(&mut self, expr: &Expression) {
         if let Expression::Var { name, .. } = expr {
             if let Some(false) = self.scopes.last().and_then(|scope| scope.get(&name.lexeme)) {
-                self.err.throw(
-                    E0x304,
-                    name.line,
-                    name.pos,
-                    vec!["a local variable".to_string()],
-                );
+                self.err
+                    .throw(E0x420, name.line, name.pos, vec![name.lexeme.clone()]);
+            }
+            // functions can read top level variables declared after them,
+            // they are called later
+            let is_local = self
+                .scopes
+                .iter()
+                .any(|scope| scope.contains_key(&name.lexeme));
+            if !self.is_crnt_fnc
+                && !self.is_quoted
+                && !is_local
+                && self.globals.get(&name.lexeme) == Some(&false)
+            {
+                let code = if self.crnt_init.contains(&name.lexeme) {
+                    E0x420
+                } else {
+                    E0x421
+                };
+                self.err
+                    .throw(code, name.line, name.pos, vec![name.lexeme.clone()]);
             }
         } else if let Expression::Call { name, .. } = expr {
             if let Expression::Var { name, .. } = name.as_ref() {
@@ -335,13 +1232,112 @@ impl Resolver {
         }
     }
 
+    /// reading a moved variable is an error until it's assigned again
+    fn moved_read(&self, name: &Token) {
+        if self
+            .moved
+            .contains(&(self.depth(name), name.lexeme.clone()))
+        {
+            self.err
+                .throw(E0x426, name.line, name.pos, vec![name.lexeme.clone()]);
+        }
+    }
+
+    /// marks the binding of a read name, a name without one is an error,
+    /// unless it's declared elsewhere or already defined, like builtins
+    fn read(&mut self, name: &Token, env: &Rc<RefCell<Env>>) {
+        // chained calls, `f(1)(2)`, are named by the `)` of the first one
+        if name.token != TokenType::Ident || self.mark_read(&name.lexeme) {
+            return;
+        }
+        let is_known = self.is_open
+            || self.is_quoted
+            || self.globals.contains_key(&name.lexeme)
+            || self.funcs.contains_key(&name.lexeme)
+            || self.enums.contains_key(&name.lexeme)
+            || self.declared.contains(&name.lexeme)
+            || env.borrow().get_int(&name.lexeme, None).is_some();
+        if !is_known {
+            self.err
+                .throw(E0x304, name.line, name.pos, vec![name.lexeme.clone()]);
+        }
+    }
+
+    /// marks the innermost binding of the name as read, returns false for
+    /// names which aren't local
+    fn mark_read(&mut self, name: &str) -> bool {
+        match self
+            .scopes
+            .iter()
+            .rposition(|scope| scope.contains_key(name))
+        {
+            Some(i) => {
+                self.unread[i].remove(name);
+                true
+            }
+            None => {
+                self.global_reads.insert(name.to_string());
+                false
+            }
+        }
+    }
+
+    /// names in interpolated expressions are read when the string is,
+    /// the words which aren't variables are ignored
+    fn interpolated(&mut self, s: &str) {
+        for part in interpolation(s) {
+            if let Part::Expr(src) = part {
+                src.split(|c: char| !c.is_alphanumeric() && c != '_')
+                    .filter(|word| word.starts_with(|c: char| c.is_alphabetic() || c == '_'))
+                    .for_each(|word| {
+                        self.mark_read(word);
+                    });
+            }
+        }
+    }
+
+    /// warns about a variable which is never read, generated ones start with `$`
+    /// and quotes may read the ones named like the names in them
+    fn unread(&self, name: &Token) {
+        if !name.lexeme.starts_with('$') && !self.quoted.contains(&name.lexeme) {
+            self.err
+                .warn(E0x441, name.line, name.pos, vec![name.lexeme.clone()]);
+        }
+    }
+
+    /// depth of the scope declaring the name, 0 for top level variables
+    fn depth(&self, name: &Token) -> usize {
+        self.scopes
+            .iter()
+            .rposition(|scope| scope.contains_key(&name.lexeme))
+            .map_or(0, |i| i + 1)
+    }
+
     fn declare(&mut self, name: &Token) {
-        if let Some(scope) = self.scopes.last_mut() {
-            if scope.contains_key(&name.lexeme) {
-                self.err
-                    .throw(E0x305, name.line, name.pos, vec![name.lexeme.clone()]);
+        let is_declared = match self.scopes.last_mut() {
+            Some(scope) => scope.insert(name.lexeme.clone(), false).is_some(),
+            // top level variables are marked once their declaration is resolved
+            None => self.globals.get(&name.lexeme) == Some(&true),
+        };
+        if is_declared {
+            self.err
+                .throw(E0x305, name.line, name.pos, vec![name.lexeme.clone()]);
+        }
+    }
+
+    fn params(&mut self, params: &[(Token, Token)]) {
+        params.iter().for_each(|(name, param_type)| {
+            self.declare(name);
+            self.define(name);
+            if is_uncallable(param_type, None) {
+                self.set_uncallable(name);
             }
-            scope.insert(name.lexeme.clone(), false);
+        });
+    }
+
+    fn set_uncallable(&mut self, name: &Token) {
+        if let Some(scope) = self.uncallable.last_mut() {
+            scope.insert(name.lexeme.clone());
         }
     }
 
@@ -352,6 +1348,10 @@ impl Resolver {
     }
 
     fn resolve_local(&mut self, name: &Token, id: usize) {
+        // quotes don't run where they're declared
+        if self.is_quoted {
+            return;
+        }
         for (i, scope) in self.scopes.iter().rev().enumerate() {
             if scope.contains_key(&name.lexeme) {
                 self.locals.insert(id, i);
@@ -366,11 +1366,290 @@ impl Resolver {
 
     fn scope_start(&mut self) {
         self.scopes.push(HashMap::new());
+        self.uncallable.push(HashSet::new());
+        self.unread.push(HashMap::new());
     }
 
     fn scope_end(&mut self) {
+        self.uncallable.pop();
+        if let Some(unread) = self.unread.pop() {
+            let mut unread: Vec<Token> = unread.into_values().collect();
+            unread.sort_by_key(|name| (name.line, name.pos));
+            unread.iter().for_each(|name| self.unread(name));
+        }
         if self.scopes.pop().is_none() {
             self.err.throw(E0x306, 0, (0, 0), vec![]);
         }
+        let depth = self.scopes.len();
+        self.moved.retain(|(d, _)| *d <= depth);
+    }
+}
+
+/// if the declared type or the value can't be a function
+fn is_uncallable(value_type: &Token, value: Option<&Expression>) -> bool {
+    use TokenType::*;
+    matches!(
+        value_type.token,
+        NumIdent | StrIdent | CharIdent | BoolIdent | ArrayIdent | VecT | Tuple
+    ) || matches!(
+        value,
+        Some(
+            Expression::Value { .. }
+                | Expression::Vec { .. }
+                | Expression::Tuple { .. }
+                | Expression::Record { .. }
+                | Expression::Range { .. }
+                | Expression::Comprehension { .. }
+                | Expression::Binary { .. }
+                | Expression::Unary { .. }
+        )
+    )
+}
+
+/// operators with the operand types they work on and the result type, `==`, `!=`,
+/// `||` and `&&` work on any values, `in` on anything in a vector or a tuple
+const OPERATORS: [(&[TokenType], &str, &str, &str); 8] = {
+    use TokenType::*;
+    [
+        (
+            &[Plus, Min, Mul, Div, Prcnt, Sqr],
+            "number",
+            "number",
+            "number",
+        ),
+        (&[Plus, Min, Mul, Div], "number", "vector", "vector"),
+        (&[Plus], "string", "string", "string"),
+        (&[Gr, GrOrEq, Ls, LsOrEq], "number", "number", "bool"),
+        (&[Gr, GrOrEq, Ls, LsOrEq], "string", "string", "bool"),
+        (&[Gr, GrOrEq, Ls, LsOrEq], "char", "char", "bool"),
+        (&[In], "string", "string", "bool"),
+        (&[In], "char", "string", "bool"),
+    ]
+};
+
+/// the type of an operand when it's known before running, `None` for `any`
+fn operand_type(expr: &Expression) -> Option<String> {
+    match expr {
+        Expression::Value { value, .. }
+            if value.is_primitive() || matches!(value, LiteralType::Vec(_)) =>
+        {
+            Some(value.type_name())
+        }
+        Expression::Vec { .. } => Some("vector".to_string()),
+        Expression::Tuple { .. } => Some("tuple".to_string()),
+        Expression::Grouping { expression, .. } => operand_type(expression),
+        Expression::Unary { operator, .. } if matches!(operator.token, TokenType::Bang) => {
+            Some("bool".to_string())
+        }
+        Expression::Binary {
+            left,
+            operator,
+            right,
+            ..
+        } => binary_type(&operator.token, operand_type(left), operand_type(right))
+            .ok()
+            .flatten(),
+        _ => None,
+    }
+}
+
+/// the result type of a binary operation, `None` when it's only known when run,
+/// the operand types when the operator doesn't work on them
+fn binary_type(
+    operator: &TokenType,
+    left: Option<String>,
+    right: Option<String>,
+) -> Result<Option<String>, (String, String)> {
+    use TokenType::*;
+    let bool = || Ok(Some("bool".to_string()));
+    match (operator, left, right) {
+        (Eq | BangEq, ..) => bool(),
+        (Or | DblAnd, left, right) => Ok(left.filter(|left| Some(left) == right.as_ref())),
+        (In, _, Some(right)) if right == "vector" || right == "tuple" => bool(),
+        (operator, Some(left), Some(right)) => OPERATORS
+            .iter()
+            .find(|(operators, l, r, _)| operators.contains(operator) && *l == left && *r == right)
+            .map(|(.., result)| Some(result.to_string()))
+            .ok_or((left, right)),
+        (Gr | GrOrEq | Ls | LsOrEq | In, ..) => bool(),
+        _ => Ok(None),
+    }
+}
+
+/// the first literal not matching a primitive or vector type, with the index path
+/// to it, `[1][0]`, the expected type and the found one, other values are checked when run
+fn mismatch(typ: &Token, expr: &Expression) -> Option<(String, String, String)> {
+    match expr {
+        Expression::Value { value, .. } => literal_mismatch(typ, value),
+        Expression::Vec { items, .. } => match item_type(typ) {
+            Some(item) => items.iter().enumerate().find_map(|(i, expr)| {
+                mismatch(item, expr).map(|(path, e, f)| (format!("[{i}]{path}"), e, f))
+            }),
+            None if is_primitive(typ) => {
+                Some((String::new(), typ.lexeme.clone(), "vector".to_string()))
+            }
+            None => None,
+        },
+        _ => None,
+    }
+}
+
+/// parameters and bodies of the top level functions, and if they're declared `@io`
+type FuncBodies<'a> = HashMap<&'a String, (&'a Vec<(Token, Token)>, &'a FuncBody, bool)>;
+
+/// the top level functions and mutable variables
+fn bodies(stmts: &[Statement]) -> (FuncBodies<'_>, HashSet<&String>) {
+    let mut funcs = HashMap::new();
+    let mut mutable = HashSet::new();
+    for stmt in stmts {
+        match stmt {
+            Statement::Func {
+                name,
+                params,
+                body,
+                is_io,
+                ..
+            } => {
+                funcs.insert(&name.lexeme, (params, body, *is_io));
+            }
+            Statement::Var {
+                names,
+                is_mut: true,
+                ..
+            } => mutable.extend(names.iter().map(|name| &name.lexeme)),
+            _ => {}
+        }
     }
+    (funcs, mutable)
+}
+
+/// builtins with side effects, which `@memo` functions can't call
+const EFFECTFUL: [&str; 11] = [
+    "print",
+    "eprint",
+    "warn",
+    "panic",
+    "exit",
+    "read_num",
+    "read_str",
+    "read_char",
+    "read_bool",
+    "eval",
+    "assert",
+];
+
+/// why the result of a top level function may depend on more than its arguments,
+/// following the functions it calls, which are taken as pure while they're checked,
+/// with `counts` outer variables may be changed by statements, like `calls += 1;`,
+/// as long as they aren't read
+fn impurity(
+    name: &String,
+    funcs: &FuncBodies,
+    mutable: &HashSet<&String>,
+    counts: bool,
+    checked: &mut HashSet<String>,
+) -> Option<String> {
+    let (params, body, _) = funcs.get(name)?;
+    checked.insert(name.clone());
+    // names the function declares itself
+    let mut locals: HashSet<&String> = params.iter().map(|(p, _)| &p.lexeme).collect();
+    walk_body(body, &mut |node| match node {
+        Node::Stmt(Statement::Var { names, .. }) => {
+            locals.extend(names.iter().map(|name| &name.lexeme))
+        }
+        Node::Stmt(Statement::For {
+            iterator, index, ..
+        }) => {
+            locals.insert(&iterator.lexeme);
+            locals.extend(index.iter().map(|index| &index.lexeme));
+        }
+        Node::Stmt(Statement::Func { name, params, .. }) => {
+            locals.insert(&name.lexeme);
+            locals.extend(params.iter().map(|(p, _)| &p.lexeme));
+        }
+        Node::Expr(Expression::Func { params, .. }) => {
+            locals.extend(params.iter().map(|(p, _)| &p.lexeme))
+        }
+        Node::Expr(Expression::Comprehension { var, .. }) => {
+            locals.insert(&var.lexeme);
+        }
+        _ => {}
+    });
+    let bound: Vec<String> = {
+        let mut bound = vec![];
+        walk_body(body, &mut |node| {
+            if let Node::Stmt(Statement::Match { cases, .. }) = node {
+                bound.extend(bindings(cases));
+            }
+        });
+        bound
+    };
+    let is_local = |name: &String| locals.contains(name) || bound.contains(name);
+    let mut counters: Vec<&Expression> = vec![];
+    if counts {
+        walk_body(body, &mut |node| {
+            if let Node::Stmt(Statement::Expression {
+                expr: expr @ Expression::Assign { .. },
+            }) = node
+            {
+                counters.push(expr);
+            }
+        });
+    }
+
+    let mut reason = None;
+    walk_body(body, &mut |node| {
+        if reason.is_some() {
+            return;
+        }
+        reason = match node {
+            Node::Stmt(Statement::Sh { .. }) => Some("it runs shell commands".to_string()),
+            Node::Stmt(Statement::Use { .. } | Statement::Mod { .. }) => {
+                Some("it imports modules".to_string())
+            }
+            Node::Expr(expr @ Expression::Assign { .. })
+                if counters.iter().any(|counter| std::ptr::eq(*counter, expr)) =>
+            {
+                None
+            }
+            Node::Expr(Expression::Assign { name, .. } | Expression::Move { name, .. })
+                if !is_local(&name.lexeme) =>
+            {
+                Some(format!("it changes '{}'", name.lexeme))
+            }
+            Node::Expr(Expression::Var { name, .. })
+                if !is_local(&name.lexeme) && mutable.contains(&name.lexeme) =>
+            {
+                Some(format!("it reads the mutable variable '{}'", name.lexeme))
+            }
+            Node::Expr(Expression::Await { .. }) => Some("it awaits".to_string()),
+            Node::Expr(Expression::Call {
+                name,
+                call_type: CallType::Func,
+                ..
+            }) => match name.as_ref() {
+                Expression::Var { name, .. } if is_local(&name.lexeme) => None,
+                Expression::Var { name, .. } if EFFECTFUL.contains(&name.lexeme.as_str()) => {
+                    Some(format!("it calls '{}'", name.lexeme))
+                }
+                Expression::Var { name, .. }
+                    if funcs.get(&name.lexeme).is_some_and(|(_, _, is_io)| *is_io) =>
+                {
+                    Some(format!("it calls '{}', which is '@io'", name.lexeme))
+                }
+                Expression::Var { name, .. } if !checked.contains(&name.lexeme) => {
+                    impurity(&name.lexeme, funcs, mutable, counts, checked).map(|reason| {
+                        format!(
+                            "it calls '{}', which {}",
+                            name.lexeme,
+                            reason.trim_start_matches("it ")
+                        )
+                    })
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+    });
+    reason
 }
@@ -0,0 +1,61 @@
+use crate::{
+    ast::{LiteralType, Token, Wrapper},
+    errors::raw,
+    std::{func, StdFunc},
+};
+use std::{process::exit, rc::Rc};
+
+impl StdFunc {
+    pub fn load_core_cmp(&mut self) {
+        self.load_between(None);
+    }
+
+    /// between(x, lo, hi) - checks if lo <= x <= hi, for numbers and chars
+    pub fn load_between(&mut self, name: Option<Token>) {
+        let name = match name {
+            Some(n) => n.lexeme.clone(),
+            None => "between".to_string(),
+        };
+        func(
+            name.as_str(),
+            3,
+            &mut self.env,
+            Rc::new(Wrapper(Box::new(|args: &[Option<LiteralType>]| {
+                if args.len() != 3 {
+                    raw("expected 3 arguments");
+                    exit(1)
+                }
+                let args: Vec<LiteralType> = args
+                    .iter()
+                    .map(|arg| arg.clone().unwrap_or(LiteralType::Void))
+                    .collect();
+                let (x, lo, hi) = match (&args[0], &args[1], &args[2]) {
+                    (LiteralType::Number(x), LiteralType::Number(lo), LiteralType::Number(hi)) => {
+                        (*x, *lo, *hi)
+                    }
+                    (LiteralType::Char(x), LiteralType::Char(lo), LiteralType::Char(hi)) => {
+                        (*x as u32 as f32, *lo as u32 as f32, *hi as u32 as f32)
+                    }
+                    _ => {
+                        raw(format!(
+                            "between() expects numbers or chars, got {}, {}, {}",
+                            args[0].type_name(),
+                            args[1].type_name(),
+                            args[2].type_name()
+                        )
+                        .as_str());
+                        exit(1)
+                    }
+                };
+                if lo > hi {
+                    raw(
+                        format!("between() bounds are inverted: {} > {}", args[1], args[2])
+                            .as_str(),
+                    );
+                    exit(1)
+                }
+                LiteralType::Boolean(lo <= x && x <= hi)
+            }))),
+        );
+    }
+}
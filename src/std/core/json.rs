@@ -0,0 +1,289 @@
+use crate::{
+    ast::{LiteralType, Token, Wrapper},
+    errors::raw,
+    interpreter::{env::Env, expr::Expression},
+    std::{func, StdFunc},
+};
+use std::{cell::RefCell, iter::Peekable, rc::Rc, str::Chars};
+
+/// how deep arrays and objects can nest, deeper ones would overflow the stack
+const MAX_DEPTH: usize = 256;
+
+impl StdFunc {
+    pub fn load_core_json(&mut self) {
+        self.load_to_json(None);
+        self.load_from_json(None);
+    }
+
+    /// to_json(value), vectors and tuples become arrays and records objects, in field order
+    pub fn load_to_json(&mut self, name: Option<Token>) {
+        let name = match name {
+            Some(n) => n.lexeme.clone(),
+            None => "to_json".to_string(),
+        };
+        // record fields are evaluated when serialized
+        let env = Rc::clone(&self.env);
+        func(
+            name.as_str(),
+            1,
+            &mut self.env,
+            Rc::new(Wrapper(Box::new(move |args: &[Option<LiteralType>]| {
+                if args.len() != 1 {
+                    raw("expected an argument");
+                }
+                match to_json(&args[0].clone().unwrap_or(LiteralType::Void), &env) {
+                    Ok(json) => LiteralType::String(json),
+                    Err(msg) => {
+                        raw(format!("to_json() {msg}").as_str());
+                        LiteralType::Null
+                    }
+                }
+            }))),
+        );
+    }
+
+    /// from_json(string), arrays become vectors and objects records
+    pub fn load_from_json(&mut self, name: Option<Token>) {
+        let name = match name {
+            Some(n) => n.lexeme.clone(),
+            None => "from_json".to_string(),
+        };
+        func(
+            name.as_str(),
+            1,
+            &mut self.env,
+            Rc::new(Wrapper(Box::new(|args: &[Option<LiteralType>]| {
+                if args.len() != 1 {
+                    raw("expected an argument");
+                }
+                match args[0].clone().unwrap_or(LiteralType::Void) {
+                    LiteralType::String(s) => {
+                        let mut chars = s.chars().peekable();
+                        let value = from_json(&mut chars, 0).and_then(|value| {
+                            skip_whitespace(&mut chars);
+                            match chars.next() {
+                                Some(c) => Err(format!("unexpected '{c}' after the value")),
+                                None => Ok(value),
+                            }
+                        });
+                        match value {
+                            Ok(value) => value,
+                            Err(msg) => {
+                                raw(format!("from_json() got invalid JSON, {msg}").as_str());
+                                LiteralType::Null
+                            }
+                        }
+                    }
+                    v => {
+                        raw(
+                            format!("from_json() expects a string, got {}", v.type_name()).as_str(),
+                        );
+                        LiteralType::Null
+                    }
+                }
+            }))),
+        );
+    }
+}
+
+fn to_json(value: &LiteralType, env: &Rc<RefCell<Env>>) -> Result<String, String> {
+    match value {
+        LiteralType::Number(n) if n.is_finite() => Ok(n.to_string()),
+        LiteralType::Number(n) => Err(format!("can't serialize {n}")),
+        LiteralType::String(s) => Ok(quote(s)),
+        LiteralType::Char(c) => Ok(quote(&c.to_string())),
+        LiteralType::Boolean(b) => Ok(b.to_string()),
+        LiteralType::Null => Ok("null".to_string()),
+        LiteralType::Bytes(bytes) => Ok(format!(
+            "[{}]",
+            bytes
+                .iter()
+                .map(|b| b.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        )),
+        LiteralType::Vec(items) | LiteralType::Tuple(items) => {
+            let items = items
+                .iter()
+                .map(|item| to_json(item, env))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(format!("[{}]", items.join(",")))
+        }
+        LiteralType::Record(fields) => {
+            let fields = fields
+                .iter()
+                .map(|(name, value)| {
+                    to_json(&value.eval(Rc::clone(env)), env)
+                        .map(|value| format!("{}:{}", quote(name), value))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(format!("{{{}}}", fields.join(",")))
+        }
+        v => Err(format!("can't serialize a {}", v.type_name())),
+    }
+}
+
+/// a JSON string literal, escaping quotes, backslashes and control chars
+fn quote(s: &str) -> String {
+    let mut json = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn from_json(chars: &mut Peekable<Chars>, depth: usize) -> Result<LiteralType, String> {
+    skip_whitespace(chars);
+    if depth >= MAX_DEPTH && matches!(chars.peek(), Some('[' | '{')) {
+        return Err(format!("arrays and objects nest deeper than {MAX_DEPTH}"));
+    }
+    match chars.peek() {
+        Some('"') => string(chars).map(LiteralType::String),
+        Some('[') => {
+            chars.next();
+            let mut items = vec![];
+            skip_whitespace(chars);
+            if chars.next_if_eq(&']').is_none() {
+                loop {
+                    items.push(from_json(chars, depth + 1)?);
+                    skip_whitespace(chars);
+                    match chars.next() {
+                        Some(',') => {}
+                        Some(']') => break,
+                        c => return Err(unexpected(c, "',' or ']'")),
+                    }
+                }
+            }
+            Ok(LiteralType::Vec(items))
+        }
+        Some('{') => {
+            chars.next();
+            let mut fields = vec![];
+            skip_whitespace(chars);
+            if chars.next_if_eq(&'}').is_none() {
+                loop {
+                    skip_whitespace(chars);
+                    if chars.peek() != Some(&'"') {
+                        return Err(unexpected(chars.next(), "a key"));
+                    }
+                    let key = string(chars)?;
+                    skip_whitespace(chars);
+                    if chars.next_if_eq(&':').is_none() {
+                        return Err(unexpected(chars.next(), "':'"));
+                    }
+                    let value = match from_json(chars, depth + 1)? {
+                        // string fields are interpolated when read, their braces are escaped
+                        LiteralType::String(s) => {
+                            LiteralType::String(s.replace('{', "{{").replace('}', "}}"))
+                        }
+                        value => value,
+                    };
+                    fields.push((key, Expression::Value { id: 0, value }));
+                    skip_whitespace(chars);
+                    match chars.next() {
+                        Some(',') => {}
+                        Some('}') => break,
+                        c => return Err(unexpected(c, "',' or '}'")),
+                    }
+                }
+            }
+            Ok(LiteralType::Record(fields))
+        }
+        Some(c) if c.is_ascii_alphabetic() => {
+            let mut word = String::new();
+            while let Some(c) = chars.next_if(|c| c.is_ascii_alphabetic()) {
+                word.push(c);
+            }
+            match word.as_str() {
+                "true" => Ok(LiteralType::Boolean(true)),
+                "false" => Ok(LiteralType::Boolean(false)),
+                "null" => Ok(LiteralType::Null),
+                _ => Err(format!("unexpected '{word}'")),
+            }
+        }
+        Some(c) if *c == '-' || c.is_ascii_digit() => {
+            let mut number = String::new();
+            while let Some(c) =
+                chars.next_if(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+            {
+                number.push(c);
+            }
+            number
+                .parse()
+                .map(LiteralType::Number)
+                .map_err(|_| format!("'{number}' isn't a number"))
+        }
+        c => Err(unexpected(c.copied(), "a value")),
+    }
+}
+
+/// a JSON string literal, the opening quote is next
+fn string(chars: &mut Peekable<Chars>) -> Result<String, String> {
+    chars.next();
+    let mut s = String::new();
+    loop {
+        match chars.next() {
+            Some('"') => return Ok(s),
+            Some('\\') => match chars.next() {
+                Some('n') => s.push('\n'),
+                Some('r') => s.push('\r'),
+                Some('t') => s.push('\t'),
+                Some('b') => s.push('\u{8}'),
+                Some('f') => s.push('\u{c}'),
+                Some('u') => {
+                    let mut code = hex(chars)?;
+                    // chars above the basic plane are escaped as a pair of surrogates
+                    if (0xd800..0xdc00).contains(&code)
+                        && chars.next_if_eq(&'\\').is_some()
+                        && chars.next_if_eq(&'u').is_some()
+                    {
+                        let low = hex(chars)?;
+                        if !(0xdc00..0xe000).contains(&low) {
+                            return Err(format!(
+                                "'\\u{code:04x}' isn't followed by a low surrogate"
+                            ));
+                        }
+                        code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+                    }
+                    match char::from_u32(code) {
+                        Some(c) => s.push(c),
+                        None => return Err(format!("'\\u{code:04x}' isn't a char")),
+                    }
+                }
+                Some(c @ ('"' | '\\' | '/')) => s.push(c),
+                c => return Err(unexpected(c, "an escape")),
+            },
+            Some(c) => s.push(c),
+            None => return Err("unterminated string".to_string()),
+        }
+    }
+}
+
+/// the four hex digits of a `\u` escape
+fn hex(chars: &mut Peekable<Chars>) -> Result<u32, String> {
+    let hex: String = (0..4).filter_map(|_| chars.next()).collect();
+    match u32::from_str_radix(&hex, 16) {
+        Ok(code) if hex.len() == 4 && hex.chars().all(|c| c.is_ascii_hexdigit()) => Ok(code),
+        _ => Err(format!("'\\u{hex}' isn't a char")),
+    }
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
+
+fn unexpected(c: Option<char>, expected: &str) -> String {
+    match c {
+        Some(c) => format!("expected {expected}, got '{c}'"),
+        None => format!("expected {expected}, got the end"),
+    }
+}
@@ -0,0 +1,192 @@
+use crate::{
+    ast::{FuncBody, LiteralType, Statement, Token, Wrapper},
+    errors::raw,
+    interpreter::{caller, expr::Expression, run_quote},
+    std::{func, StdFunc},
+};
+use std::{process::exit, rc::Rc};
+
+impl StdFunc {
+    pub fn load_core_meta(&mut self) {
+        self.load_eval(None);
+        self.load_reflect(None);
+    }
+
+    /// eval(quote) - executes the quoted code where it's called
+    pub fn load_eval(&mut self, name: Option<Token>) {
+        let name = match name {
+            Some(n) => n.lexeme.clone(),
+            None => "eval".to_string(),
+        };
+        let env = Rc::clone(&self.env);
+        func(
+            name.as_str(),
+            1,
+            &mut self.env,
+            Rc::new(Wrapper(Box::new(move |args: &[Option<LiteralType>]| {
+                if args.len() != 1 {
+                    raw("expected an argument");
+                }
+                match args[0].clone().unwrap_or(LiteralType::Void) {
+                    LiteralType::Quote(quote) => {
+                        run_quote(quote, caller().unwrap_or_else(|| Rc::clone(&env)))
+                    }
+                    v => {
+                        raw(format!("eval() expects a quote, got {}", v.type_name()).as_str());
+                        exit(1)
+                    }
+                }
+            }))),
+        );
+    }
+
+    /// reflect(quote) - the quoted code as a vector of records, one for each
+    /// statement, or for the quoted expression, with the kind of node in `kind`
+    pub fn load_reflect(&mut self, name: Option<Token>) {
+        let name = match name {
+            Some(n) => n.lexeme.clone(),
+            None => "reflect".to_string(),
+        };
+        func(
+            name.as_str(),
+            1,
+            &mut self.env,
+            Rc::new(Wrapper(Box::new(|args: &[Option<LiteralType>]| {
+                if args.len() != 1 {
+                    raw("expected an argument");
+                }
+                match args[0].clone().unwrap_or(LiteralType::Void) {
+                    LiteralType::Quote(quote) => LiteralType::Vec(match &quote.body {
+                        FuncBody::Expression(expr) => vec![reflect_expr(expr)],
+                        FuncBody::Statements(stmts) => stmts.iter().map(reflect_stmt).collect(),
+                    }),
+                    v => {
+                        raw(format!("reflect() expects a quote, got {}", v.type_name()).as_str());
+                        exit(1)
+                    }
+                }
+            }))),
+        );
+    }
+}
+
+/// a node of reflected code, a record of its kind and fields
+fn node(kind: &str, fields: Vec<(&str, LiteralType)>) -> LiteralType {
+    LiteralType::Record(
+        [("kind", text(kind))]
+            .into_iter()
+            .chain(fields)
+            .map(|(name, value)| (name.to_string(), Expression::Value { id: 0, value }))
+            .collect(),
+    )
+}
+
+/// string fields are interpolated when read, their braces are escaped
+fn text(s: &str) -> LiteralType {
+    LiteralType::String(s.replace('{', "{{").replace('}', "}}"))
+}
+
+fn nodes(exprs: &[Expression]) -> LiteralType {
+    LiteralType::Vec(exprs.iter().map(reflect_expr).collect())
+}
+
+fn reflect_expr(expr: &Expression) -> LiteralType {
+    match expr {
+        // strings keep their source, they aren't interpolated here
+        Expression::Value {
+            value: LiteralType::String(s),
+            ..
+        } => node("value", vec![("value", text(s))]),
+        Expression::Value { value, .. } => node("value", vec![("value", value.clone())]),
+        Expression::Var { name, .. } => node("var", vec![("name", text(&name.lexeme))]),
+        Expression::Binary {
+            left,
+            operator,
+            right,
+            ..
+        } => node(
+            "binary",
+            vec![
+                ("operator", text(&operator.lexeme)),
+                ("left", reflect_expr(left)),
+                ("right", reflect_expr(right)),
+            ],
+        ),
+        Expression::Unary { left, operator, .. } => node(
+            "unary",
+            vec![
+                ("operator", text(&operator.lexeme)),
+                ("expr", reflect_expr(left)),
+            ],
+        ),
+        Expression::Grouping { expression, .. } => {
+            node("group", vec![("expr", reflect_expr(expression))])
+        }
+        Expression::Call { name, args, .. } => node(
+            "call",
+            vec![("callee", reflect_expr(name)), ("args", nodes(args))],
+        ),
+        Expression::Method {
+            object, name, args, ..
+        } => node(
+            "method",
+            vec![
+                ("object", reflect_expr(object)),
+                ("name", text(&name.lexeme)),
+                ("args", nodes(args)),
+            ],
+        ),
+        Expression::Vec { items, .. } => node("vector", vec![("items", nodes(items))]),
+        Expression::Tuple { items, .. } => node("tuple", vec![("items", nodes(items))]),
+        Expression::Assign { name, value, .. } => node(
+            "assign",
+            vec![("name", text(&name.lexeme)), ("value", reflect_expr(value))],
+        ),
+        // other expressions keep their source
+        expr => node("expression", vec![("source", text(&expr.to_string()))]),
+    }
+}
+
+fn reflect_stmt(stmt: &Statement) -> LiteralType {
+    match stmt {
+        Statement::Expression { expr } => node(stmt_kind(stmt), vec![("expr", reflect_expr(expr))]),
+        Statement::Var { names, value, .. } => node(
+            stmt_kind(stmt),
+            vec![
+                (
+                    "names",
+                    LiteralType::Vec(names.iter().map(|name| text(&name.lexeme)).collect()),
+                ),
+                (
+                    "value",
+                    value.as_ref().map_or(LiteralType::Null, reflect_expr),
+                ),
+            ],
+        ),
+        Statement::Return { expr } => node(stmt_kind(stmt), vec![("expr", reflect_expr(expr))]),
+        stmt => node(stmt_kind(stmt), vec![]),
+    }
+}
+
+fn stmt_kind(stmt: &Statement) -> &'static str {
+    match stmt {
+        Statement::Record { .. } => "record",
+        Statement::Enum { .. } => "enum",
+        Statement::Sh { .. } => "sh",
+        Statement::Impl { .. } => "impl",
+        Statement::Type { .. } => "type",
+        Statement::Expression { .. } => "expression statement",
+        Statement::Block { .. } => "block",
+        Statement::Var { .. } => "let",
+        Statement::Func { .. } => "func",
+        Statement::If { .. } => "if",
+        Statement::Return { .. } => "return",
+        Statement::For { .. } => "for",
+        Statement::While { .. } => "while",
+        Statement::Loop { .. } => "loop",
+        Statement::Break {} => "break",
+        Statement::Match { .. } => "match",
+        Statement::Mod { .. } => "mod",
+        Statement::Use { .. } => "use",
+    }
+}
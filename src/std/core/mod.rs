@@ -1,2 +1,5 @@
+pub mod cmp;
 pub mod io;
+pub mod json;
+pub mod meta;
 pub mod test;
@@ -0,0 +1,111 @@
+use std::rc::Rc;
+
+use crate::{
+    ast::{LiteralType, Token, Wrapper},
+    errors::raw,
+    std::{func, StdFunc},
+};
+
+impl StdFunc {
+    pub fn load_literal_bytes(&mut self) {
+        self.load_to_bytes(None);
+        self.load_from_bytes(None);
+        self.load_byte_at(None);
+        self.load_len(None);
+    }
+
+    /// to_bytes(string), the UTF-8 bytes of the string
+    pub fn load_to_bytes(&mut self, name: Option<Token>) {
+        let name = match name {
+            Some(n) => n.lexeme.clone(),
+            None => "to_bytes".to_string(),
+        };
+        func(
+            name.as_str(),
+            1,
+            &mut self.env,
+            Rc::new(Wrapper(Box::new(|args: &[Option<LiteralType>]| {
+                if args.len() != 1 {
+                    raw("expected an argument");
+                }
+                match &args[0].clone().unwrap_or(LiteralType::Void) {
+                    LiteralType::String(s) => LiteralType::Bytes(s.as_bytes().to_vec()),
+                    LiteralType::Char(c) => LiteralType::Bytes(c.to_string().into_bytes()),
+                    LiteralType::Bytes(b) => LiteralType::Bytes(b.clone()),
+                    _ => LiteralType::Null,
+                }
+            }))),
+        );
+    }
+
+    /// from_bytes(bytes), decodes UTF-8 bytes into a string
+    pub fn load_from_bytes(&mut self, name: Option<Token>) {
+        let name = match name {
+            Some(n) => n.lexeme.clone(),
+            None => "from_bytes".to_string(),
+        };
+        func(
+            name.as_str(),
+            1,
+            &mut self.env,
+            Rc::new(Wrapper(Box::new(|args: &[Option<LiteralType>]| {
+                if args.len() != 1 {
+                    raw("expected an argument");
+                }
+                match &args[0].clone().unwrap_or(LiteralType::Void) {
+                    LiteralType::Bytes(b) => match String::from_utf8(b.clone()) {
+                        Ok(s) => LiteralType::String(s),
+                        Err(e) => {
+                            raw(format!(
+                                "bytes aren't valid UTF-8, at byte {}",
+                                e.utf8_error().valid_up_to()
+                            )
+                            .as_str());
+                            LiteralType::Null
+                        }
+                    },
+                    _ => LiteralType::Null,
+                }
+            }))),
+        );
+    }
+
+    /// byte_at(bytes, index), the byte as a number from 0 to 255
+    pub fn load_byte_at(&mut self, name: Option<Token>) {
+        let name = match name {
+            Some(n) => n.lexeme.clone(),
+            None => "byte_at".to_string(),
+        };
+        func(
+            name.as_str(),
+            2,
+            &mut self.env,
+            Rc::new(Wrapper(Box::new(|args: &[Option<LiteralType>]| {
+                if args.len() != 2 {
+                    raw("expected 2 argument");
+                }
+                match (
+                    &args[0].clone().unwrap_or(LiteralType::Void),
+                    &args[1].clone().unwrap_or(LiteralType::Void),
+                ) {
+                    (LiteralType::Bytes(b), LiteralType::Number(i)) => {
+                        let byte = if *i >= 0.0 && i.fract() == 0.0 {
+                            b.get(*i as usize)
+                        } else {
+                            None
+                        };
+                        match byte {
+                            Some(byte) => LiteralType::Number(*byte as f32),
+                            None => {
+                                raw(format!("index {} is out of range for {} bytes", i, b.len())
+                                    .as_str());
+                                LiteralType::Null
+                            }
+                        }
+                    }
+                    _ => LiteralType::Null,
+                }
+            }))),
+        );
+    }
+}
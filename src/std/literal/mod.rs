@@ -1,3 +1,4 @@
+pub mod bytes;
 pub mod number;
 pub mod string;
 pub mod vector;
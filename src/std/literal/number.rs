@@ -1,7 +1,7 @@
 use crate::{
     ast::{LiteralType, Token, Wrapper},
     errors::raw,
-    std::{func, StdFunc},
+    std::{constant, func, StdFunc},
 };
 use std::{process, rc::Rc};
 
@@ -38,6 +38,27 @@ impl StdFunc {
         self.load_avg(None);
         self.load_to_degrees(None);
         self.load_to_radians(None);
+        self.load_is_nan(None);
+        self.load_pi(None);
+        self.load_e(None);
+    }
+
+    /// PI, the ratio of a circle's circumference to its diameter
+    pub fn load_pi(&mut self, name: Option<Token>) {
+        let name = match name {
+            Some(n) => n.lexeme.clone(),
+            None => "PI".to_string(),
+        };
+        constant(name.as_str(), std::f32::consts::PI, &mut self.env);
+    }
+
+    /// E, the base of the natural logarithm
+    pub fn load_e(&mut self, name: Option<Token>) {
+        let name = match name {
+            Some(n) => n.lexeme.clone(),
+            None => "E".to_string(),
+        };
+        constant(name.as_str(), std::f32::consts::E, &mut self.env);
     }
 
     pub fn load_sqr(&mut self, name: Option<Token>) {
@@ -931,4 +952,29 @@ impl StdFunc {
             }),
         );
     }
+
+    /// `NaN` is unequal to every number, itself included, so `n == n` can't detect it
+    pub fn load_is_nan(&mut self, name: Option<Token>) {
+        let name = match name {
+            Some(n) => n.lexeme.clone(),
+            None => "is_nan".to_string(),
+        };
+        func(
+            name.as_str(),
+            1,
+            &mut self.env,
+            Rc::new(Wrapper(Box::new(|args: &[Option<LiteralType>]| {
+                if args.len() != 1 {
+                    raw("expected an argument");
+                }
+                match args[0].clone().unwrap_or(LiteralType::Void) {
+                    LiteralType::Number(n) => LiteralType::Boolean(n.is_nan()),
+                    _ => {
+                        raw("is_nan() expects a number");
+                        process::exit(1)
+                    }
+                }
+            }))),
+        );
+    }
 }
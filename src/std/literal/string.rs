@@ -9,7 +9,8 @@ use crate::{
 impl StdFunc {
     pub fn load_literal_string(&mut self) {
         self.load_string(None);
-        // self.load_chars(None);
+        self.load_chars(None);
+        self.load_char_at(None);
         self.load_chars_count(None);
         self.load_contains(None);
         self.load_find(None);
@@ -22,7 +23,8 @@ impl StdFunc {
         self.load_to_uppercase(None);
         // self.load_parse(None);
         self.load_replace(None);
-        // self.load_split(None);
+        self.load_split(None);
+        self.load_substr(None);
         // self.load_split_once(None);
         // self.load_split_whitespace(None);
         self.load_trim(None);
@@ -50,25 +52,72 @@ impl StdFunc {
         );
     }
 
-    // pub fn load_chars(&mut self, name: Option<Token>) {
-    //     let name = match name {
-    //         Some(n) => n.lexeme.clone(),
-    //         None => "chars".to_string(),
-    //     };
-    //     func(
-    //         name.as_str(),
-    //         1,
-    //         &mut self.env,
-    //         Rc::new(Wrapper {
-    //             0: Box::new(|args: &[Option<LiteralType>]| match &args[0].clone().unwrap_or(LiteralType::Void) {
-    //                 LiteralType::String(s) => {
-    //                     LiteralType::Array(s.chars().map(LiteralType::Char).collect())
-    //                 }
-    //                 _ => LiteralType::Null,
-    //             }),
-    //         }),
-    //     );
-    // }
+    /// chars(string), splits the string into chars
+    pub fn load_chars(&mut self, name: Option<Token>) {
+        let name = match name {
+            Some(n) => n.lexeme.clone(),
+            None => "chars".to_string(),
+        };
+        func(
+            name.as_str(),
+            1,
+            &mut self.env,
+            Rc::new(Wrapper(Box::new(|args: &[Option<LiteralType>]| {
+                if args.len() != 1 {
+                    raw("expected an argument");
+                }
+                match &args[0].clone().unwrap_or(LiteralType::Void) {
+                    LiteralType::String(s) => {
+                        LiteralType::Vec(s.chars().map(LiteralType::Char).collect())
+                    }
+                    _ => LiteralType::Null,
+                }
+            }))),
+        );
+    }
+
+    /// char_at(string, index), indexes by chars, not bytes
+    pub fn load_char_at(&mut self, name: Option<Token>) {
+        let name = match name {
+            Some(n) => n.lexeme.clone(),
+            None => "char_at".to_string(),
+        };
+        func(
+            name.as_str(),
+            2,
+            &mut self.env,
+            Rc::new(Wrapper(Box::new(|args: &[Option<LiteralType>]| {
+                if args.len() != 2 {
+                    raw("expected 2 argument");
+                }
+                match (
+                    &args[0].clone().unwrap_or(LiteralType::Void),
+                    &args[1].clone().unwrap_or(LiteralType::Void),
+                ) {
+                    (LiteralType::String(s), LiteralType::Number(i)) => {
+                        let c = if *i >= 0.0 && i.fract() == 0.0 {
+                            s.chars().nth(*i as usize)
+                        } else {
+                            None
+                        };
+                        match c {
+                            Some(c) => LiteralType::Char(c),
+                            None => {
+                                raw(format!(
+                                    "index {} is out of range for a string of {} chars",
+                                    i,
+                                    s.chars().count()
+                                )
+                                .as_str());
+                                LiteralType::Null
+                            }
+                        }
+                    }
+                    _ => LiteralType::Null,
+                }
+            }))),
+        );
+    }
 
     pub fn load_chars_count(&mut self, name: Option<Token>) {
         let name = match name {
@@ -232,6 +281,7 @@ impl StdFunc {
         );
     }
 
+    /// len(string), the length in bytes, `chars_count` counts chars
     pub fn load_len(&mut self, name: Option<Token>) {
         let name = match name {
             Some(n) => n.lexeme.clone(),
@@ -248,6 +298,7 @@ impl StdFunc {
                     }
                     match &args[0].clone().unwrap_or(LiteralType::Void) {
                         LiteralType::String(a) => LiteralType::Number(a.len() as f32),
+                        LiteralType::Bytes(a) => LiteralType::Number(a.len() as f32),
                         _ => LiteralType::Null,
                     }
                 }),
@@ -375,27 +426,82 @@ impl StdFunc {
         );
     }
 
-    // pub fn load_split(&mut self, name: Option<Token>) {
-    //     let name = match name {
-    //         Some(n) => n.lexeme.clone(),
-    //         None => "split".to_string(),
-    //     };
-    //     func(
-    //         name.as_str(),
-    //         2,
-    //         &mut self.env,
-    //         Rc::new(Wrapper {
-    //             0: Box::new(|args: &[Option<LiteralType>]| match (&args[0].clone().unwrap_or(LiteralType::Void), &args[1].clone().unwrap_or(LiteralType::Void)) {
-    //                 (LiteralType::String(a), LiteralType::String(b)) => LiteralType::Array(
-    //                     a.split(b)
-    //                         .map(|s| LiteralType::String(s.to_string()))
-    //                         .collect(),
-    //                 ),
-    //                 _ => LiteralType::Null,
-    //             }),
-    //         }),
-    //     );
-    // }
+    /// split(string, separator), the parts between the separators as a vector of strings
+    pub fn load_split(&mut self, name: Option<Token>) {
+        let name = match name {
+            Some(n) => n.lexeme.clone(),
+            None => "split".to_string(),
+        };
+        func(
+            name.as_str(),
+            2,
+            &mut self.env,
+            Rc::new(Wrapper(Box::new(|args: &[Option<LiteralType>]| {
+                if args.len() != 2 {
+                    raw("expected 2 argument");
+                }
+                match (
+                    &args[0].clone().unwrap_or(LiteralType::Void),
+                    &args[1].clone().unwrap_or(LiteralType::Void),
+                ) {
+                    (LiteralType::String(a), LiteralType::String(b)) => LiteralType::Vec(
+                        a.split(b.as_str())
+                            .map(|s| LiteralType::String(s.to_string()))
+                            .collect(),
+                    ),
+                    _ => LiteralType::Null,
+                }
+            }))),
+        );
+    }
+
+    /// substr(string, start, end), the chars from `start` up to `end`, not including it,
+    /// indexes by chars like `char_at`
+    pub fn load_substr(&mut self, name: Option<Token>) {
+        let name = match name {
+            Some(n) => n.lexeme.clone(),
+            None => "substr".to_string(),
+        };
+        func(
+            name.as_str(),
+            3,
+            &mut self.env,
+            Rc::new(Wrapper(Box::new(|args: &[Option<LiteralType>]| {
+                if args.len() != 3 {
+                    raw("expected 3 argument");
+                }
+                match (
+                    &args[0].clone().unwrap_or(LiteralType::Void),
+                    &args[1].clone().unwrap_or(LiteralType::Void),
+                    &args[2].clone().unwrap_or(LiteralType::Void),
+                ) {
+                    (
+                        LiteralType::String(s),
+                        LiteralType::Number(start),
+                        LiteralType::Number(end),
+                    ) => {
+                        let count = s.chars().count();
+                        let is_index = |i: f32| i >= 0.0 && i.fract() == 0.0 && i as usize <= count;
+                        if !is_index(*start) || !is_index(*end) || start > end {
+                            raw(format!(
+                                "range {}..{} is out of range for a string of {} chars",
+                                start, end, count
+                            )
+                            .as_str());
+                            return LiteralType::Null;
+                        }
+                        LiteralType::String(
+                            s.chars()
+                                .skip(*start as usize)
+                                .take((end - start) as usize)
+                                .collect(),
+                        )
+                    }
+                    _ => LiteralType::Null,
+                }
+            }))),
+        );
+    }
 
     // pub fn load_split_once(&mut self, name: Option<Token>) {
     //     let name = match name {
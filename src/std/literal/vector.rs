@@ -3,7 +3,7 @@ use std::rc::Rc;
 use crate::{
     ast::{LiteralType, Token, Wrapper},
     errors::raw,
-    std::{func, StdFunc},
+    std::{arity, call, func, StdFunc},
 };
 
 impl StdFunc {
@@ -20,6 +20,9 @@ impl StdFunc {
         self.load_has(None);
         self.load_key(None);
         self.load_get(None);
+        self.load_map(None);
+        self.load_filter(None);
+        self.load_reduce(None);
     }
 
     /// push(vector, item);
@@ -218,7 +221,7 @@ impl StdFunc {
         );
     }
 
-    /// for_each(vector, |item| void)
+    /// for_each(vector, |item| void), for_each(vector, |item, index| void)
     pub fn load_for_each(&mut self, name: Option<Token>) {
         let name = match name {
             Some(n) => n.lexeme.clone(),
@@ -237,11 +240,12 @@ impl StdFunc {
                         Some(v) => match v.as_ref().unwrap_or(&LiteralType::Null) {
                             LiteralType::Vec(v) => {
                                 let v: Vec<LiteralType> = v.clone();
-                                if let Some(Some(LiteralType::DeclrFunc(f))) = args.get(1) {
-                                    v.iter().for_each(|c| {
-                                        (*f.func).call(vec![Some(c.clone())]);
-                                    });
-                                };
+                                let f = args.get(1).cloned().flatten();
+                                let f = f.unwrap_or(LiteralType::Null);
+                                let with_index = arity(&f) > 1;
+                                for (i, c) in v.iter().enumerate() {
+                                    call(&f, item_args(c, i, with_index));
+                                }
                                 return LiteralType::Void;
                             }
                             _ => {}
@@ -457,41 +461,100 @@ impl StdFunc {
         );
     }
 
-    //// map(vector, |item| void)
-    // pub fn load_map(&mut self, name: Option<Token>) {
-    //     let name = match name {
-    //         Some(n) => n.lexeme.clone(),
-    //         None => "map".to_string(),
-    //     };
-    //     func(
-    //         name.as_str(),
-    //         1,
-    //         &mut self.env,
-    //         Rc::new(Wrapper {
-    //             0: Box::new(|args: &[Option<LiteralType>]| {
-    //                 if args.len() != 2 {
-    //                     raw("expected two argument");
-    //                 }
-    //                 match args.get(0) {
-    //                     Some(v) => match v.as_ref().unwrap_or(&LiteralType::Null) {
-    //                         LiteralType::Vec(v) => {
-    //                             let v: Vec<LiteralType> = v.clone();
-    //                             if let Some(Some(LiteralType::DeclrFunc(f))) = args.get(1) {
-    //                                 v.iter().for_each(|c| {
-    //                                     return (*f.func).call(vec![Some(c.clone())]);
-    //                                 });
-    //                             } else {
-    //                                 return LiteralType::Null
-    //                             };
-    //                             return LiteralType::Void;
-    //                         }
-    //                         _ => {}
-    //                     },
-    //                     None => {}
-    //                 };
-    //                 LiteralType::Null
-    //             }),
-    //         }),
-    //     );
-    // }
+    /// map(vector, |item| T), map(vector, |item, index| T), a vector of the results
+    pub fn load_map(&mut self, name: Option<Token>) {
+        let name = match name {
+            Some(n) => n.lexeme.clone(),
+            None => "map".to_string(),
+        };
+        func(
+            name.as_str(),
+            2,
+            &mut self.env,
+            Rc::new(Wrapper(Box::new(|args: &[Option<LiteralType>]| {
+                if args.len() != 2 {
+                    raw("expected 2 argument");
+                }
+                match (&args[0], &args[1]) {
+                    (Some(LiteralType::Vec(v)), Some(f)) => {
+                        let with_index = arity(f) > 1;
+                        LiteralType::Vec(
+                            v.iter()
+                                .enumerate()
+                                .map(|(i, c)| call(f, item_args(c, i, with_index)))
+                                .collect(),
+                        )
+                    }
+                    _ => LiteralType::Null,
+                }
+            }))),
+        );
+    }
+
+    /// filter(vector, |item| bool), filter(vector, |item, index| bool),
+    /// the items the callback returns a truthy value for
+    pub fn load_filter(&mut self, name: Option<Token>) {
+        let name = match name {
+            Some(n) => n.lexeme.clone(),
+            None => "filter".to_string(),
+        };
+        func(
+            name.as_str(),
+            2,
+            &mut self.env,
+            Rc::new(Wrapper(Box::new(|args: &[Option<LiteralType>]| {
+                if args.len() != 2 {
+                    raw("expected 2 argument");
+                }
+                match (&args[0], &args[1]) {
+                    (Some(LiteralType::Vec(v)), Some(f)) => {
+                        let with_index = arity(f) > 1;
+                        LiteralType::Vec(
+                            v.iter()
+                                .enumerate()
+                                .filter(|(i, c)| call(f, item_args(c, *i, with_index)).is_truthy())
+                                .map(|(_, c)| c.clone())
+                                .collect(),
+                        )
+                    }
+                    _ => LiteralType::Null,
+                }
+            }))),
+        );
+    }
+
+    /// reduce(vector, |acc, item| T, init), folds the items into `init` from the first one,
+    /// `init` for an empty vector
+    pub fn load_reduce(&mut self, name: Option<Token>) {
+        let name = match name {
+            Some(n) => n.lexeme.clone(),
+            None => "reduce".to_string(),
+        };
+        func(
+            name.as_str(),
+            3,
+            &mut self.env,
+            Rc::new(Wrapper(Box::new(|args: &[Option<LiteralType>]| {
+                if args.len() != 3 {
+                    raw("expected 3 argument");
+                }
+                match (&args[0], &args[1]) {
+                    (Some(LiteralType::Vec(v)), Some(f)) => {
+                        let init = args[2].clone().unwrap_or(LiteralType::Null);
+                        v.iter().fold(init, |acc, c| call(f, vec![acc, c.clone()]))
+                    }
+                    _ => LiteralType::Null,
+                }
+            }))),
+        );
+    }
+}
+
+/// the arguments of a callback for an item, the index is given to callbacks taking two parameters
+fn item_args(item: &LiteralType, index: usize, with_index: bool) -> Vec<LiteralType> {
+    let mut args = vec![item.clone()];
+    if with_index {
+        args.push(LiteralType::Number(index as f32));
+    }
+    args
 }
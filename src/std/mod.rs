@@ -3,11 +3,12 @@ pub mod core;
 pub mod literal;
 
 use crate::{
-    ast::{DeclrFuncType, FuncValType, LiteralType, Token},
+    ast::{DeclrFuncType, FuncValType, LiteralType, Token, TokenType},
     errors::raw,
     interpreter::{
-        env::{Env, FuncKind},
-        Interpreter,
+        env::{Env, FuncKind, VarKind},
+        expr::Expression,
+        run_func, Interpreter,
     },
 };
 
@@ -28,6 +29,53 @@ pub fn func(name: &str, arity: usize, env: &mut Rc<RefCell<Env>>, func: Rc<dyn F
     )
 }
 
+/// defines an immutable number, `PI`
+pub fn constant(name: &str, value: f32, env: &mut Rc<RefCell<Env>>) {
+    env.borrow().define_pub_var(
+        name.to_string(),
+        LiteralType::Number(value),
+        VarKind {
+            is_mut: false,
+            is_pub: true,
+            is_func: false,
+            value_type: Token {
+                token: TokenType::NumIdent,
+                lexeme: "number".to_string(),
+                value: None,
+                line: 0,
+                pos: (0, 0),
+            },
+        },
+    )
+}
+
+/// calls a function given to a std function, `for_each(v, show)`
+pub fn call(callback: &LiteralType, args: Vec<LiteralType>) -> LiteralType {
+    match callback {
+        LiteralType::Func(f) => {
+            let args: Vec<Expression> = args
+                .into_iter()
+                .map(|value| Expression::Value { id: 0, value })
+                .collect();
+            run_func(f.clone(), &args, Rc::clone(&f.env))
+        }
+        LiteralType::DeclrFunc(f) => (*f.func).call(args.into_iter().map(Some).collect()),
+        v => {
+            raw(format!("expected a function, got {}", v.type_name()).as_str());
+            LiteralType::Null
+        }
+    }
+}
+
+/// how many arguments a function given to a std function takes,
+/// std functions only get the first one
+pub fn arity(callback: &LiteralType) -> usize {
+    match callback {
+        LiteralType::Func(f) => f.params.len() - f.bound.iter().flatten().count(),
+        _ => 1,
+    }
+}
+
 #[derive(Clone)]
 pub struct StdFunc {
     env: Rc<RefCell<Env>>,
@@ -144,6 +192,66 @@ impl Interpreter {
                             ),
                         ]),
                     ),
+                    (
+                        "meta",
+                        HashMap::from([
+                            (
+                                "eval",
+                                Box::new({
+                                    let mut std = std.clone();
+                                    move |name2: &Option<Token>| {
+                                        std.load_eval(name2.clone());
+                                    }
+                                }) as Box<dyn FnMut(&Option<Token>)>,
+                            ),
+                            (
+                                "reflect",
+                                Box::new({
+                                    let mut std = std.clone();
+                                    move |name2: &Option<Token>| {
+                                        std.load_reflect(name2.clone());
+                                    }
+                                }) as Box<dyn FnMut(&Option<Token>)>,
+                            ),
+                        ]),
+                    ),
+                    (
+                        "cmp",
+                        HashMap::from([(
+                            "between",
+                            Box::new({
+                                let mut std = std.clone();
+                                move |name2: &Option<Token>| {
+                                    std.load_between(name2.clone());
+                                }
+                            }) as Box<dyn FnMut(&Option<Token>)>,
+                        )]),
+                    ),
+                    (
+                        "json",
+                        HashMap::from([
+                            (
+                                "to_json",
+                                Box::new({
+                                    let mut std = std.clone();
+                                    move |name2: &Option<Token>| {
+                                        std.load_to_json(name2.clone());
+                                    }
+                                })
+                                    as Box<dyn FnMut(&Option<Token>)>,
+                            ),
+                            (
+                                "from_json",
+                                Box::new({
+                                    let mut std = std.clone();
+                                    move |name2: &Option<Token>| {
+                                        std.load_from_json(name2.clone());
+                                    }
+                                })
+                                    as Box<dyn FnMut(&Option<Token>)>,
+                            ),
+                        ]),
+                    ),
                     (
                         "test",
                         HashMap::from([(
@@ -161,9 +269,74 @@ impl Interpreter {
             (
                 "literal",
                 vec![
+                    (
+                        "bytes",
+                        HashMap::from([
+                            (
+                                "to_bytes",
+                                Box::new({
+                                    let mut std = std.clone();
+                                    move |name2: &Option<Token>| {
+                                        std.load_to_bytes(name2.clone());
+                                    }
+                                })
+                                    as Box<dyn FnMut(&Option<Token>)>,
+                            ),
+                            (
+                                "from_bytes",
+                                Box::new({
+                                    let mut std = std.clone();
+                                    move |name2: &Option<Token>| {
+                                        std.load_from_bytes(name2.clone());
+                                    }
+                                })
+                                    as Box<dyn FnMut(&Option<Token>)>,
+                            ),
+                            (
+                                "byte_at",
+                                Box::new({
+                                    let mut std = std.clone();
+                                    move |name2: &Option<Token>| {
+                                        std.load_byte_at(name2.clone());
+                                    }
+                                })
+                                    as Box<dyn FnMut(&Option<Token>)>,
+                            ),
+                            (
+                                "len",
+                                Box::new({
+                                    let mut std = std.clone();
+                                    move |name2: &Option<Token>| {
+                                        std.load_len(name2.clone());
+                                    }
+                                })
+                                    as Box<dyn FnMut(&Option<Token>)>,
+                            ),
+                        ]),
+                    ),
                     (
                         "number",
                         HashMap::from([
+                            (
+                                "PI",
+                                Box::new({
+                                    let mut std = std.clone();
+                                    move |name2: &Option<Token>| {
+                                        std.load_pi(name2.clone());
+                                    }
+                                })
+                                    as Box<dyn FnMut(&Option<Token>)>,
+                            ),
+                            (
+                                "E",
+                                Box::new({
+                                    let mut std = std.clone();
+                                    move |name2: &Option<Token>| {
+                                        std.load_e(name2.clone());
+                                    }
+                                })
+                                    as Box<dyn FnMut(&Option<Token>)>,
+                            ),
                             (
                                 "sqr",
                                 Box::new({
@@ -474,11 +647,41 @@ impl Interpreter {
                                 })
                                     as Box<dyn FnMut(&Option<Token>)>,
                             ),
+                            (
+                                "is_nan",
+                                Box::new({
+                                    let mut std = std.clone();
+                                    move |name2: &Option<Token>| {
+                                        std.load_is_nan(name2.clone());
+                                    }
+                                })
+                                    as Box<dyn FnMut(&Option<Token>)>,
+                            ),
                         ]),
                     ),
                     (
                         "string",
                         HashMap::from([
+                            (
+                                "chars",
+                                Box::new({
+                                    let mut std = std.clone();
+                                    move |name2: &Option<Token>| {
+                                        std.load_chars(name2.clone());
+                                    }
+                                })
+                                    as Box<dyn FnMut(&Option<Token>)>,
+                            ),
+                            (
+                                "char_at",
+                                Box::new({
+                                    let mut std = std.clone();
+                                    move |name2: &Option<Token>| {
+                                        std.load_char_at(name2.clone());
+                                    }
+                                })
+                                    as Box<dyn FnMut(&Option<Token>)>,
+                            ),
                             (
                                 "chars_count",
                                 Box::new({
@@ -609,6 +812,26 @@ impl Interpreter {
                                 })
                                     as Box<dyn FnMut(&Option<Token>)>,
                             ),
+                            (
+                                "split",
+                                Box::new({
+                                    let mut std = std.clone();
+                                    move |name2: &Option<Token>| {
+                                        std.load_split(name2.clone());
+                                    }
+                                })
+                                    as Box<dyn FnMut(&Option<Token>)>,
+                            ),
+                            (
+                                "substr",
+                                Box::new({
+                                    let mut std = std.clone();
+                                    move |name2: &Option<Token>| {
+                                        std.load_substr(name2.clone());
+                                    }
+                                })
+                                    as Box<dyn FnMut(&Option<Token>)>,
+                            ),
                         ]),
                     ),
                     (
@@ -734,6 +957,36 @@ impl Interpreter {
                                 })
                                     as Box<dyn FnMut(&Option<Token>)>,
                             ),
+                            (
+                                "map",
+                                Box::new({
+                                    let mut std = std.clone();
+                                    move |name2: &Option<Token>| {
+                                        std.load_map(name2.clone());
+                                    }
+                                })
+                                    as Box<dyn FnMut(&Option<Token>)>,
+                            ),
+                            (
+                                "filter",
+                                Box::new({
+                                    let mut std = std.clone();
+                                    move |name2: &Option<Token>| {
+                                        std.load_filter(name2.clone());
+                                    }
+                                })
+                                    as Box<dyn FnMut(&Option<Token>)>,
+                            ),
+                            (
+                                "reduce",
+                                Box::new({
+                                    let mut std = std.clone();
+                                    move |name2: &Option<Token>| {
+                                        std.load_reduce(name2.clone());
+                                    }
+                                })
+                                    as Box<dyn FnMut(&Option<Token>)>,
+                            ),
                         ]),
                     ),
                 ],
@@ -762,6 +1015,9 @@ impl Interpreter {
                         match parts[1] {
                             "core" => match parts[2] {
                                 "io" => std.load_core_io(),
+                                "meta" => std.load_core_meta(),
+                                "cmp" => std.load_core_cmp(),
+                                "json" => std.load_core_json(),
                                 "test" => std.load_core_test(),
                                 _ => raw(format!(
                                     "std module '{}::{}' doesn't exist",
@@ -770,6 +1026,7 @@ impl Interpreter {
                                 .as_str()),
                             },
                             "literal" => match parts[2] {
+                                "bytes" => std.load_literal_bytes(),
                                 "number" => std.load_literal_number(),
                                 "string" => std.load_literal_string(),
                                 "vector" => std.load_literal_vector(),
@@ -0,0 +1,605 @@
+// Absurd transpiler, it emits JavaScript source from statements
+use crate::ast::{CallType, Destruct, FuncBody, LiteralType, Statement, Token, TokenType::*};
+use crate::bundler::{interpolation, parse_expr, Part};
+use crate::errors::{Error, ErrorCode::*};
+use crate::interpreter::expr::{AssignKind, Expression};
+use std::process::exit;
+
+/// builtins from the prelude, mapped to JavaScript
+const JS_PRELUDE: &str = "\
+const print = (text) => console.log(text);
+const eprint = (text) => console.error(text);
+const warn = (text) => console.warn(text);
+const panic = (text) => { throw new Error(text); };
+const between = (x, lo, hi) => lo <= x && x <= hi;
+const $in = (x, xs) => typeof xs === \"string\"
+  ? xs.includes(x)
+  : xs.some((y) => JSON.stringify(y) === JSON.stringify(x));
+const $range = (start, end, inclusive) => {
+  if (typeof start === \"string\") {
+    if (start > end) throw new Error(`the char range '${start}'..'${end}' is reversed`);
+    return $range(start.codePointAt(0), end.codePointAt(0), inclusive)
+      .map((c) => String.fromCodePoint(c));
+  }
+  const items = [];
+  for (let n = start; n < end || (inclusive && n === end); n++) items.push(n);
+  return items;
+};
+";
+
+#[derive(Debug, Clone)]
+pub struct Transpiler {
+    err: Error,
+    depth: usize,
+    /// counter for generated names
+    tmp: usize,
+}
+
+impl Transpiler {
+    pub fn new(err: Error) -> Self {
+        Transpiler {
+            err,
+            depth: 0,
+            tmp: 0,
+        }
+    }
+
+    /// entry method
+    pub fn transpile(&mut self, stmts: &[Statement]) -> String {
+        let mut js = JS_PRELUDE.to_string();
+        js.push('\n');
+        stmts.iter().for_each(|stmt| js.push_str(&self.stmt(stmt)));
+        // the `@entry` function runs after the top level
+        if let Some((name, params)) = stmts.iter().find_map(|stmt| match stmt {
+            Statement::Func {
+                name,
+                params,
+                is_entry: true,
+                ..
+            } => Some((name, params)),
+            _ => None,
+        }) {
+            let args = if params.is_empty() {
+                ""
+            } else {
+                "process.argv.slice(2)"
+            };
+            js.push_str(&format!("{}({});\n", name.lexeme, args));
+        }
+        js
+    }
+
+    /// statement transpiler
+    fn stmt(&mut self, stmt: &Statement) -> String {
+        let code = match stmt {
+            Statement::Expression { expr } => format!("{};", self.expr(expr)),
+            Statement::Block { stmts } => self.block(stmts),
+            Statement::Var {
+                names,
+                destruct,
+                value,
+                is_mut,
+                constraint,
+                ..
+            } => {
+                let code = self.var(names, destruct, value, *is_mut);
+                match constraint {
+                    Some(constraint) => {
+                        let msg = format!(
+                            "'{}' doesn't satisfy the constraint '{}'",
+                            names[0].lexeme, constraint
+                        );
+                        format!(
+                            "{}\n{}if (!{}) throw new Error({:?});",
+                            code,
+                            self.indent(),
+                            self.expr(constraint),
+                            msg
+                        )
+                    }
+                    None => code,
+                }
+            }
+            Statement::Func {
+                name,
+                body,
+                params,
+                is_async,
+                ..
+            } => {
+                let params = self.params(params);
+                let body = self.func_body(body);
+                let keyword = if *is_async {
+                    "async function"
+                } else {
+                    "function"
+                };
+                format!("{} {}({}) {}", keyword, name.lexeme, params, body)
+            }
+            Statement::If {
+                cond,
+                body,
+                else_if_branches,
+                else_branch,
+            } => {
+                let mut code = format!("if ({}) {}", self.cond(cond), self.body(body));
+                for (cond, body) in else_if_branches {
+                    code.push_str(&format!(
+                        " else if ({}) {}",
+                        self.cond(cond),
+                        self.body(body)
+                    ));
+                }
+                if let Some(body) = else_branch {
+                    code.push_str(&format!(" else {}", self.body(body)));
+                }
+                code
+            }
+            Statement::Return { expr } => format!("return {};", self.expr(expr)),
+            Statement::For {
+                iterator,
+                index,
+                expr,
+                body,
+            } => {
+                let expr = self.expr(expr);
+                let body = self.body(body);
+                match index {
+                    Some(index) => format!(
+                        "for (const [{}, {}] of ({}).entries()) {}",
+                        index.lexeme, iterator.lexeme, expr, body
+                    ),
+                    None => format!("for (const {} of {}) {}", iterator.lexeme, expr, body),
+                }
+            }
+            Statement::While { cond, body } => {
+                format!("while ({}) {}", self.cond(cond), self.body(body))
+            }
+            Statement::Loop { iter, body } => match iter {
+                Some(n) => {
+                    let i = self.tmp_name();
+                    format!("for (let {i} = 0; {i} < {}; {i}++) {}", n, self.body(body))
+                }
+                None => format!("while (true) {}", self.body(body)),
+            },
+            Statement::Break {} => "break;".to_string(),
+            Statement::Match {
+                cond,
+                cases,
+                def_case,
+            } => self.matchs(cond, cases, def_case),
+            Statement::Record { name, getters, .. } if !getters.is_empty() => {
+                self.unsupported("record getters", Some(name))
+            }
+            // types are erased
+            Statement::Record { .. } | Statement::Type { .. } => return String::new(),
+            Statement::Enum { name, .. } => self.unsupported("enums", Some(name)),
+            Statement::Impl { name, .. } => self.unsupported("impl blocks", Some(name)),
+            Statement::Sh { .. } => self.unsupported("shell commands", None),
+            Statement::Mod { .. } => self.unsupported("modules", None),
+            Statement::Use { .. } => self.unsupported("imports", None),
+        };
+        format!("{}{}\n", self.indent(), code)
+    }
+
+    fn var(
+        &mut self,
+        names: &[Token],
+        destruct: &Option<Destruct>,
+        value: &Option<Expression>,
+        is_mut: bool,
+    ) -> String {
+        let value = match value {
+            Some(value) => self.expr(value),
+            // empty variables are always mutable
+            None => {
+                let names: Vec<String> = names.iter().map(|n| n.lexeme.clone()).collect();
+                return format!("let {};", names.join(", "));
+            }
+        };
+        let keyword = if is_mut { "let" } else { "const" };
+        // `_` skips the entry
+        let names: Vec<String> = names
+            .iter()
+            .map(|n| match n.token {
+                Null => String::new(),
+                _ => n.lexeme.clone(),
+            })
+            .collect();
+        match destruct {
+            Some(Destruct::Vector) | Some(Destruct::Tuple) => {
+                format!("{} [{}] = {};", keyword, names.join(", "), value)
+            }
+            Some(Destruct::Record) => {
+                format!("{} {{ {} }} = {};", keyword, names.join(", "), value)
+            }
+            None => {
+                let names: Vec<String> =
+                    names.iter().map(|n| format!("{} = {}", n, value)).collect();
+                format!("{} {};", keyword, names.join(", "))
+            }
+        }
+    }
+
+    /// match statements become an if chain, so `break` still targets loops
+    fn matchs(
+        &mut self,
+        cond: &Expression,
+        cases: &[(Expression, Option<Expression>, FuncBody)],
+        def_case: &Option<FuncBody>,
+    ) -> String {
+        let tmp = self.tmp_name();
+        let mut code = format!(
+            "{{\n{}const {} = {};\n",
+            self.inner_indent(),
+            tmp,
+            self.expr(cond)
+        );
+        code.push_str(&self.inner_indent());
+        for (i, (case, guard, body)) in cases.iter().enumerate() {
+            if i != 0 {
+                code.push_str(" else ");
+            }
+            if let Expression::Tuple { .. } = case {
+                self.unsupported("tuple patterns", case.pattern_names().first().copied());
+            }
+            let (cond, binding) = match case.string_pattern() {
+                // `"GET " ++ path` binds the rest of the string
+                Some((text, name, is_prefix)) => {
+                    let text = self.expr(text);
+                    let (method, rest) = match is_prefix {
+                        true => ("startsWith", format!("{}.slice({}.length)", tmp, text)),
+                        false => (
+                            "endsWith",
+                            format!("{}.slice(0, {}.length - {}.length)", tmp, tmp, text),
+                        ),
+                    };
+                    let cond = format!(
+                        "typeof {} === \"string\" && {}.{}({})",
+                        tmp, tmp, method, text
+                    );
+                    let binding = (name.token != Underscore).then_some((&name.lexeme, rest));
+                    (cond, binding)
+                }
+                // `'a'..'z'` matches by its bounds
+                None => match case {
+                    Expression::Range {
+                        start,
+                        end,
+                        inclusive,
+                        ..
+                    } => {
+                        let operator = if *inclusive { "<=" } else { "<" };
+                        let cond = format!(
+                            "{} <= {} && {} {} {}",
+                            self.expr(start),
+                            tmp,
+                            tmp,
+                            operator,
+                            self.expr(end)
+                        );
+                        (cond, None)
+                    }
+                    case => (format!("{} === {}", tmp, self.expr(case)), None),
+                },
+            };
+            // the guard sees the bound name through a call
+            let cond = match (guard, &binding) {
+                (Some(guard), Some((name, rest))) => {
+                    format!("{} && (({}) => {})({})", cond, name, self.expr(guard), rest)
+                }
+                (Some(guard), None) => format!("{} && {}", cond, self.expr(guard)),
+                (None, _) => cond,
+            };
+            let mut body = self.case_body(body);
+            if let Some((name, rest)) = binding {
+                self.depth += 2;
+                let binding = format!("const {} = {};", name, rest);
+                body = body.replacen('\n', &format!("\n{}{}\n", self.indent(), binding), 1);
+                self.depth -= 2;
+            }
+            code.push_str(&format!("if ({}) {}", cond, body));
+        }
+        let def_case = def_case.as_ref().filter(
+            |def_case| !matches!(def_case, FuncBody::Statements(stmts) if stmts.is_empty()),
+        );
+        if let Some(def_case) = def_case {
+            if cases.is_empty() {
+                code.push_str(&self.case_body(def_case));
+            } else {
+                code.push_str(&format!(" else {}", self.case_body(def_case)));
+            }
+        }
+        code.push_str(&format!("\n{}}}", self.indent()));
+        code
+    }
+
+    fn case_body(&mut self, body: &FuncBody) -> String {
+        self.depth += 1;
+        let body = match body {
+            FuncBody::Statements(stmts) => self.body(stmts),
+            FuncBody::Expression(expr) => {
+                self.depth += 1;
+                let code = format!("{{\n{}{};\n", self.indent(), self.expr(expr));
+                self.depth -= 1;
+                format!("{}{}}}", code, self.indent())
+            }
+        };
+        self.depth -= 1;
+        body
+    }
+
+    fn func_body(&mut self, body: &FuncBody) -> String {
+        match body {
+            FuncBody::Statements(stmts) => self.body(stmts),
+            FuncBody::Expression(expr) => {
+                self.depth += 1;
+                let code = format!("{{\n{}return {};\n", self.indent(), self.expr(expr));
+                self.depth -= 1;
+                format!("{}{}}}", code, self.indent())
+            }
+        }
+    }
+
+    /// bodies are parsed as a single block
+    fn body(&mut self, stmts: &[Statement]) -> String {
+        if let [Statement::Block { stmts }] = stmts {
+            return self.block(stmts);
+        }
+        self.block(stmts)
+    }
+
+    fn block(&mut self, stmts: &[Statement]) -> String {
+        self.depth += 1;
+        let code: String = stmts.iter().map(|stmt| self.stmt(stmt)).collect();
+        self.depth -= 1;
+        format!("{{\n{}{}}}", code, self.indent())
+    }
+
+    fn params(&self, params: &[(Token, Token)]) -> String {
+        let params: Vec<String> = params.iter().map(|(name, _)| name.lexeme.clone()).collect();
+        params.join(", ")
+    }
+
+    /// expression transpiler
+    fn expr(&mut self, expr: &Expression) -> String {
+        match expr {
+            Expression::Value { value, .. } => self.value(value),
+            Expression::Var { name, .. } | Expression::Move { name, .. } => name.lexeme.clone(),
+            Expression::Grouping { expression, .. } => format!("({})", self.expr(expression)),
+            Expression::Vec { items, .. } | Expression::Tuple { items, .. } => {
+                format!("[{}]", self.exprs(items))
+            }
+            Expression::Record { fields, .. } => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|(name, value)| format!("{}: {}", name, self.expr(value)))
+                    .collect();
+                format!("{{ {} }}", fields.join(", "))
+            }
+            Expression::Assign {
+                name,
+                path,
+                value,
+                kind,
+                ..
+            } => {
+                let mut target = name.lexeme.clone();
+                for key in path {
+                    match key {
+                        Expression::Value {
+                            value: LiteralType::String(field),
+                            ..
+                        } => target = format!("{}.{}", target, field),
+                        key => target = format!("{}[{}]", target, self.expr(key)),
+                    }
+                }
+                let operator = match kind {
+                    AssignKind::Normal => "=",
+                    AssignKind::Plus => "+=",
+                    AssignKind::Minus => "-=",
+                    AssignKind::Mult => "*=",
+                    AssignKind::Div => "/=",
+                };
+                format!("{} {} {}", target, operator, self.expr(value))
+            }
+            Expression::Call {
+                name,
+                args,
+                call_type,
+                ..
+            } => {
+                let callee = self.expr(name);
+                match call_type {
+                    CallType::Func => format!("{}({})", callee, self.exprs(args)),
+                    CallType::Vector => format!("{}[{}]", callee, self.exprs(args)),
+                    CallType::Struct => match args.first() {
+                        Some(Expression::Value {
+                            value: LiteralType::String(field),
+                            ..
+                        }) => format!("{}.{}", callee, field),
+                        _ => self.unsupported("computed field access", None),
+                    },
+                    CallType::Enum => self.unsupported("`::` paths", None),
+                }
+            }
+            Expression::Unary { left, operator, .. } => {
+                let left = self.expr(left);
+                match operator.token {
+                    Min => format!("(-{})", left),
+                    Bang => format!("!{}", left),
+                    DblBang => format!("!!{}", left),
+                    Sqr => format!("({0} * {0})", left),
+                    Incr => format!("({} + 1)", left),
+                    Decr => format!("({} - 1)", left),
+                    _ => self.unsupported("unary operator", Some(operator)),
+                }
+            }
+            Expression::Binary {
+                left,
+                operator,
+                right,
+                ..
+            } => {
+                let js_operator = match operator.token {
+                    Plus => "+",
+                    Min => "-",
+                    Mul => "*",
+                    Div => "/",
+                    Prcnt => "%",
+                    Sqr => "**",
+                    Gr => ">",
+                    GrOrEq => ">=",
+                    Ls => "<",
+                    LsOrEq => "<=",
+                    Eq => "===",
+                    BangEq => "!==",
+                    Or => "||",
+                    DblAnd => "&&",
+                    In => return format!("$in({}, {})", self.expr(left), self.expr(right)),
+                    _ => self.unsupported("binary operator", Some(operator)),
+                };
+                // parentheses keep the evaluation order of the tree
+                format!("({} {} {})", self.expr(left), js_operator, self.expr(right))
+            }
+            Expression::If {
+                cond,
+                body,
+                else_branch,
+                ..
+            } => {
+                let else_branch = match else_branch {
+                    Some(branch) => self.expr(branch),
+                    None => "null".to_string(),
+                };
+                format!(
+                    "({} ? {} : {})",
+                    self.expr(cond),
+                    self.expr(body),
+                    else_branch
+                )
+            }
+            Expression::Func {
+                body,
+                params,
+                is_async,
+                ..
+            } => {
+                let params = self.params(params);
+                let body = self.func_body(body);
+                let keyword = if *is_async { "async " } else { "" };
+                format!("{}({}) => {}", keyword, params, body)
+            }
+            Expression::Await { expr, .. } => format!("await {}", self.expr(expr)),
+            Expression::Range {
+                start,
+                end,
+                inclusive,
+                ..
+            } => format!(
+                "$range({}, {}, {})",
+                self.expr(start),
+                self.expr(end),
+                inclusive
+            ),
+            Expression::Comprehension {
+                expr,
+                var,
+                iter,
+                filter,
+                ..
+            } => {
+                let mut code = format!("({})", self.expr(iter));
+                if let Some(filter) = filter {
+                    code.push_str(&format!(
+                        ".filter(({}) => {})",
+                        var.lexeme,
+                        self.expr(filter)
+                    ));
+                }
+                code.push_str(&format!(".map(({}) => {})", var.lexeme, self.expr(expr)));
+                code
+            }
+            Expression::Method { name, .. } => self.unsupported("methods", Some(name)),
+            Expression::Labeled { label, .. } => self.unsupported("labeled arguments", Some(label)),
+            Expression::Quote { .. } => self.unsupported("quotes", None),
+        }
+    }
+
+    /// conditions are already parenthesized
+    fn cond(&mut self, expr: &Expression) -> String {
+        let code = self.expr(expr);
+        match expr {
+            // `in` is emitted as a call, without the outer parentheses
+            Expression::Binary { operator, .. } if operator.token != In => {
+                code[1..code.len() - 1].to_string()
+            }
+            _ => code,
+        }
+    }
+
+    fn exprs(&mut self, exprs: &[Expression]) -> String {
+        let exprs: Vec<String> = exprs.iter().map(|expr| self.expr(expr)).collect();
+        exprs.join(", ")
+    }
+
+    fn value(&mut self, value: &LiteralType) -> String {
+        match value {
+            LiteralType::Number(n) => n.to_string(),
+            LiteralType::String(s) => self.string(s),
+            LiteralType::Char(c) => format!("{:?}", c.to_string()),
+            LiteralType::Bytes(b) => format!("new Uint8Array({:?})", b),
+            LiteralType::Boolean(b) => b.to_string(),
+            LiteralType::Null => "null".to_string(),
+            LiteralType::Void => "undefined".to_string(),
+            LiteralType::Vec(items) => {
+                let items: Vec<String> = items.iter().map(|item| self.value(item)).collect();
+                format!("[{}]", items.join(", "))
+            }
+            v => self.unsupported(&format!("{} values", v.type_name()), None),
+        }
+    }
+
+    /// strings become template literals, `{expr}` is interpolated
+    fn string(&mut self, s: &str) -> String {
+        let mut js = String::from("`");
+        for part in interpolation(s) {
+            match part {
+                Part::Text(text) => js.push_str(&escape(&text)),
+                Part::Expr(expr) => {
+                    let expr = parse_expr(&expr, self.err.clone());
+                    js.push_str(&format!("${{{}}}", self.expr(&expr)));
+                }
+            }
+        }
+        js.push('`');
+        js
+    }
+
+    fn tmp_name(&mut self) -> String {
+        self.tmp += 1;
+        format!("$abs{}", self.tmp)
+    }
+
+    fn indent(&self) -> String {
+        "  ".repeat(self.depth)
+    }
+
+    fn inner_indent(&self) -> String {
+        "  ".repeat(self.depth + 1)
+    }
+
+    fn unsupported(&self, what: &str, token: Option<&Token>) -> ! {
+        let (line, pos) = token.map_or((0, (0, 0)), |t| (t.line, t.pos));
+        self.err
+            .throw(E0x601, line, pos, vec!["js".to_string(), what.to_string()]);
+        exit(1);
+    }
+}
+
+/// escapes text for template literals
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('`', "\\`")
+        .replace("${", "\\${")
+}
@@ -0,0 +1,39 @@
+mod common;
+use common::run;
+
+const USE: &str = "use between from std::core::cmp;\n";
+
+#[test]
+fn in_range() {
+    let out = run(&format!(
+        "{USE}print(between(5, 1, 10));\nprint(between(1, 1, 1));\nprint(between('c', 'a', 'z'));\n"
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "true\ntrue\ntrue\n");
+}
+
+#[test]
+fn out_of_range() {
+    let out = run(&format!(
+        "{USE}print(between(11, 1, 10));\nprint(between(0.5, 1, 10));\nprint(between('A', 'a', 'z'));\n"
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "false\nfalse\nfalse\n");
+}
+
+#[test]
+fn inverted_bounds() {
+    let out = run(&format!("{USE}print(between(5, 10, 1));\n"));
+    assert!(out.stderr.contains("bounds are inverted: 10 > 1"));
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn mixed_types() {
+    let out = run(&format!("{USE}print(between('c', 1, 10));\n"));
+    assert!(out
+        .stderr
+        .contains("expects numbers or chars, got char, number, number"));
+    let out = run(&format!("{USE}print(between(5, \"a\", 10));\n"));
+    assert!(out.stderr.contains("expects numbers or chars"));
+}
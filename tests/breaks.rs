@@ -0,0 +1,57 @@
+mod common;
+use common::run;
+
+#[test]
+fn break_in_a_match_arm_leaves_the_while() {
+    let out = run(concat!(
+        "let mut i = 0;\n",
+        "while true {\n",
+        "    i += 1;\n",
+        "    match i {\n",
+        "        3 => { break; }\n",
+        "        _ => print(i)\n",
+        "    }\n",
+        "}\n",
+        "print(\"done {i}\");\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "1\n2\ndone 3\n");
+}
+
+#[test]
+fn break_in_a_match_arm_skips_the_rest_of_the_loop() {
+    let out = run(concat!(
+        "let mut i = 0;\n",
+        "loop {\n",
+        "    i += 1;\n",
+        "    match i {\n",
+        "        2 => { break; }\n",
+        "        _ => {}\n",
+        "    }\n",
+        "    print(i);\n",
+        "}\n",
+        "print(\"done {i}\");\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "1\ndone 2\n");
+}
+
+#[test]
+fn return_in_a_match_arm_leaves_the_function() {
+    let out = run(concat!(
+        "func f() -> number {\n",
+        "    let mut i = 0;\n",
+        "    while true {\n",
+        "        i += 1;\n",
+        "        match i {\n",
+        "            4 => { return i * 10; }\n",
+        "            _ => {}\n",
+        "        }\n",
+        "    }\n",
+        "    return 0;\n",
+        "}\n",
+        "print(f());\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "40\n");
+}
@@ -0,0 +1,69 @@
+mod common;
+use common::run;
+
+const BYTES: &str = "use * from std::literal::bytes;\n";
+
+#[test]
+fn byte_literals() {
+    let out = run(&format!(
+        "{BYTES}let b = b\"hi\\xff\";\nprint(b);\nprint(len(b));\nprint(byte_at(b, 2));\n"
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "b\"hi\\xff\"\n3\n255\n");
+}
+
+#[test]
+fn strings_to_bytes_and_back() {
+    let out = run(&format!(
+        "{BYTES}let t = to_bytes(\"ok\");\nprint(t);\nprint(from_bytes(t));\n"
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "b\"ok\"\nok\n");
+}
+
+#[test]
+fn invalid_utf8() {
+    let out = run(&format!("{BYTES}print(from_bytes(b\"\\xff\"));\n"));
+    assert!(
+        out.stderr.contains("bytes aren't valid UTF-8, at byte 0"),
+        "{}",
+        out.stderr
+    );
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn byte_out_of_range() {
+    let out = run(&format!("{BYTES}print(byte_at(b\"a\", 5));\n"));
+    assert!(
+        out.stderr.contains("index 5 is out of range for 1 bytes"),
+        "{}",
+        out.stderr
+    );
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn malformed_byte_escape() {
+    let out = run("print(b\"\\xzz\");\n");
+    assert_eq!(out.errors(), ["E0x111"]);
+}
+
+#[test]
+fn indexed_bytes() {
+    let out = run("let b = b\"ab\";\nprint(b[0]);\nprint(b[1]);\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "97\n98\n");
+}
+
+#[test]
+fn index_out_of_bounds() {
+    let out = run("let b = b\"a\";\nprint(b[3]);\n");
+    assert_eq!(out.errors(), ["E0x436"]);
+    assert!(out
+        .stderr
+        .contains("can't access 'b[3]', index 3 is out of bounds for length 1"));
+    assert_eq!(out.stdout, "");
+    let out = run("let b = b\"a\";\nprint(b[-1]);\n");
+    assert_eq!(out.errors(), ["E0x436"]);
+}
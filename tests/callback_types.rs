@@ -0,0 +1,56 @@
+mod common;
+use common::run;
+
+const APPLY: &str = "func ap(k: |number| number, n: number) -> number = k(n);\n";
+
+#[test]
+fn matching_functions() {
+    let out = run(&format!(
+        concat!(
+            "{}",
+            "func double(n: number) -> number = n * 2;\n",
+            "func add(a: number, b: number) -> number = a + b;\n",
+            "print(ap(|x: number| number: x * 3, 2));\n",
+            "print(ap(double, 4));\n",
+            "print(ap(add(1), 2));\n",
+            "print(ap(|x|: x * 10, 2));\n",
+            "let v: |Vec<number>| Vec<number> = |x: Vec<number>| Vec<number>: x;\n",
+            "print(v([1]));\n",
+        ),
+        APPLY
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "6\n8\n3\n20\n[1]\n");
+}
+
+#[test]
+fn any_matches_every_type() {
+    let out = run("let a: |any| any = |x: number| number: x;\nprint(a(1));\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "1\n");
+}
+
+#[test]
+fn arity_mismatch() {
+    let out = run("let f: |number| number = |x: number, y: number| number: x;\nprint(f);\n");
+    assert_eq!(out.errors(), ["E0x301"]);
+    assert!(out
+        .stderr
+        .contains("type mismatch: expected '|number| number', got '<func>'"));
+}
+
+#[test]
+fn parameter_mismatch() {
+    let out = run("let f: |number| number = |x: string| string: x;\nprint(f);\n");
+    assert_eq!(out.errors(), ["E0x301"]);
+    let out = run(&format!("{APPLY}print(ap(|x: string| number: 1, 2));\n"));
+    assert_eq!(out.errors(), ["E0x301"]);
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn return_mismatch() {
+    let out = run("let f: |number| number = |x: number| string: \"a\";\nprint(f);\n");
+    assert_eq!(out.errors(), ["E0x301"]);
+    assert_eq!(out.stdout, "");
+}
@@ -0,0 +1,34 @@
+mod common;
+use common::run;
+
+#[test]
+fn carets_under_the_error_columns() {
+    let out = run("let x = [1, 2];\nprint(x[5]);\n");
+    assert_eq!(out.errors(), ["E0x436"]);
+    assert!(out
+        .stderr
+        .contains("1 | let x = [1, 2];\n2 | print(x[5]);\n  |       ^\n"));
+    assert!(out.stderr.contains("at line 2:7-8"));
+}
+
+#[test]
+fn carets_span_the_whole_name() {
+    let out = run("let x = 1;\nlet y = x / nope;\nprint(y);\n");
+    assert_eq!(out.errors(), ["E0x304"]);
+    assert!(out.stderr.contains(concat!(
+        "1 | let x = 1;\n",
+        "2 | let y = x / nope;\n",
+        "  |             ^^^^\n",
+        "3 | print(y);\n",
+    )));
+}
+
+#[test]
+fn tabs_keep_the_carets_aligned() {
+    let out = run("let x = 1;\n\tlet y = x / nope;\nprint(y);\n");
+    assert_eq!(out.errors(), ["E0x304"]);
+    assert!(out
+        .stderr
+        .contains("2 | \tlet y = x / nope;\n  | \t            ^^^^\n"));
+    assert!(out.stderr.contains("at line 2:17-21"));
+}
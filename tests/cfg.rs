@@ -0,0 +1,36 @@
+mod common;
+use common::{run, run_with};
+
+const GATED: &str = concat!(
+    "@cfg(linux) func f() -> void { print(\"linux\"); }\n",
+    "@cfg(not(linux)) func f() -> void { print(\"other\"); }\n",
+    "f();\n",
+);
+
+#[test]
+fn with_the_flag() {
+    let out = run_with(GATED, &["--cfg", "linux"]);
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "linux\n");
+}
+
+#[test]
+fn without_the_flag() {
+    let out = run(GATED);
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "other\n");
+}
+
+#[test]
+fn gated_function_is_absent() {
+    let out = run("@cfg(linux) func f() -> void { print(\"linux\"); }\nf();\n");
+    assert_eq!(out.errors(), ["E0x304"]);
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn combined_flags() {
+    let src = "@cfg(and(linux, or(x86, arm))) let x = 1;\nprint(x);\n";
+    assert_eq!(run_with(src, &["--cfg", "linux,arm"]).stdout, "1\n");
+    assert_eq!(run_with(src, &["--cfg", "linux"]).errors(), ["E0x304"]);
+}
@@ -0,0 +1,49 @@
+mod common;
+use common::run;
+
+#[test]
+fn char_ranges() {
+    let out = run("print('a'..'e');\nprint('a'..<'e');\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "[a, b, c, d, e]\n[a, b, c, d]\n");
+}
+
+#[test]
+fn reversed_char_range() {
+    let out = run("print('e'..'a');\n");
+    assert_eq!(out.errors(), ["E0x437"]);
+    assert!(out
+        .stderr
+        .contains("the char range 'e'..'a' is reversed, its start is after its end"));
+}
+
+#[test]
+fn char_range_cases() {
+    let out = run(concat!(
+        "for c in ['q', 'Q', '5', '!'] {\n",
+        "    match c {\n",
+        "        'a'..'z' => print(\"lower\"),\n",
+        "        'A'..'Z' => print(\"upper\"),\n",
+        "        '0'..'9' => print(\"digit\"),\n",
+        "        _ => print(\"other\")\n",
+        "    }\n",
+        "}\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "lower\nupper\ndigit\nother\n");
+}
+
+#[test]
+fn number_range_cases() {
+    let out = run(concat!(
+        "for n in [3, 10, 99] {\n",
+        "    match n {\n",
+        "        0..<10 => print(\"small\"),\n",
+        "        10 => print(\"ten\"),\n",
+        "        _ => print(\"big\")\n",
+        "    }\n",
+        "}\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "small\nten\nbig\n");
+}
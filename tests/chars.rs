@@ -0,0 +1,24 @@
+mod common;
+use common::run;
+
+#[test]
+fn char_at_a_multibyte_char() {
+    let out = run("use char_at from std::literal::string;\nprint(char_at(\"héllo\", 1));\n");
+    assert_eq!(out.stdout, "é\n");
+}
+
+#[test]
+fn char_at_out_of_range() {
+    let out = run("use char_at from std::literal::string;\nprint(char_at(\"ab\", 5));\n");
+    assert!(out
+        .stderr
+        .contains("index 5 is out of range for a string of 2 chars"));
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn chars_of_a_string() {
+    let out =
+        run("use chars from std::literal::string;\nprint(chars(\"ab\"));\nprint(chars(\"\"));\n");
+    assert_eq!(out.stdout, "[a, b]\n[]\n");
+}
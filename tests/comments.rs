@@ -0,0 +1,17 @@
+mod common;
+use common::run;
+
+#[test]
+fn nested_block_comments() {
+    let out = run("/* a /* b */ c */ print(1);\n/* /* */ */\nprint(2);\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "1\n2\n");
+}
+
+#[test]
+fn unterminated_nested_comment() {
+    let out = run("print(1);\n/* a /* b */ c\nprint(2);\n");
+    assert_eq!(out.errors(), ["E0x113"]);
+    assert!(out.stderr.contains("at line 2:1-3"));
+    assert_eq!(out.stdout, "");
+}
@@ -0,0 +1,116 @@
+// runs programs with the built binary, like the benchmarks do
+#![allow(dead_code)]
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// numbers the source files, tests run in parallel
+static FILES: AtomicUsize = AtomicUsize::new(0);
+
+pub struct Output {
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl Output {
+    /// codes of the reported errors, in order
+    pub fn errors(&self) -> Vec<&str> {
+        self.codes(" error ")
+    }
+
+    /// codes of the reported warnings, in order
+    pub fn warnings(&self) -> Vec<&str> {
+        self.codes(" warning ")
+    }
+
+    fn codes(&self, kind: &str) -> Vec<&str> {
+        self.stderr
+            .lines()
+            .filter_map(|line| line.split_once(kind))
+            .filter_map(|(_, rest)| rest.split(':').next())
+            .filter(|code| code.starts_with("E0x"))
+            .collect()
+    }
+}
+
+/// a source file for `mod` to load, removed when dropped
+pub struct Module {
+    pub path: PathBuf,
+}
+
+impl Module {
+    pub fn new(source: &str) -> Self {
+        let path = temp_file();
+        fs::write(&path, source).expect("failed to write the module");
+        Module { path }
+    }
+}
+
+impl Drop for Module {
+    fn drop(&mut self) {
+        fs::remove_file(&self.path).ok();
+    }
+}
+
+/// a new path in the temporary directory
+fn temp_file() -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "absurd-test-{}-{}.abs",
+        std::process::id(),
+        FILES.fetch_add(1, Ordering::SeqCst)
+    ))
+}
+
+/// runs the binary with the arguments
+pub fn absurd(args: &[&str]) -> Output {
+    let output = Command::new(env!("CARGO_BIN_EXE_absurd"))
+        .current_dir(Path::new(env!("CARGO_MANIFEST_DIR")))
+        .args(args)
+        .output()
+        .expect("failed to run absurd");
+    Output {
+        stdout: strip_colors(&String::from_utf8_lossy(&output.stdout)),
+        stderr: strip_colors(&String::from_utf8_lossy(&output.stderr)),
+    }
+}
+
+/// runs the source as a file
+pub fn run(source: &str) -> Output {
+    run_with(source, &[])
+}
+
+/// runs the source as a file, with the options after it
+pub fn run_with(source: &str, options: &[&str]) -> Output {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let file = temp_file();
+    fs::write(&file, source).expect("failed to write the source");
+    let output = Command::new(env!("CARGO_BIN_EXE_absurd"))
+        .current_dir(root)
+        .arg("run")
+        .arg(&file)
+        .args(options)
+        .output()
+        .expect("failed to run absurd");
+    fs::remove_file(&file).ok();
+    Output {
+        stdout: strip_colors(&String::from_utf8_lossy(&output.stdout)),
+        stderr: strip_colors(&String::from_utf8_lossy(&output.stderr)),
+    }
+}
+
+fn strip_colors(text: &str) -> String {
+    let mut plain = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            // skips `ESC[...m`
+            chars.by_ref().find(|c| *c == 'm');
+        } else {
+            plain.push(c);
+        }
+    }
+    plain
+}
@@ -0,0 +1,27 @@
+mod common;
+use common::run;
+
+#[test]
+fn filtered_comprehension() {
+    let out = run("let arr = [3, -1, 2];\nprint([x * 2 for x in arr if x > 0]);\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "[6, 4]\n");
+}
+
+#[test]
+fn sources() {
+    let out = run(concat!(
+        "let arr = [3, -1, 2];\n",
+        "print([x + 1 for x in arr]);\n",
+        "print([x for x in 1..4]);\n",
+        "print([x for x in arr if x > 10]);\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "[4, 0, 3]\n[1, 2, 3, 4]\n[]\n");
+}
+
+#[test]
+fn source_which_is_not_a_vector() {
+    let out = run("print([x for x in 5]);\n");
+    assert_eq!(out.errors(), ["E0x301"]);
+}
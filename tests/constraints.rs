@@ -0,0 +1,27 @@
+mod common;
+use common::run;
+
+#[test]
+fn passing_constraint() {
+    let out = run(concat!(
+        "func input() -> number = 5;\n",
+        "let age: number where age >= 0 = input();\n",
+        "print(age);\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "5\n");
+}
+
+#[test]
+fn failing_constraint() {
+    let out = run(concat!(
+        "func input() -> number = -1;\n",
+        "let age: number where age >= 0 = input();\n",
+        "print(age);\n",
+    ));
+    assert_eq!(out.errors(), ["E0x417"]);
+    assert!(out
+        .stderr
+        .contains("'age' doesn't satisfy the constraint 'age >= 0'"));
+    assert_eq!(out.stdout, "");
+}
@@ -0,0 +1,41 @@
+mod common;
+use common::run;
+
+#[test]
+fn per_name_types() {
+    let out = run("let a: number, b: string = 1, \"x\";\nprint(a);\nprint(b);\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "1\nx\n");
+}
+
+#[test]
+fn per_name_value_count() {
+    let out = run("let a: number, b: string = 1;\nprint(a);\n");
+    assert_eq!(out.errors(), ["E0x108"]);
+}
+
+#[test]
+fn per_name_type_mismatch() {
+    // literals are checked by the resolver, before anything runs
+    let out = run("print(1);\nlet a: number, b: string = 1, 2;\nprint(b);\n");
+    assert_eq!(out.errors(), ["E0x432"]);
+    assert!(out.stderr.contains("'b' is declared as 'string'"));
+    assert_eq!(out.stdout, "");
+    // other values when they're evaluated
+    let out = run("func two() -> any = 2;\nlet a: number, b: string = 1, two();\nprint(b);\n");
+    assert_eq!(out.errors(), ["E0x301"]);
+}
+
+#[test]
+fn inferred_primitive_types() {
+    let out = run("let b = true;\nlet n = 2;\nlet s = \"x\";\nprint(b);\nprint(n);\nprint(s);\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "true\n2\nx\n");
+}
+
+#[test]
+fn declared_type_mismatch() {
+    let out = run("let d: bool = 1;\n");
+    assert_eq!(out.errors(), ["E0x432"]);
+    assert!(out.stderr.contains("'d' is declared as 'bool'"));
+}
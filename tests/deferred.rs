@@ -0,0 +1,23 @@
+mod common;
+use common::run;
+
+#[test]
+fn assigned_its_declared_type() {
+    let out = run("let x: number;\nx = 3;\nprint(x);\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "3\n");
+}
+
+#[test]
+fn assigned_another_type() {
+    let out = run("let x: number;\nx = \"a\";\nprint(x);\n");
+    assert_eq!(out.errors(), ["E0x412"]);
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn assigned_twice() {
+    let out = run("let x: number;\nx = 3;\nx = 4;\nprint(x);\n");
+    assert_eq!(out.errors(), ["E0x410"]);
+    assert_eq!(out.stdout, "");
+}
@@ -0,0 +1,46 @@
+mod common;
+use common::{run, run_with};
+
+const FUNCS: &str = concat!(
+    "@deprecated(\"use g\") func f() -> number = 1;\n",
+    "func g() -> number = 2;\n",
+);
+
+#[test]
+fn call_to_a_deprecated_function() {
+    let out = run(&format!("{FUNCS}print(f());\n"));
+    assert_eq!(out.warnings(), ["E0x424"]);
+    assert!(out.stderr.contains("'f' is deprecated: use g"));
+    assert_eq!(out.stdout, "1\n");
+}
+
+#[test]
+fn call_to_another_function() {
+    let out = run(&format!("{FUNCS}print(g());\n"));
+    assert_eq!(out.warnings(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "2\n");
+}
+
+#[test]
+fn without_a_message() {
+    let out = run("@deprecated func f() -> number = 1;\nprint(f());\n");
+    assert!(out.stderr.contains("'f' is deprecated, at line 2"));
+}
+
+#[test]
+fn deprecated_record() {
+    let out = run(concat!(
+        "@deprecated(\"use Q\") record P { x: number }\n",
+        "let p: P = { x: 1 };\n",
+        "print(p.x);\n",
+    ));
+    assert_eq!(out.warnings(), ["E0x424"]);
+    assert_eq!(out.stdout, "1\n");
+}
+
+#[test]
+fn denied_warnings() {
+    let out = run_with(&format!("{FUNCS}print(f());\n"), &["--deny-warnings"]);
+    assert_eq!(out.errors(), ["E0x424"]);
+    assert_eq!(out.stdout, "");
+}
@@ -0,0 +1,35 @@
+mod common;
+use common::run;
+
+#[test]
+fn functions() {
+    let out = run(concat!(
+        "func add(a: number, b: number) -> number = a + b;\n",
+        "print(add);\n",
+        "print(add(1));\n",
+        "print(|x: number| number: x);\n",
+        "print(print);\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "<func add>\n<func add>\n<func>\n<func print>\n");
+}
+
+#[test]
+fn values() {
+    let out = run(concat!(
+        "print(3);\n",
+        "print(2.5);\n",
+        "print('c');\n",
+        "print(true);\n",
+        "print(null);\n",
+        "print([1, 2.5, \"a\"]);\n",
+        "print({ a: 1, b: \"x\" });\n",
+        "let t = (1, \"a\");\n",
+        "print(t);\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(
+        out.stdout,
+        "3\n2.5\nc\ntrue\nnull\n[1, 2.5, a]\n{ a:1, b:x }\n(1, a)\n"
+    );
+}
@@ -0,0 +1,49 @@
+mod common;
+use common::{run, run_with};
+
+const LOG: &str = "@io func log(s: string) -> void { print(s); }\n";
+
+#[test]
+fn annotated_functions_run() {
+    let out = run(&format!(
+        "{LOG}@pure func twice(n: number) -> number = n * 2;\nlog(\"x\");\nprint(twice(2));\n"
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "x\n4\n");
+}
+
+#[test]
+fn pure_calling_io() {
+    let out = run(&format!(
+        "{LOG}@pure func f(n: number) -> number {{ log(\"a\"); return n; }}\n"
+    ));
+    assert_eq!(out.errors(), ["E0x439"]);
+    assert!(out
+        .stderr
+        .contains("'f' can't be pure: it calls 'log', which is '@io'"));
+}
+
+#[test]
+fn pure_reaching_side_effects() {
+    let out = run(concat!(
+        "func g(n: number) -> number { print(n); return n; }\n",
+        "@pure func f(n: number) -> number = g(n);\n",
+    ));
+    assert_eq!(out.errors(), ["E0x439"]);
+    assert!(out
+        .stderr
+        .contains("'f' can't be pure: it calls 'g', which calls 'print'"));
+}
+
+#[test]
+fn pure_reading_mutable_variables() {
+    let out = run("let mut k = 1;\n@pure func f(n: number) -> number = n + k;\n");
+    assert_eq!(out.errors(), ["E0x439"]);
+    assert!(out.stderr.contains("it reads the mutable variable 'k'"));
+}
+
+#[test]
+fn io_without_side_effects() {
+    let out = run_with(LOG, &["--side-effects"]);
+    assert_eq!(out.errors(), ["E0x415"]);
+}
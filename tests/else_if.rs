@@ -0,0 +1,37 @@
+mod common;
+use common::run;
+
+#[test]
+fn else_if_and_elif() {
+    let out = run(concat!(
+        "func grade(n: number) -> string {\n",
+        "    if n > 90 {\n",
+        "        return \"a\";\n",
+        "    } else if n > 80 {\n",
+        "        return \"b\";\n",
+        "    } elif n > 70 {\n",
+        "        return \"c\";\n",
+        "    } else {\n",
+        "        return \"f\";\n",
+        "    }\n",
+        "}\n",
+        "print(grade(95));\n",
+        "print(grade(85));\n",
+        "print(grade(75));\n",
+        "print(grade(10));\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "a\nb\nc\nf\n");
+}
+
+#[test]
+fn else_if_without_else() {
+    let out = run(concat!(
+        "let x = 1;\n",
+        "if x == 0 { print(\"zero\"); } else if x == 1 { print(\"one\"); }\n",
+        "if x == 0 { print(\"zero\"); } else if x == 2 { print(\"two\"); }\n",
+        "print(\"done\");\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "one\ndone\n");
+}
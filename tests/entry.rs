@@ -0,0 +1,51 @@
+mod common;
+use common::{run, run_with};
+
+#[test]
+fn runs_after_the_top_level() {
+    let out = run("@entry func start() -> void { print(\"started\"); }\nprint(\"top\");\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "top\nstarted\n");
+}
+
+#[test]
+fn takes_the_command_line_arguments() {
+    let out = run_with(
+        "@entry func start(args: Vec<string>) -> void { print(args); }\n",
+        &["x", "y"],
+    );
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "[x, y]\n");
+}
+
+#[test]
+fn replaces_main() {
+    let out = run(concat!(
+        "func main() -> void { print(\"main\"); }\n",
+        "@entry func start() -> void { print(\"start\"); }\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "start\n");
+}
+
+#[test]
+fn second_entry() {
+    let out = run("@entry func a() -> void {}\n@entry func b() -> void {}\n");
+    assert_eq!(out.errors(), ["E0x438"]);
+    assert!(out
+        .stderr
+        .contains("'b' can't be the entry: 'a' is already the entry"));
+}
+
+#[test]
+fn other_parameters() {
+    let out = run("@entry func a(n: number) -> void {}\n");
+    assert_eq!(out.errors(), ["E0x438"]);
+}
+
+#[test]
+fn nested_entry() {
+    let out = run("func outer() -> void { @entry func inner() -> void {} }\n");
+    assert_eq!(out.errors(), ["E0x438"]);
+    assert!(out.stderr.contains("it isn't declared at the top level"));
+}
@@ -0,0 +1,42 @@
+mod common;
+use common::run;
+
+#[test]
+fn string_escapes() {
+    let out = run(concat!(
+        "print(\"a\\\"b\");\n",
+        "print(\"tab\\tend\");\n",
+        "print(\"line\\nnext\");\n",
+        "print(\"back\\\\slash\");\n",
+        "print(\"nul[\\0]\");\n",
+        "print(\"quote\\'s\");\n",
+        "print(\"\\u{1F600} \\u{41}\");\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(
+        out.stdout,
+        "a\"b\ntab\tend\nline\nnext\nback\\slash\nnul[\0]\nquote's\n\u{1F600} A\n"
+    );
+}
+
+#[test]
+fn unknown_escape() {
+    let out = run("print(\"bad \\q\");\n");
+    assert_eq!(out.errors(), ["E0x111"]);
+    assert!(out.stderr.contains("unknown escape sequence '\\q'"));
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn char_escapes() {
+    let out = run("print('\\'');\nprint('\\u{41}');\nprint('\\n');\nprint('é');\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "'\nA\n\n\né\n");
+}
+
+#[test]
+fn malformed_chars_are_all_reported() {
+    let out = run("print('ab');\nprint('\\q');\nprint(1);\n");
+    assert_eq!(out.errors(), ["E0x101", "E0x111"]);
+    assert_eq!(out.stdout, "");
+}
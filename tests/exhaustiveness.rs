@@ -0,0 +1,40 @@
+mod common;
+use common::run;
+
+const COLOR: &str = "enum Color { Red, Green, Blue }\nlet c = Color::Blue;\n";
+
+#[test]
+fn every_item_without_a_default() {
+    let out = run(&format!(
+        "{COLOR}match c {{\n    Color::Red => print(\"r\"),\n    Color::Green => print(\"g\"),\n    Color::Blue => print(\"b\"),\n}}\n"
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "b\n");
+}
+
+#[test]
+fn missing_items_without_a_default() {
+    let out = run(&format!(
+        "print(0);\n{COLOR}match c {{\n    Color::Red => print(\"r\"),\n    Color::Green => print(\"g\"),\n}}\n"
+    ));
+    assert_eq!(out.errors(), ["E0x419"]);
+    assert!(out.stderr.contains("doesn't cover Color::Blue"));
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn default_for_missing_items() {
+    let out = run(&format!(
+        "{COLOR}match c {{\n    Color::Red => print(\"r\"),\n    _ => print(\"x\")\n}}\n"
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "x\n");
+}
+
+#[test]
+fn unreachable_default() {
+    let out = run(&format!(
+        "{COLOR}match c {{\n    Color::Red => print(\"r\"),\n    Color::Green => print(\"g\"),\n    Color::Blue => print(\"b\"),\n    _ => print(\"x\")\n}}\n"
+    ));
+    assert_eq!(out.errors(), ["E0x418"]);
+}
@@ -0,0 +1,19 @@
+mod common;
+use common::run;
+
+#[test]
+fn names_each_expected_token() {
+    let out = run("impl Point {\n}\n");
+    assert_eq!(out.errors(), ["E0x106"]);
+    assert!(out
+        .stderr
+        .contains("expected a token 'number' or 'string' or 'char' or 'bool', at line 1:6-11"));
+}
+
+#[test]
+fn accepts_any_expected_token() {
+    let out =
+        run("impl number {\n    func double(self) -> number = self * 2;\n}\nprint(3.double());\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "6\n");
+}
@@ -0,0 +1,26 @@
+mod common;
+use common::absurd;
+
+#[test]
+fn known_code() {
+    let out = absurd(&["--explain", "E0x301"]);
+    assert!(out.stdout.starts_with("E0x301 runtime error: type mismatch\n"));
+    assert!(out.stdout.contains("erroneous code:"));
+    assert_eq!(out.stderr, "");
+}
+
+#[test]
+fn lowercase_code() {
+    let out = absurd(&["--explain", "e0x301"]);
+    assert!(out.stdout.starts_with("E0x301 "));
+}
+
+#[test]
+fn unknown_code() {
+    // there are no `E0x2xx` codes
+    let out = absurd(&["--explain", "E0x204"]);
+    assert_eq!(out.stdout, "");
+    assert!(out
+        .stderr
+        .starts_with("no such error 'E0x204', codes are E0x101-E0x115, "));
+}
@@ -0,0 +1,33 @@
+mod common;
+use common::run;
+
+#[test]
+fn methods_on_primitives() {
+    let out = run(concat!(
+        "impl number {\n",
+        "    func double(self) -> number = self * 2;\n",
+        "}\n",
+        "impl string {\n",
+        "    func twice(self) -> string = self + self;\n",
+        "}\n",
+        "let n = 4;\n",
+        "print((5).double());\n",
+        "print(n.double());\n",
+        "print(\"ab\".twice());\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "10\n8\nabab\n");
+}
+
+#[test]
+fn conflicting_extensions() {
+    let out = run(concat!(
+        "impl number {\n",
+        "    func double(self) -> number = self * 2;\n",
+        "}\n",
+        "impl number {\n",
+        "    func double(self) -> number = self + self;\n",
+        "}\n",
+    ));
+    assert_eq!(out.errors(), ["E0x305"]);
+}
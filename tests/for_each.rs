@@ -0,0 +1,32 @@
+mod common;
+use common::run;
+
+#[test]
+fn callback_with_the_index() {
+    let out = run(concat!(
+        "use for_each from std::literal::vector;\n",
+        "for_each([5, 6], |x: number, i: number| void: print(\"{i}: {x}\"));\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "0: 5\n1: 6\n");
+}
+
+#[test]
+fn callback_without_the_index() {
+    let out = run(concat!(
+        "use for_each from std::literal::vector;\n",
+        "for_each([7], |x: number| void: print(x));\n",
+    ));
+    assert_eq!(out.stdout, "7\n");
+}
+
+#[test]
+fn declared_function() {
+    let out = run(concat!(
+        "use for_each from std::literal::vector;\n",
+        "func show(x: number) -> void = print(x * 2);\n",
+        "for_each([1, 2], show);\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "2\n4\n");
+}
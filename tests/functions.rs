@@ -0,0 +1,15 @@
+mod common;
+use common::run;
+
+#[test]
+fn arguments_take_their_parameter_types() {
+    let out = run("func len(s: string) -> number {\n    return 1;\n}\nprint(len(\"ab\"));\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "1\n");
+}
+
+#[test]
+fn arguments_of_other_types() {
+    let out = run("func len(s: string) -> number {\n    return 1;\n}\nprint(len(2));\n");
+    assert_eq!(out.errors(), ["E0x301"]);
+}
@@ -0,0 +1,53 @@
+mod common;
+use common::run;
+
+const MAX: &str = "func mx<T: comparable>(a: T, b: T) -> T = a > b ? a : b;\n";
+
+#[test]
+fn same_types() {
+    let out = run(&format!(
+        "{MAX}print(mx(1, 2));\nprint(mx(\"a\", \"b\"));\n"
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "2\nb\n");
+}
+
+#[test]
+fn mismatched_literals() {
+    let out = run(&format!("{MAX}print(mx(1, \"a\"));\n"));
+    assert_eq!(out.errors(), ["E0x442"]);
+    assert!(out
+        .stderr
+        .contains("the type parameter 'T' is 'number' from an earlier argument, got 'string'"));
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn mismatched_values() {
+    let out = run(&format!("{MAX}let s: any = \"a\";\nprint(mx(1, s));\n"));
+    assert_eq!(out.errors(), ["E0x442"]);
+    // an earlier argument of a partial application binds the type too
+    let out = run(&format!(
+        "{MAX}let f = mx(1);\nlet s: any = \"x\";\nprint(f(s));\n"
+    ));
+    assert_eq!(out.errors(), ["E0x442"]);
+}
+
+#[test]
+fn unbounded_type_parameter() {
+    let out = run(concat!(
+        "func same<T>(a: T, b: T) -> bool = a == b;\n",
+        "print(same(1, 1));\n",
+        "print(same(1, \"1\"));\n",
+    ));
+    assert_eq!(out.errors(), ["E0x442"]);
+}
+
+#[test]
+fn unsatisfied_bound() {
+    let out = run(&format!("{MAX}print(mx(true, false));\n"));
+    assert_eq!(out.errors(), ["E0x429"]);
+    assert!(out
+        .stderr
+        .contains("the type 'bool' doesn't satisfy the bound 'T: comparable'"));
+}
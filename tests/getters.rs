@@ -0,0 +1,56 @@
+mod common;
+use common::run;
+
+const RECORDS: &str = concat!(
+    "record A { x: number, get d -> number { return self.x * 2; } }\n",
+    "record B { x: number, get d -> number { return self.x * 3; } }\n",
+);
+
+#[test]
+fn computed_on_access() {
+    let out = run(concat!(
+        "record Rect { w: number, h: number, get area -> number { return self.w * self.h; } }\n",
+        "let r: Rect = { w: 2, h: 3 };\n",
+        "print(r.area);\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "6\n");
+}
+
+#[test]
+fn getter_of_the_declared_type() {
+    let out = run(&format!(
+        "{RECORDS}let a: A = {{ x: 1 }};\nlet b: B = {{ x: 1 }};\nprint(a.d);\nprint(b.d);\n"
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "2\n3\n");
+}
+
+#[test]
+fn getter_of_the_parameter_type() {
+    let out = run(&format!(
+        "{RECORDS}func show(v: B) -> void {{ print(v.d); }}\nshow({{ x: 2 }});\n"
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "6\n");
+}
+
+#[test]
+fn undeclared_values_are_matched_by_their_fields() {
+    let out = run(concat!(
+        "record A { x: number, get d -> number { return self.x * 2; } }\n",
+        "record C { x: number, y: number, get d -> number { return self.y; } }\n",
+        "let a = { x: 1 };\n",
+        "let c = { x: 1, y: 5 };\n",
+        "print(a.d);\n",
+        "print(c.d);\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "2\n5\n");
+}
+
+#[test]
+fn getter_named_as_a_field() {
+    let out = run("record A { x: number, get x -> number { return 1; } }\n");
+    assert_eq!(out.errors(), ["E0x305"]);
+}
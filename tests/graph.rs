@@ -0,0 +1,46 @@
+mod common;
+use common::{absurd, Module};
+use std::fs;
+
+#[test]
+fn modules_as_a_tree() {
+    let leaf = Module::new("func pub two() -> number = 2;\n");
+    let middle = Module::new(&format!(
+        "mod \"{}\";\nuse \"std::literal::number\" as n;\n",
+        leaf.path.display()
+    ));
+    let main = Module::new(&format!("mod \"{}\";\n", middle.path.display()));
+    let out = absurd(&["graph", &main.path.to_string_lossy()]);
+    assert_eq!(
+        out.stdout,
+        format!(
+            "{}\n  {}\n    {}\n    std::literal::number\n",
+            main.path.display(),
+            middle.path.display(),
+            leaf.path.display()
+        )
+    );
+}
+
+#[test]
+fn cycles_are_not_followed() {
+    let main = Module::new("");
+    let other = Module::new(&format!("mod \"{}\";\n", main.path.display()));
+    fs::write(&main.path, format!("mod \"{}\";\n", other.path.display())).unwrap();
+    let out = absurd(&["graph", &main.path.to_string_lossy()]);
+    assert_eq!(
+        out.stdout,
+        format!(
+            "{0}\n  {1}\n    {0} (cycle)\n",
+            main.path.display(),
+            other.path.display()
+        )
+    );
+}
+
+#[test]
+fn nothing_is_run() {
+    let main = Module::new("print(\"ran\");\n");
+    let out = absurd(&["graph", &main.path.to_string_lossy()]);
+    assert_eq!(out.stdout, format!("{}\n", main.path.display()));
+}
@@ -0,0 +1,72 @@
+mod common;
+use common::run;
+
+const VECTOR: &str = "use map, filter, reduce from std::literal::vector;\nlet v = [1, 2, 3, 4];\n";
+
+#[test]
+fn map_items() {
+    let out = run(&format!(
+        concat!(
+            "{}",
+            "print(map(v, |x: number| number: x * 10));\n",
+            "func sq(n: number) -> number = n * n;\n",
+            "print(map(v, sq));\n",
+            "print(map(v, |x|: x + 1));\n",
+            "print(map([], |x: number| number: x));\n",
+        ),
+        VECTOR
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(
+        out.stdout,
+        "[10, 20, 30, 40]\n[1, 4, 9, 16]\n[2, 3, 4, 5]\n[]\n"
+    );
+}
+
+#[test]
+fn callbacks_with_the_index() {
+    let out = run(&format!(
+        concat!(
+            "{}",
+            "print(map(v, |x: number, i: number| number: x * i));\n",
+            "print(filter(v, |x: number, i: number| bool: i > 1));\n",
+        ),
+        VECTOR
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "[0, 2, 6, 12]\n[3, 4]\n");
+}
+
+#[test]
+fn filter_items() {
+    let out = run(&format!(
+        "{VECTOR}print(filter(v, |x: number| bool: x % 2 == 0));\n"
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "[2, 4]\n");
+}
+
+#[test]
+fn reduce_items() {
+    let out = run(&format!(
+        concat!(
+            "{}",
+            "print(reduce(v, |acc: number, x: number| number: acc + x, 0));\n",
+            "print(reduce([], |acc: number, x: number| number: acc + x, 5));\n",
+        ),
+        VECTOR
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "10\n5\n");
+}
+
+#[test]
+fn not_a_function() {
+    let out = run(&format!("{VECTOR}print(map(v, 5));\n"));
+    assert!(
+        out.stderr.contains("expected a function, got number"),
+        "{}",
+        out.stderr
+    );
+    assert_eq!(out.stdout, "");
+}
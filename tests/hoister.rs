@@ -0,0 +1,155 @@
+mod common;
+use common::run;
+
+/// `next()` prints and returns 1, 2, 3... on each call
+const COUNTER: &str = concat!(
+    "let mut count = 0;\n",
+    "func next() -> number {\n",
+    "    count += 1;\n",
+    "    print(count);\n",
+    "    return count;\n",
+    "}\n",
+    "func half(n: number) -> number = n / 2;\n",
+);
+
+#[test]
+fn impure_call_runs_each_iteration() {
+    let out = run(&format!(
+        "{COUNTER}let k = 10;\nloop 3 {{ print(next() + half(k)); }}\nprint(count);\n"
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "1\n6\n2\n7\n3\n8\n3\n");
+}
+
+#[test]
+fn zero_iterations_compute_nothing() {
+    let out = run(&format!(
+        concat!(
+            "{}let s: any = \"a\";\n",
+            "let v: Vec<number> = [];\n",
+            "for i in v {{ print(next() + half(s)); }}\n",
+            "loop 0 {{ print(next() + half(s)); }}\n",
+            "while false {{ print(next() + half(s)); }}\n",
+            "print(count);\n",
+        ),
+        COUNTER
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "0\n");
+}
+
+#[test]
+fn hoisted_values_match_the_loop() {
+    let out = run(&format!(
+        concat!(
+            "{}let k = 10;\n",
+            "let mut t = 0;\n",
+            "for i in [1, 2, 3] {{ t = t + half(k) + i; }}\n",
+            "loop 3 {{ for x in [1, 2] {{ t = t + half(k + 2) * x; }} }}\n",
+            "print(t);\n",
+        ),
+        COUNTER
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "75\n");
+}
+
+#[test]
+fn constant_vectors_stay_unchanged() {
+    let out = run(concat!(
+        "func fresh() -> Vec<number> {\n",
+        "    return [1, 2];\n",
+        "}\n",
+        "let mut a = fresh();\n",
+        "a[1] = 5;\n",
+        "print(a);\n",
+        "print(fresh());\n",
+        "loop 2 {\n",
+        "    let mut v = [1, 2];\n",
+        "    v[0] = v[0] + 10;\n",
+        "    print(v);\n",
+        "}\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "[1, 5]\n[1, 2]\n[11, 2]\n[11, 2]\n");
+}
+
+#[test]
+fn mutable_vectors_are_built_each_time() {
+    let out = run(concat!(
+        "use push from std::literal::vector;\n",
+        "loop 2 {\n",
+        "    let mut v = [1, 2];\n",
+        "    v = push(v, 3);\n",
+        "    print(v);\n",
+        "}\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "[1, 2, 3]\n[1, 2, 3]\n");
+}
+
+#[test]
+fn vector_items_run_each_time() {
+    let out = run(&format!(
+        "{COUNTER}loop 2 {{ let v = [next(), 0]; print(v); }}\nprint(count);\n"
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "1\n[1, 0]\n2\n[2, 0]\n2\n");
+}
+
+#[test]
+fn branches_which_never_run() {
+    let out = run(concat!(
+        "let s: any = \"a\";\n",
+        "func half(n: number) -> number = n / 2;\n",
+        "let mut k = 0;\n",
+        "while k < 2 {\n",
+        "    if false {\n",
+        "        print(half(s));\n",
+        "    }\n",
+        "    k += 1;\n",
+        "}\n",
+        "print(k);\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "2\n");
+}
+
+#[test]
+fn arms_which_never_run() {
+    let out = run(&format!(
+        concat!(
+            "{}let s: any = \"a\";\n",
+            "for i in [1, 2] {{\n",
+            "    print(false ? half(s) : i);\n",
+            "    match i {{ 5 => print(half(s)), _ => print(0) }}\n",
+            "    for x in [] {{ print(half(s)); }}\n",
+            "}}\n",
+        ),
+        COUNTER
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "1\n0\n2\n0\n");
+}
+
+#[test]
+fn statements_after_a_return() {
+    let out = run(concat!(
+        "let s: any = \"a\";\n",
+        "func half(n: number) -> number = n / 2;\n",
+        "func first() -> number {\n",
+        "    let mut k = 0;\n",
+        "    while k < 2 {\n",
+        "        k += 1;\n",
+        "        if k > 0 {\n",
+        "            return k;\n",
+        "        }\n",
+        "        print(half(s));\n",
+        "    }\n",
+        "    return k;\n",
+        "}\n",
+        "print(first());\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "1\n");
+}
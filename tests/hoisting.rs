@@ -0,0 +1,34 @@
+mod common;
+use common::run;
+
+#[test]
+fn mutual_recursion() {
+    let out = run(concat!(
+        "func is_even(n: number) -> bool = n == 0 ? true : is_odd(n - 1);\n",
+        "func is_odd(n: number) -> bool = n == 0 ? false : is_even(n - 1);\n",
+        "print(is_even(4));\n",
+        "print(is_odd(4));\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "true\nfalse\n");
+}
+
+#[test]
+fn forward_calls() {
+    let out = run(concat!(
+        "print(later());\n",
+        "func later() -> string = \"top\";\n",
+        "if true {\n",
+        "    print(inner(2));\n",
+        "    func inner(n: number) -> number = n * 3;\n",
+        "}\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "top\n6\n");
+}
+
+#[test]
+fn undeclared_function() {
+    let out = run("print(nope());\n");
+    assert_eq!(out.errors(), ["E0x304"]);
+}
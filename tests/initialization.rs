@@ -0,0 +1,47 @@
+mod common;
+use common::run;
+
+#[test]
+fn read_in_its_own_initializer() {
+    let out = run("let x: number = x + 1;\nprint(x);\n");
+    assert_eq!(out.errors(), ["E0x420"]);
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn local_read_in_its_own_initializer() {
+    let out = run("func m() -> void {\n    let z = z;\n}\nm();\n");
+    assert_eq!(out.errors(), ["E0x420"]);
+}
+
+#[test]
+fn used_before_its_declaration() {
+    let out = run("print(y);\nlet y = 2;\n");
+    assert_eq!(out.errors(), ["E0x421"]);
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn forward_function_reference() {
+    let out = run(concat!(
+        "func f() -> number = g();\n",
+        "func g() -> number = 5;\n",
+        "print(f());\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "5\n");
+}
+
+#[test]
+fn function_reads_a_later_variable() {
+    let out = run("func f() -> number = k;\nlet k = 4;\nprint(f());\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "4\n");
+}
+
+#[test]
+fn recursive_callback() {
+    let out = run("let f = |n: number| number: n < 1 ? 0 : f(n - 1);\nprint(f(3));\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "0\n");
+}
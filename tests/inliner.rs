@@ -0,0 +1,64 @@
+mod common;
+use common::run;
+
+/// `next()` prints and returns 1, 2, 3... on each call
+const COUNTER: &str = concat!(
+    "let mut count = 0;\n",
+    "func next() -> number {\n",
+    "    count += 1;\n",
+    "    print(count);\n",
+    "    return count;\n",
+    "}\n",
+);
+
+#[test]
+fn arguments_run_in_order() {
+    let out = run(&format!(
+        "{COUNTER}@inline func sub(a: number, b: number) -> number = a - b;\nprint(sub(next(), next() * 10));\n"
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "1\n2\n-19\n");
+}
+
+#[test]
+fn argument_runs_once() {
+    let out = run(&format!(
+        "{COUNTER}@inline func twice(x: number) -> number = x + x;\nprint(twice(next()));\nprint(twice(5));\n"
+    ));
+    assert_eq!(out.stdout, "1\n2\n10\n");
+}
+
+#[test]
+fn recursion_isnt_inlined() {
+    let out =
+        run("@inline func down(n: number) -> number = n < 1 ? 0 : down(n - 1);\nprint(down(3));\n");
+    assert_eq!(out.errors(), ["E0x602"]);
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn mutual_recursion_isnt_inlined() {
+    let out = run(concat!(
+        "@inline func ping(n: number) -> number = n < 1 ? 0 : pong(n - 1);\n",
+        "@inline func pong(n: number) -> number = ping(n);\n",
+        "print(ping(3));\n",
+    ));
+    assert_eq!(out.errors()[0], "E0x602");
+}
+
+#[test]
+fn shadowed_names_keep_their_binding() {
+    let out = run(concat!(
+        "let k = 1;\n",
+        "@inline func add_k(x: number) -> number = x + k;\n",
+        "@inline func sq(x: number) -> number = x * x;\n",
+        "func g(x: number) -> number {\n",
+        "    let k = 100;\n",
+        "    return add_k(x) + sq(x + 1) + k;\n",
+        "}\n",
+        "print(g(2));\n",
+        "print(add_k(2));\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "112\n3\n");
+}
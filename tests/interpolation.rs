@@ -0,0 +1,39 @@
+mod common;
+use common::run;
+
+#[test]
+fn interpolated_expressions() {
+    let out = run(concat!(
+        "let a = 2;\n",
+        "print(\"x{a + 1}y\");\n",
+        "print(\"{a} and {a * a}\");\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "x3y\n2 and 4\n");
+}
+
+#[test]
+fn escaped_braces() {
+    let out = run(concat!(
+        "let a = 2;\n",
+        "print(\"\\{a\\}\");\n",
+        "print(\"{{a}}\");\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "{a}\n{a}\n");
+}
+
+#[test]
+fn nested_braces() {
+    let out = run("let r = {a: 1};\nprint(\"{ {a: 1} == r }\");\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "true\n");
+}
+
+#[test]
+fn tokens_after_the_expression() {
+    let out = run("let a = 1;\nprint(\"{a ; }\");\n");
+    assert_eq!(out.errors(), ["E0x103"]);
+    assert!(out.stderr.contains("unexpected token ';'"));
+    assert_eq!(out.stdout, "");
+}
@@ -0,0 +1,65 @@
+mod common;
+use common::run;
+
+#[test]
+fn surrogate_pairs() {
+    let out = run(concat!(
+        "use from_json from std::core::json;\n",
+        r#"print(from_json("\"\\uD83D\\uDE00 \\u00e9\""));"#,
+        "\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "\u{1F600} \u{e9}\n");
+}
+
+#[test]
+fn lone_surrogate() {
+    let out = run(concat!(
+        "use from_json from std::core::json;\n",
+        r#"print(from_json("\"\\uD83D x\""));"#,
+        "\n",
+    ));
+    assert!(out.stderr.contains("'\\ud83d' isn't a char"));
+}
+
+#[test]
+fn nesting_depth() {
+    let json = |depth: usize| {
+        format!(
+            "use from_json from std::core::json;\nprint(from_json(\"{}1{}\") != null);\n",
+            "[".repeat(depth),
+            "]".repeat(depth)
+        )
+    };
+    let out = run(&json(256));
+    assert_eq!(out.stdout, "true\n");
+    let out = run(&json(100_000));
+    assert!(out.stderr.contains("nest deeper than 256"));
+}
+
+#[test]
+fn nested_round_trip() {
+    let out = run(concat!(
+        "use to_json, from_json from std::core::json;\n",
+        "let v = { name: \"a\\\"b\", items: [[1, 2], [3.5, -4]], inner: { ok: true, none: null, tags: [\"x\", \"y\"] } };\n",
+        "let s = to_json(v);\n",
+        "print(s);\n",
+        "let back = from_json(s);\n",
+        "print(back);\n",
+        "print(to_json(back) == s);\n",
+        "let rows = [{ a: 1 }, { a: [2, { b: \"c\" }] }];\n",
+        "print(to_json(from_json(to_json(rows))) == to_json(rows));\n",
+        "print(from_json(to_json([[1], [2, [3]]])) == [[1], [2, [3]]]);\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(
+        out.stdout,
+        concat!(
+            "{\"name\":\"a\\\"b\",\"items\":[[1,2],[3.5,-4]],\"inner\":{\"ok\":true,\"none\":null,\"tags\":[\"x\",\"y\"]}}\n",
+            "{ name:a\"b, items:[[1, 2], [3.5, -4]], inner:{ ok:true, none:null, tags:[x, y] } }\n",
+            "true\n",
+            "true\n",
+            "true\n",
+        )
+    );
+}
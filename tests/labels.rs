@@ -0,0 +1,44 @@
+mod common;
+use common::run;
+
+const AREA: &str = "func area(w: number, h: number) -> number = w - h;\n";
+
+#[test]
+fn labeled_in_any_order() {
+    let out = run(&format!("{AREA}print(area(h: 3, w: 5));\n"));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "2\n");
+}
+
+#[test]
+fn positional_then_labeled() {
+    let out = run(&format!("{AREA}print(area(5, h: 3));\n"));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "2\n");
+}
+
+#[test]
+fn positional_after_labeled() {
+    let out = run(&format!("{AREA}print(area(w: 5, 3));\n"));
+    assert_eq!(out.errors(), ["E0x109"]);
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn unknown_label() {
+    let out = run(&format!("{AREA}print(area(5, height: 3));\n"));
+    assert_eq!(out.errors(), ["E0x422"]);
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn label_of_a_positional_argument() {
+    let out = run(&format!("{AREA}print(area(5, w: 3));\n"));
+    assert_eq!(out.errors(), ["E0x423"]);
+}
+
+#[test]
+fn duplicate_label() {
+    let out = run(&format!("{AREA}print(area(w: 5, w: 3));\n"));
+    assert_eq!(out.errors(), ["E0x423"]);
+}
@@ -0,0 +1,30 @@
+mod common;
+use common::run;
+
+#[test]
+fn vector_membership() {
+    let out = run(concat!(
+        "let v = [1, 2, 3];\n",
+        "print(2 in v);\n",
+        "print(4 in v);\n",
+        "print(1 + 1 in v);\n",
+        "print([1] in [[1], [2]]);\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "true\nfalse\ntrue\ntrue\n");
+}
+
+#[test]
+fn substrings() {
+    let out = run("print(\"ell\" in \"hello\");\nprint(\"x\" in \"hello\");\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "true\nfalse\n");
+}
+
+#[test]
+fn unsupported_operands() {
+    let out = run("print(1 in 5);\n");
+    assert_eq!(out.errors(), ["E0x435"]);
+    let out = run("print(1 in \"abc\");\n");
+    assert_eq!(out.errors(), ["E0x435"]);
+}
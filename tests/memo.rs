@@ -0,0 +1,56 @@
+mod common;
+use common::run;
+
+#[test]
+fn memoized_fib_runs_once_per_argument() {
+    let out = run(concat!(
+        "let mut calls = 0;\n",
+        "@memo func fib(n: number) -> number {\n",
+        "    calls += 1;\n",
+        "    return n < 2 ? n : fib(n - 1) + fib(n - 2);\n",
+        "}\n",
+        "print(fib(30));\n",
+        "print(calls);\n",
+        "print(fib(30));\n",
+        "print(calls);\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    // fib(0) to fib(30), each once, the second call is cached
+    assert_eq!(out.stdout, "832040\n31\n832040\n31\n");
+}
+
+#[test]
+fn reading_the_counter() {
+    let out = run(concat!(
+        "let mut calls = 0;\n",
+        "@memo func f(n: number) -> number {\n",
+        "    calls += 1;\n",
+        "    return n + calls;\n",
+        "}\n",
+    ));
+    assert_eq!(out.errors(), ["E0x434"]);
+    assert!(out
+        .stderr
+        .contains("'f' can't be memoized: it reads the mutable variable 'calls'"));
+}
+
+#[test]
+fn counter_in_the_result() {
+    let out = run("let mut calls = 0;\n@memo func f(n: number) -> number = n + (calls += 1);\n");
+    assert_eq!(out.errors(), ["E0x434"]);
+    assert!(out
+        .stderr
+        .contains("'f' can't be memoized: it changes 'calls'"));
+}
+
+#[test]
+fn pure_functions_keep_no_counters() {
+    let out = run(concat!(
+        "let mut calls = 0;\n",
+        "@pure func f(n: number) -> number {\n",
+        "    calls += 1;\n",
+        "    return n;\n",
+        "}\n",
+    ));
+    assert_eq!(out.errors(), ["E0x439"]);
+}
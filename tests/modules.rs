@@ -0,0 +1,32 @@
+mod common;
+use common::{run, Module};
+
+#[test]
+fn module_alias() {
+    let math = Module::new("func pub sqrt(n: number) -> number = n / 3;\nlet pub(k) k = 2;\n");
+    let out = run(&format!(
+        "mod \"{}\" as math;\nuse \"math\" as m;\nprint(m::sqrt(9));\nprint(m::k);\n",
+        math.path.display()
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "3\n2\n");
+}
+
+#[test]
+fn std_module_alias() {
+    let out = run("use \"std::literal::number\" as n;\nprint(n::sqrt(16));\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "4\n");
+}
+
+#[test]
+fn missing_module() {
+    let out = run("use \"math\" as m;\n");
+    assert_eq!(out.errors(), ["E0x416"]);
+}
+
+#[test]
+fn missing_member() {
+    let out = run("use \"std::literal::number\" as n;\nprint(n::nothing(1));\n");
+    assert_eq!(out.errors(), ["E0x304"]);
+}
@@ -0,0 +1,37 @@
+mod common;
+use common::run;
+
+#[test]
+fn moved_value() {
+    let out = run(concat!(
+        "func first(v: Vec<number>) -> number = v[0];\n",
+        "let a = [1, 2, 3];\n",
+        "let b = move a;\n",
+        "print(b);\n",
+        "print(first(move b));\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "[1, 2, 3]\n1\n");
+}
+
+#[test]
+fn used_after_a_move() {
+    let out = run("let a = [1, 2, 3];\nlet b = move a;\nprint(a);\n");
+    assert_eq!(out.errors(), ["E0x426"]);
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn assigned_again_after_a_move() {
+    let out = run("let mut a = [1];\nlet b = move a;\na = [2];\nprint(a);\nprint(b);\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "[2]\n[1]\n");
+}
+
+#[test]
+fn moved_inside_a_loop_or_function() {
+    let out = run("let a = [1];\nloop 2 {\n    let b = move a;\n}\n");
+    assert_eq!(out.errors(), ["E0x427"]);
+    let out = run("let a = [1];\nfunc f() -> void {\n    let b = move a;\n}\nf();\n");
+    assert_eq!(out.errors(), ["E0x427"]);
+}
@@ -0,0 +1,20 @@
+mod common;
+use common::run;
+
+#[test]
+fn is_nan() {
+    let out = run(concat!(
+        "use is_nan from std::literal::number;\n",
+        "print(is_nan(0 / 0));\n",
+        "print(is_nan(1));\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "true\nfalse\n");
+}
+
+#[test]
+fn ieee_equality() {
+    let out = run("let n = 0 / 0;\nprint(n == n);\nprint(n != n);\nprint(0 == -0);\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "false\ntrue\ntrue\n");
+}
@@ -0,0 +1,37 @@
+mod common;
+use common::run;
+
+#[test]
+fn nested_items_of_the_type() {
+    let out = run("let m: Vec<Vec<number>> = [[1, 2], [3, 4]];\nprint(m);\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "[[1, 2], [3, 4]]\n");
+}
+
+#[test]
+fn nested_item_of_another_type() {
+    let out = run("let m: Vec<Vec<number>> = [[1, 2], [\"a\", 4]];\nprint(m);\n");
+    assert_eq!(out.errors(), ["E0x433"]);
+    assert!(out
+        .stderr
+        .contains("item [1][0] of 'm' expected 'number', found 'string'"));
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn flat_items_of_a_nested_type() {
+    let out = run("let m: Vec<Vec<number>> = [1, 2];\nprint(m);\n");
+    assert_eq!(out.errors(), ["E0x433"]);
+    assert!(out
+        .stderr
+        .contains("item [0] of 'm' expected 'Vec<number>', found 'number'"));
+}
+
+#[test]
+fn bool_items() {
+    let out = run("let b: Vec<bool> = [true, 1];\nprint(b);\n");
+    assert_eq!(out.errors(), ["E0x433"]);
+    assert!(out
+        .stderr
+        .contains("item [1] of 'b' expected 'bool', found 'number'"));
+}
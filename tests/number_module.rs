@@ -0,0 +1,38 @@
+mod common;
+use common::run;
+
+#[test]
+fn constants() {
+    let out = run(
+        "use sqrt, PI, E from std::literal::number;\nprint(PI);\nprint(E);\nprint(sqrt(16));\n",
+    );
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "3.1415927\n2.7182817\n4\n");
+}
+
+#[test]
+fn constants_with_the_rest() {
+    let out = run(concat!(
+        "use * from std::literal::number;\n",
+        "print(PI * 2);\n",
+        "print(floor(PI));\n",
+        "print(round(E));\n",
+        "print(pow(2, 10));\n",
+        "print(abs(-3));\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "6.2831855\n3\n3\n1024\n3\n");
+}
+
+#[test]
+fn aliased_constant() {
+    let out = run("use PI as pi from std::literal::number;\nprint(pi);\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "3.1415927\n");
+}
+
+#[test]
+fn constants_are_immutable() {
+    let out = run("use * from std::literal::number;\nPI = 3;\n");
+    assert_eq!(out.errors(), ["E0x410"]);
+}
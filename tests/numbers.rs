@@ -0,0 +1,18 @@
+mod common;
+use common::run;
+
+#[test]
+fn underscores_between_digits() {
+    let out = run("print(1_000_000);\nprint(0xFF_FF);\nprint(1_0.2_5);\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "1000000\n65535\n10.25\n");
+}
+
+#[test]
+fn misplaced_underscores() {
+    for src in ["print(5_);\n", "print(5__0);\n", "print(0x_FF);\n"] {
+        let out = run(src);
+        assert_eq!(out.errors(), ["E0x112"], "{src}");
+        assert_eq!(out.stdout, "");
+    }
+}
@@ -0,0 +1,51 @@
+mod common;
+use common::run;
+
+#[test]
+fn operands_of_the_operator() {
+    let out = run(concat!(
+        "print(\"a\" + \"b\");\n",
+        "print(2 * [1, 2]);\n",
+        "print(\"a\" < \"b\");\n",
+        "print(2 ** 3);\n",
+        "print(1 == \"1\" || true);\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "ab\n[2, 4]\ntrue\n8\ntrue\n");
+}
+
+#[test]
+fn arithmetic_on_other_types() {
+    let out = run("print(\"a\" - 1);\n");
+    assert_eq!(out.errors(), ["E0x435"]);
+    assert!(out
+        .stderr
+        .contains("'-' can't be applied to 'string' and 'number'"));
+    assert_eq!(out.stdout, "");
+    let out = run("print(true * 2);\n");
+    assert_eq!(out.errors(), ["E0x435"]);
+}
+
+#[test]
+fn ordering_other_types() {
+    let out = run("print(1 < \"a\");\n");
+    assert_eq!(out.errors(), ["E0x435"]);
+    assert!(out
+        .stderr
+        .contains("'<' can't be applied to 'number' and 'string'"));
+}
+
+#[test]
+fn nested_operations() {
+    let out = run("print(!1 + 2);\n");
+    assert_eq!(out.errors(), ["E0x435"]);
+    assert!(out
+        .stderr
+        .contains("'+' can't be applied to 'bool' and 'number'"));
+}
+
+#[test]
+fn unknown_types_are_left_to_runtime() {
+    let out = run("let x: any = \"a\";\nprint(x - 1);\n");
+    assert!(!out.errors().contains(&"E0x435"), "{}", out.stderr);
+}
@@ -0,0 +1,42 @@
+mod common;
+use common::run;
+
+const AREA: &str =
+    "func area(w: number, /, h: number, *, scale: number) -> number = scale * (w - h);\n";
+
+#[test]
+fn markers_allow_their_calls() {
+    let out = run(&format!(
+        "{AREA}print(area(5, 3, scale: 2));\nprint(area(5, scale: 2, h: 3));\n"
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "4\n4\n");
+}
+
+#[test]
+fn labeled_positional_only() {
+    let out = run(&format!("{AREA}print(area(w: 5, h: 3, scale: 2));\n"));
+    assert_eq!(out.errors(), ["E0x430"]);
+    assert!(out
+        .stderr
+        .contains("'w' of 'area' is positional-only and can't be labeled"));
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn unlabeled_keyword_only() {
+    let out = run(&format!("{AREA}print(area(5, 3, 2));\n"));
+    assert_eq!(out.errors(), ["E0x431"]);
+    assert!(out
+        .stderr
+        .contains("'scale' of 'area' is keyword-only and must be labeled"));
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn misplaced_markers() {
+    let out = run("func f(a: number, /, /, b: number) -> number = a;\n");
+    assert_eq!(out.errors(), ["E0x103"]);
+    let out = run("func f(*, a: number, /) -> number = a;\n");
+    assert_eq!(out.errors(), ["E0x103"]);
+}
@@ -0,0 +1,47 @@
+mod common;
+use common::run;
+
+#[test]
+fn reports_each_statement_once() {
+    let out = run("let x = ;\nlet y = 1 +;\nprint(3);\n");
+    assert_eq!(out.errors(), ["E0x103", "E0x103"]);
+}
+
+#[test]
+fn skips_a_block_with_an_error() {
+    let out = run("loop 2.5 { print(1); }\nprint(2);\n");
+    assert_eq!(out.errors(), ["E0x114"]);
+}
+
+#[test]
+fn skips_else_of_an_if_with_an_error() {
+    let out = run("if true { let = 1; } else { print(1); }\nlet z = );\n");
+    assert_eq!(out.errors(), ["E0x106", "E0x103"]);
+}
+
+#[test]
+fn closes_brackets_left_open() {
+    let out = run("func f() -> number { let a = (1 + ; return 1; }\nprint(f());\nlet q = ;\n");
+    assert_eq!(out.errors(), ["E0x103", "E0x103"]);
+    let out = run("let v = [1, 2;\nprint(1);\nlet w = {a: };\n");
+    assert_eq!(out.errors(), ["E0x103", "E0x103"]);
+}
+
+#[test]
+fn skips_too_deep_nesting() {
+    let source = format!(
+        "let x = {}1{};\nlet y = ;\n",
+        "(".repeat(300),
+        ")".repeat(300)
+    );
+    let out = run(&source);
+    assert_eq!(out.errors(), ["E0x115", "E0x103"]);
+}
+
+#[test]
+fn reports_only_the_loop_count() {
+    let out = run("loop -3 { if true { print(1); } else { loop 1 { print(3); } } }\nprint(2);\n");
+    assert_eq!(out.errors(), ["E0x114"]);
+    let out = run("loop 2.5 { let v = [1, 2]; print(v); }\nlet x = 1;\n");
+    assert_eq!(out.errors(), ["E0x114"]);
+}
@@ -0,0 +1,39 @@
+mod common;
+use common::run;
+
+#[test]
+fn fewer_arguments_return_a_function() {
+    let out = run(concat!(
+        "func add(a: number, b: number) -> number = a + b;\n",
+        "let inc = add(1);\n",
+        "print(inc(2));\n",
+        "print(add(2, 3));\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "3\n5\n");
+}
+
+#[test]
+fn applied_in_steps() {
+    let out = run(concat!(
+        "func add3(a: number, b: number, c: number) -> number = a + b + c;\n",
+        "let f = add3(1);\n",
+        "let g = f(2);\n",
+        "print(g(3));\n",
+        "print(f(2, 3));\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "6\n6\n");
+}
+
+#[test]
+fn applied_by_label() {
+    let out = run(concat!(
+        "func area(w: number, h: number) -> number = w * h;\n",
+        "let tall = area(h: 2);\n",
+        "print(tall(5));\n",
+        "print(tall(w: 3));\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "10\n6\n");
+}
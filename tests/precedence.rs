@@ -0,0 +1,48 @@
+mod common;
+use common::{run, run_with};
+
+#[test]
+fn arithmetic_before_comparisons_before_logic() {
+    let out = run(concat!(
+        "print(1 + 2 * 3);\n",
+        "print((1 + 2) * 3);\n",
+        "print(10 - 4 - 3);\n",
+        "print(7 % 3 * 2);\n",
+        "print(1 + 2 == 3 && 2 < 3);\n",
+        "print(false || true && false);\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "7\n9\n3\n2\ntrue\nfalse\n");
+}
+
+#[test]
+fn power_is_right_associative() {
+    let out = run("print(2 ** 3 ** 2);\nprint(2 * 3 ** 2);\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "512\n18\n");
+}
+
+#[test]
+fn power_binds_tighter_than_minus() {
+    let out = run(concat!(
+        "let x = 3;\n",
+        "print(-2 ** 2);\n",
+        "print((-2) ** 2);\n",
+        "print(-x ** 2);\n",
+        "print(2 ** -1);\n",
+        "print(1 - -2 ** 2);\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "-4\n4\n-9\n0.5\n5\n");
+}
+
+#[test]
+fn transpiled_minus_keeps_the_order() {
+    let out = run_with("print(-2 ** 2);\nprint((-2) ** 2);\n", &["--target", "js"]);
+    assert!(out.stdout.contains("print((-(2 ** 2)));"), "{}", out.stdout);
+    assert!(
+        out.stdout.contains("print((((-2)) ** 2));"),
+        "{}",
+        out.stdout
+    );
+}
@@ -0,0 +1,55 @@
+mod common;
+use common::run;
+
+#[test]
+fn eval_matches_direct() {
+    let out = run("let x = 4;\nprint(x * 2 + 1);\nprint(eval(quote { x * 2 + 1 }));\n");
+    assert_eq!(out.stdout, "9\n9\n");
+}
+
+#[test]
+fn eval_in_caller_env() {
+    let out = run(concat!(
+        "let x = 1;\n",
+        "let q = quote { x + 1 };\n",
+        "func f() -> number {\n",
+        "    let x = 10;\n",
+        "    return eval(q);\n",
+        "}\n",
+        "print(eval(q));\n",
+        "print(f());\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.warnings(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "2\n11\n");
+}
+
+#[test]
+fn eval_names_declared_later() {
+    let out = run("let q = quote { y * 2 };\nlet y = 4;\nprint(eval(q));\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "8\n");
+}
+
+#[test]
+fn reflect_nodes() {
+    let out = run(concat!(
+        "let x = 1;\n",
+        "let top = reflect(quote { x + 1 })[0];\n",
+        "print(top.kind);\n",
+        "print(top.operator);\n",
+        "let left = top.left;\n",
+        "print(left.name);\n",
+        "let stmts = reflect(quote { let s = \"a {x}\"; print(s); });\n",
+        "let decl = stmts[0];\n",
+        "print(decl.kind);\n",
+        "let value = decl.value;\n",
+        "print(value.value);\n",
+        "let call = stmts[1];\n",
+        "print(call.kind);\n",
+    ));
+    assert_eq!(
+        out.stdout,
+        "binary\n+\nx\nlet\na {x}\nexpression statement\n"
+    );
+}
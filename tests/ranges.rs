@@ -0,0 +1,40 @@
+mod common;
+use common::run;
+
+#[test]
+fn inclusive_and_exclusive() {
+    let out = run(concat!(
+        "let n = 4;\n",
+        "print(1..n);\n",
+        "print(0..<n);\n",
+        "print(3..1);\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "[1, 2, 3, 4]\n[0, 1, 2, 3]\n[]\n");
+}
+
+#[test]
+fn looser_than_arithmetic() {
+    let out = run(concat!(
+        "let n = 4;\n",
+        "print(0..<n - 1);\n",
+        "print(3 in 0..n + 1);\n",
+        "print(5 in 0..<5);\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "[0, 1, 2]\ntrue\nfalse\n");
+}
+
+#[test]
+fn loops_over_a_range() {
+    let out = run("for i in 1..3 { print(i); }\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "1\n2\n3\n");
+}
+
+#[test]
+fn open_range() {
+    let out = run("for i in 0.. { print(i); }\n");
+    assert_eq!(out.errors(), ["E0x103"]);
+    assert_eq!(out.stdout, "");
+}
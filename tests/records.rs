@@ -0,0 +1,34 @@
+mod common;
+use common::run;
+
+#[test]
+fn equal_in_any_field_order() {
+    let out = run(concat!(
+        "print({x: 1, y: 2} == {y: 2, x: 1});\n",
+        "print({x: 1, y: 2} != {y: 2, x: 1});\n",
+        "print({x: 1, y: 2} == {y: 1, x: 2});\n",
+        "print({x: 1, y: 2} == {x: 1, z: 2});\n",
+        "print({x: 1} == {x: 1, y: 2});\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "true\nfalse\nfalse\nfalse\nfalse\n");
+}
+
+#[test]
+fn fields_in_declaration_order() {
+    let source = concat!(
+        "record Point { x: number, y: number, z: number, w: number }\n",
+        "let p: Point = { w: 4, y: 2, z: 3, x: 1 };\n",
+        "let q = { d: 1, b: 2, c: 3, a: 4 };\n",
+        "print(p);\n",
+        "print(q);\n",
+    );
+    for _ in 0..3 {
+        let out = run(source);
+        assert_eq!(out.errors(), Vec::<&str>::new());
+        assert_eq!(
+            out.stdout,
+            "{ x:1, y:2, z:3, w:4 }\n{ d:1, b:2, c:3, a:4 }\n"
+        );
+    }
+}
@@ -0,0 +1,52 @@
+mod common;
+use common::{run, Module};
+
+#[test]
+fn undefined_names_stop_before_running() {
+    let out = run("print(1);\nprint(nope);\n");
+    assert_eq!(out.errors(), ["E0x304"]);
+    assert!(out
+        .stderr
+        .contains("failed to resolve 'nope', at line 2:7-11"));
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn block_bindings_end_with_the_block() {
+    let out = run("func f(a: number) -> void { { let v = a; print(v); } print(v); }\nf(1);\n");
+    assert_eq!(out.errors(), ["E0x304"]);
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn unread_bindings_warn() {
+    let out = run("let v = 1;\nfunc f() -> void { let w = 2; print(3); }\nf();\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.warnings(), ["E0x441", "E0x441"]);
+    assert!(out.stderr.contains("'v' is never read, at line 1:5-6"));
+    assert!(out.stderr.contains("'w' is never read, at line 2:24-25"));
+    assert_eq!(out.stdout, "3\n");
+}
+
+#[test]
+fn shadowed_bindings_are_read_separately() {
+    let out = run("let v = 1;\nfunc f() -> void { let v = 2; print(v); }\nf();\n");
+    assert_eq!(out.warnings(), ["E0x441"]);
+    assert!(out.stderr.contains("'v' is never read, at line 1:5-6"));
+    assert_eq!(out.stdout, "2\n");
+}
+
+#[test]
+fn public_bindings_are_read_by_other_modules() {
+    let out = run("let pub v = 1;\nlet pub(w) x = 2;\nprint(3);\n");
+    assert_eq!(out.warnings(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "3\n");
+
+    let module = Module::new("let pub k = 2;\n");
+    let out = run(&format!(
+        "mod \"{}\" as mm;\nuse \"mm\" as m;\nprint(m::k);\n",
+        module.path.display()
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "2\n");
+}
@@ -0,0 +1,65 @@
+mod common;
+use common::run;
+
+#[test]
+fn exhaustive_match_returns() {
+    let out = run(concat!(
+        "enum Color { Red, Green }\n",
+        "func name(c: Color) -> string {\n",
+        "    match c {\n",
+        "        Color::Red => { return \"red\"; }\n",
+        "        Color::Green => { return \"green\"; }\n",
+        "    }\n",
+        "}\n",
+        "print(name(Color::Green));\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "green\n");
+}
+
+#[test]
+fn if_returning_in_every_branch() {
+    let out = run(concat!(
+        "func sign(n: number) -> number {\n",
+        "    if n > 0 {\n",
+        "        return 1;\n",
+        "    } elif n < 0 {\n",
+        "        return -1;\n",
+        "    } else {\n",
+        "        return 0;\n",
+        "    }\n",
+        "}\n",
+        "print(sign(-3));\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "-1\n");
+}
+
+#[test]
+fn match_arm_without_a_return() {
+    let out = run(concat!(
+        "print(1);\n",
+        "enum Color { Red, Green }\n",
+        "func name(c: Color) -> string {\n",
+        "    match c {\n",
+        "        Color::Red => { return \"red\"; }\n",
+        "        Color::Green => { print(1); }\n",
+        "    }\n",
+        "}\n",
+    ));
+    // reported before running
+    assert_eq!(out.errors(), ["E0x406"]);
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn if_without_an_else() {
+    let out = run(concat!(
+        "func sign(n: number) -> number {\n",
+        "    if n > 0 {\n",
+        "        return 1;\n",
+        "    }\n",
+        "}\n",
+    ));
+    assert_eq!(out.errors(), ["E0x406"]);
+}
@@ -0,0 +1,24 @@
+mod common;
+use common::run;
+
+#[test]
+fn one_unknown_char() {
+    let out = run("let x = 1 $ + 2;\nprint(x);\n");
+    assert_eq!(out.errors(), ["E0x110"]);
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn every_bad_token_is_reported() {
+    let out = run("let x = 1 $ + 2;\nlet c = 'ab';\nprint(x);\n");
+    assert_eq!(out.errors(), ["E0x110", "E0x101"]);
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn columns_after_multi_line_tokens() {
+    let out = run("let s = \"a\nbc\"; let t = 1 $;\n/* x\ny */ let u = 2 ~;\n");
+    assert_eq!(out.errors(), ["E0x110", "E0x110"]);
+    assert!(out.stderr.contains("unknown character '$', at line 2:16-17"));
+    assert!(out.stderr.contains("unknown character '~', at line 4:16-17"));
+}
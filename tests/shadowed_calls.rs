@@ -0,0 +1,38 @@
+mod common;
+use common::run;
+
+#[test]
+fn function_called_normally() {
+    let out = run("func f() -> number = 1;\nprint(f());\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "1\n");
+}
+
+#[test]
+fn shadowed_by_a_callback() {
+    let out = run(concat!(
+        "func f() -> number = 1;\n",
+        "func g() -> void {\n",
+        "    let f = |x: number| number: x * 5;\n",
+        "    print(f(2));\n",
+        "}\n",
+        "g();\n",
+        "print(f());\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "10\n1\n");
+}
+
+#[test]
+fn shadowed_by_a_number() {
+    let out = run(concat!(
+        "func f() -> number = 1;\n",
+        "func g() -> void {\n",
+        "    let f = 2;\n",
+        "    print(f());\n",
+        "}\n",
+        "g();\n",
+    ));
+    assert_eq!(out.errors(), ["E0x425"]);
+    assert_eq!(out.stdout, "");
+}
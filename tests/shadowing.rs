@@ -0,0 +1,56 @@
+mod common;
+use common::run;
+
+#[test]
+fn nested_shadowing_changes_type() {
+    let out = run(concat!(
+        "let x: number = 1;\n",
+        "{\n",
+        "    let x: string = \"inner\";\n",
+        "    print(x);\n",
+        "}\n",
+        "func f() -> void {\n",
+        "    let x: bool = true;\n",
+        "    print(x);\n",
+        "}\n",
+        "f();\n",
+        "print(x);\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "inner\ntrue\n1\n");
+}
+
+#[test]
+fn same_scope_redeclaration() {
+    let out = run(concat!(
+        "func f() -> void {\n",
+        "    let a: number = 1;\n",
+        "    let a: string = \"x\";\n",
+        "    print(a);\n",
+        "}\n",
+        "f();\n",
+    ));
+    assert_eq!(out.errors(), ["E0x305"]);
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn top_level_redeclaration() {
+    let out = run("let a: number = 1;\nlet a: string = \"x\";\nprint(a);\n");
+    assert_eq!(out.errors(), ["E0x305"]);
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn shadowing_a_top_level_variable() {
+    let out = run(concat!(
+        "let a = 1;\n",
+        "if true {\n",
+        "    let a = \"x\";\n",
+        "    print(a);\n",
+        "}\n",
+        "print(a);\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "x\n1\n");
+}
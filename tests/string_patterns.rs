@@ -0,0 +1,34 @@
+mod common;
+use common::run;
+
+const ROUTE: &str = concat!(
+    "func route(req: string) -> void {\n",
+    "    match req {\n",
+    "        \"GET \" ++ path => print(\"get {path}\"),\n",
+    "        name ++ \".abs\" => print(\"file {name}\"),\n",
+    "        \"POST\" => print(\"post\"),\n",
+    "        _ => print(\"other\")\n",
+    "    }\n",
+    "}\n",
+);
+
+#[test]
+fn prefix_binds_the_rest() {
+    let out = run(&format!("{ROUTE}route(\"GET /a\");\nroute(\"GET \");\n"));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "get /a\nget \n");
+}
+
+#[test]
+fn suffix_binds_the_start() {
+    let out = run(&format!("{ROUTE}route(\"main.abs\");\n"));
+    assert_eq!(out.stdout, "file main\n");
+}
+
+#[test]
+fn other_strings_match_by_equality() {
+    let out = run(&format!(
+        "{ROUTE}route(\"POST\");\nroute(\"PUT\");\nroute(\"GET\");\n"
+    ));
+    assert_eq!(out.stdout, "post\nother\nother\n");
+}
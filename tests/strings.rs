@@ -0,0 +1,60 @@
+mod common;
+use common::run;
+
+const STRING: &str = "use * from std::literal::string;\n";
+
+#[test]
+fn split_by_a_separator() {
+    let out = run(&format!(
+        "{STRING}print(split(\"a,b,,c\", \",\"));\nprint(split(\"abc\", \";\"));\n"
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "[a, b, , c]\n[abc]\n");
+}
+
+#[test]
+fn substr_by_chars() {
+    let out = run(&format!(
+        "{STRING}print(substr(\"héllo\", 1, 3));\nprint(substr(\"abc\", 0, 0));\n"
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "él\n\n");
+}
+
+#[test]
+fn substr_out_of_range() {
+    let out = run(&format!("{STRING}print(substr(\"abc\", 1, 5));\n"));
+    assert!(out
+        .stderr
+        .contains("range 1..5 is out of range for a string of 3 chars"));
+    assert_eq!(out.stdout, "");
+    let out = run(&format!("{STRING}print(substr(\"abc\", 2, 1));\n"));
+    assert!(out
+        .stderr
+        .contains("range 2..1 is out of range for a string of 3 chars"));
+}
+
+#[test]
+fn bytes_and_chars() {
+    let out = run(&format!(
+        "{STRING}print(len(\"hé\"));\nprint(chars_count(\"hé\"));\n"
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "3\n2\n");
+}
+
+#[test]
+fn existing_functions() {
+    let out = run(&format!(
+        concat!(
+            "{}",
+            "print(contains(\"hello\", \"ell\"));\n",
+            "print(replace(\"a-b\", \"-\", \"+\"));\n",
+            "print(trim(\"  x \"));\n",
+            "print(to_uppercase(\"ab\"));\n",
+        ),
+        STRING
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "true\na+b\nx\nAB\n");
+}
@@ -0,0 +1,45 @@
+mod common;
+use common::run;
+
+#[test]
+fn picks_a_branch() {
+    let out = run(concat!(
+        "let a = 3;\n",
+        "print(a > 2 ? \"big\" : \"small\");\n",
+        "let b = a < 0 ? 0 : a * 2;\n",
+        "print(b);\n",
+        "print(1 + (a > 2 ? 10 : 20));\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "big\n6\n11\n");
+}
+
+#[test]
+fn nests_to_the_right() {
+    let out = run(concat!(
+        "func sign(n: number) -> number = n > 0 ? 1 : n < 0 ? -1 : 0;\n",
+        "print(sign(-4));\n",
+        "print(sign(0));\n",
+        "print(true ? false ? 1 : 2 : 3);\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "-1\n0\n2\n");
+}
+
+#[test]
+fn runs_one_branch() {
+    let out = run(concat!(
+        "func p(s: string) -> number { print(s); return 1; }\n",
+        "let x = true ? p(\"yes\") : p(\"no\");\n",
+        "print(x);\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "yes\n1\n");
+}
+
+#[test]
+fn missing_else_branch() {
+    let out = run("let a = 1;\nprint(a > 0 ? 1);\n");
+    assert_eq!(out.errors(), ["E0x106"]);
+    assert_eq!(out.stdout, "");
+}
@@ -0,0 +1,39 @@
+mod common;
+use common::run_with;
+
+#[test]
+fn each_test_starts_from_the_same_globals() {
+    let out = run_with(
+        concat!(
+            "use assert from std::core::test;\n",
+            "let mut count = 0;\n",
+            "@test func first() -> void { count += 1; assert(count == 1, \"first\"); }\n",
+            "@test func second() -> void { count += 1; assert(count == 1, \"second\"); }\n",
+        ),
+        &["-t"],
+    );
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert!(!out.stdout.contains("fail"), "{}", out.stdout);
+    assert_eq!(out.stdout.matches("success").count(), 2);
+}
+
+#[test]
+fn failed_tests_leave_no_state() {
+    let out = run_with(
+        concat!(
+            "use assert from std::core::test;\n",
+            "let mut count = 0;\n",
+            "let mut name = \"\";\n",
+            "@test func fails() -> void { count += 5; name = \"fails\"; assert(count == 1, \"fails\"); }\n",
+            "@test func passes() -> void { count += 1; assert(count == 1 && name == \"\", \"passes\"); }\n",
+        ),
+        &["-t"],
+    );
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert!(out.stdout.contains("fail: test 'fails'"), "{}", out.stdout);
+    assert!(
+        out.stdout.contains("success: test 'passes'"),
+        "{}",
+        out.stdout
+    );
+}
@@ -0,0 +1,42 @@
+mod common;
+use common::run;
+
+const FOR_EACH: &str = "use for_each from std::literal::vector;\nlet v = [1, 2];\n";
+
+#[test]
+fn statement_after_a_trailing_closure() {
+    let out = run(&format!(
+        "{FOR_EACH}for_each(v) |x: number| void {{ print(x); }};\nprint(\"after\");\n"
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "1\n2\nafter\n");
+}
+
+#[test]
+fn trailing_closure_of_a_method() {
+    let out = run(concat!(
+        "impl number { func apply(self, h: |number| number) -> number = h(self); }\n",
+        "let n = 3;\n",
+        "print(n.apply() |x: number| number: x * 2);\n",
+        "print(\"after\");\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "6\nafter\n");
+}
+
+#[test]
+fn missing_terminator() {
+    let out = run(&format!(
+        "{FOR_EACH}for_each(v) |x: number| void {{ print(x); }}\nprint(\"after\");\n"
+    ));
+    assert_eq!(out.errors(), ["E0x106"]);
+    assert!(out.stderr.contains("expected a token ';'"));
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn call_without_a_terminator() {
+    let out = run("print(1)\nprint(2);\n");
+    assert_eq!(out.errors(), ["E0x106"]);
+    assert_eq!(out.stdout, "");
+}
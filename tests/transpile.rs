@@ -0,0 +1,65 @@
+mod common;
+use common::{run, run_with};
+use std::{fs, process::Command};
+
+const PROGRAM: &str = concat!(
+    "func add(a: number, b: number) -> number = a + b;\n",
+    "let v = [1, 2, 3];\n",
+    "let mut total = 0;\n",
+    "for x in v {\n",
+    "    total += x;\n",
+    "}\n",
+    "if total > 5 {\n",
+    "    print(add(total, 1));\n",
+    "} else {\n",
+    "    print(0);\n",
+    "}\n",
+    "let mut i = 0;\n",
+    "while i < 2 {\n",
+    "    i += 1;\n",
+    "}\n",
+    "print(i);\n",
+);
+
+#[test]
+fn emits_javascript() {
+    let out = run_with(PROGRAM, &["--target", "js"]);
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    for construct in [
+        "function add(a, b) {",
+        "const v = [1, 2, 3];",
+        "let total = 0;",
+        "for (const x of v) {",
+        "if (total > 5) {",
+        "} else {",
+        "while (i < 2) {",
+        "print(add(total, 1));",
+    ] {
+        assert!(out.stdout.contains(construct), "missing `{construct}`");
+    }
+}
+
+#[test]
+fn runs_like_the_interpreter() {
+    let Ok(node) = Command::new("node").arg("--version").output() else {
+        return;
+    };
+    if !node.status.success() {
+        return;
+    }
+    let js = run_with(PROGRAM, &["--target", "js"]).stdout;
+    let file = std::env::temp_dir().join(format!("absurd-test-{}.js", std::process::id()));
+    fs::write(&file, js).expect("failed to write the script");
+    let output = Command::new("node")
+        .arg(&file)
+        .output()
+        .expect("failed to run node");
+    fs::remove_file(&file).ok();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), run(PROGRAM).stdout);
+}
+
+#[test]
+fn unsupported_construct() {
+    let out = run_with("let q = quote { 1 };\n", &["--target", "js"]);
+    assert_eq!(out.errors(), ["E0x601"]);
+}
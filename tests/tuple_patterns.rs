@@ -0,0 +1,57 @@
+mod common;
+use common::run;
+
+#[test]
+fn positions_and_bindings() {
+    let out = run(concat!(
+        "func f(p: Tuple<(number, number)>) -> string {\n",
+        "    match p {\n",
+        "        (0, y) => { return \"y axis at {y}\"; }\n",
+        "        (x, 0) if x > 10 => { return \"far x {x}\"; }\n",
+        "        (x, 0) => { return \"x axis at {x}\"; }\n",
+        "        _ => { return \"somewhere\"; }\n",
+        "    }\n",
+        "}\n",
+        "let a = (0, 5);\n",
+        "let b = (3, 0);\n",
+        "let c = (30, 0);\n",
+        "let d = (1, 1);\n",
+        "print(f(a));\n",
+        "print(f(b));\n",
+        "print(f(c));\n",
+        "print(f(d));\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(
+        out.stdout,
+        "y axis at 5\nx axis at 3\nfar x 30\nsomewhere\n"
+    );
+}
+
+#[test]
+fn nested_patterns() {
+    let out = run(concat!(
+        "let inner = (\"x\", true);\n",
+        "let n = (1, inner);\n",
+        "match n {\n",
+        "    (1, (\"y\", _)) => print(\"no\"),\n",
+        "    (1, (s, true)) => print(\"nested {s}\"),\n",
+        "    _ => print(\"default\")\n",
+        "}\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "nested x\n");
+}
+
+#[test]
+fn patterns_of_another_length() {
+    let out = run(concat!(
+        "let m = (2, 3, 4);\n",
+        "match m {\n",
+        "    (a, b) => print(\"two\"),\n",
+        "    _ => print(\"three\")\n",
+        "}\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "three\n");
+}
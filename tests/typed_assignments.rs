@@ -0,0 +1,41 @@
+mod common;
+use common::run;
+
+#[test]
+fn assignments_of_the_declared_type() {
+    let out = run("let mut v: Vec<number> = [1, 2];\nv[1] = 5;\nprint(v);\nv = [7];\nprint(v);\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "[1, 5]\n[7]\n");
+}
+
+#[test]
+fn reassigned_variable_keeps_its_type() {
+    let out = run("let mut n: number = 1;\nn = \"a\";\nprint(n);\n");
+    assert_eq!(out.errors(), ["E0x412"]);
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn item_of_another_type() {
+    let out = run("let mut v: Vec<number> = [1, 2];\nv[0] = \"x\";\nprint(v);\n");
+    assert_eq!(out.errors(), ["E0x433"]);
+    assert!(out
+        .stderr
+        .contains("item [0] of 'v' expected 'number', found 'string'"));
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn vector_of_another_type() {
+    let out = run("let mut v: Vec<number> = [1, 2];\nv = [\"a\"];\nprint(v);\n");
+    assert_eq!(out.errors(), ["E0x433"]);
+}
+
+#[test]
+fn nested_item_of_another_type() {
+    let out = run("let mut m: Vec<Vec<number>> = [[1]];\nm[0][0] = \"a\";\nprint(m);\n");
+    assert_eq!(out.errors(), ["E0x433"]);
+    assert!(out
+        .stderr
+        .contains("item [0][0] of 'm' expected 'number', found 'string'"));
+}
@@ -0,0 +1,28 @@
+mod common;
+use common::run;
+
+#[test]
+fn mixed_items_infer_any() {
+    let out = run(concat!(
+        "let mut v = [1, \"a\"];\n",
+        "v[0] = true;\n",
+        "let w: Vec<any> = v;\n",
+        "print(w);\n",
+    ));
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "[true, a]\n");
+}
+
+#[test]
+fn same_items_infer_their_type() {
+    let out = run("let mut v = [1, 2];\nv[0] = \"b\";\nprint(v);\n");
+    assert_eq!(out.errors(), ["E0x433"]);
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn annotated_mixed_items() {
+    let out = run("let v: Vec<number> = [1, \"a\"];\nprint(v);\n");
+    assert_eq!(out.errors(), ["E0x433"]);
+    assert_eq!(out.stdout, "");
+}
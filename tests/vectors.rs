@@ -0,0 +1,26 @@
+mod common;
+use common::run;
+
+#[test]
+fn reads_items_in_bounds() {
+    let out = run("let v = [1, 2];\nlet t = (3, \"a\");\nprint(v[1]);\nprint(t[1]);\n");
+    assert_eq!(out.errors(), Vec::<&str>::new());
+    assert_eq!(out.stdout, "2\na\n");
+}
+
+#[test]
+fn out_of_bounds_read() {
+    let out = run("let v = [1, 2];\nprint(v[2]);\n");
+    assert_eq!(out.errors(), ["E0x436"]);
+    assert!(out.stderr.contains("index 2 is out of bounds for length 2"));
+}
+
+#[test]
+fn negative_and_fractional_indices() {
+    let out = run("let v = [1, 2];\nprint(v[-1]);\n");
+    assert_eq!(out.errors(), ["E0x436"]);
+    let out = run("let v = [1, 2];\nprint(v[0.5]);\n");
+    assert_eq!(out.errors(), ["E0x436"]);
+    let out = run("let mut v = [1, 2];\nv[1.5] = 3;\n");
+    assert_eq!(out.errors(), ["E0x436"]);
+}